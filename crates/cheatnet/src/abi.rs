@@ -0,0 +1,241 @@
+use crate::conversions::felt_selector_from_name;
+use cairo_felt::Felt252;
+use cairo_lang_starknet::contract_class::ContractClass;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+// Types which are serialized into exactly one felt
+pub(crate) const SINGLE_FELT_TYPES: [&str; 15] = [
+    "core::felt252",
+    "core::integer::u8",
+    "core::integer::u16",
+    "core::integer::u32",
+    "core::integer::u64",
+    "core::integer::u128",
+    "core::integer::i8",
+    "core::integer::i16",
+    "core::integer::i32",
+    "core::integer::i64",
+    "core::integer::i128",
+    "core::starknet::contract_address::ContractAddress",
+    "core::starknet::class_hash::ClassHash",
+    "core::starknet::eth_address::EthAddress",
+    "core::starknet::storage_access::StorageAddress",
+];
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AbiParam {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AbiItem {
+    Function {
+        name: String,
+    },
+    L1Handler {
+        name: String,
+    },
+    Interface {
+        name: String,
+        items: Vec<AbiItem>,
+    },
+    Constructor {
+        name: String,
+        inputs: Vec<AbiParam>,
+    },
+    Struct {
+        name: String,
+        members: Vec<AbiParam>,
+    },
+    Enum {
+        name: String,
+        variants: Vec<AbiParam>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// The parts of a Sierra contract class ABI used by cheatnet.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ContractAbi {
+    pub items: Vec<AbiItem>,
+}
+
+#[derive(Deserialize)]
+struct SierraContractClassAbi {
+    abi: Option<Vec<AbiItem>>,
+}
+
+impl ContractAbi {
+    /// Reads the ABI from a Sierra contract class json.
+    /// Returns `None` if the class has no ABI or it could not be parsed.
+    #[must_use]
+    pub fn from_sierra(sierra: &str) -> Option<Self> {
+        let SierraContractClassAbi { abi } = serde_json::from_str(sierra).ok()?;
+        abi.map(|items| ContractAbi { items })
+    }
+
+    /// Reads the ABI of a Sierra contract class.
+    /// Returns `None` if the class has no ABI or it could not be parsed.
+    #[must_use]
+    pub fn from_contract_class(contract_class: &ContractClass) -> Option<Self> {
+        let abi = serde_json::to_value(contract_class.abi.as_ref()?).ok()?;
+        let items = serde_json::from_value(abi).ok()?;
+        Some(ContractAbi { items })
+    }
+
+    /// Number of felts the constructor expects as its calldata.
+    /// Returns `None` if there is no constructor or the length of its calldata is not fixed,
+    /// e.g. when it takes an array.
+    #[must_use]
+    pub fn constructor_calldata_length(&self) -> Option<usize> {
+        let inputs = self.items.iter().find_map(|item| match item {
+            AbiItem::Constructor { inputs, .. } => Some(inputs),
+            _ => None,
+        })?;
+
+        inputs
+            .iter()
+            .map(|input| self.serialized_length(&input.ty))
+            .sum()
+    }
+
+    /// Names of the functions, including the ones in interfaces, and L1 handlers by their selectors.
+    #[must_use]
+    pub fn function_names(&self) -> HashMap<Felt252, String> {
+        let mut names = HashMap::new();
+        collect_function_names(&self.items, &mut names);
+        names
+    }
+
+    fn serialized_length(&self, ty: &str) -> Option<usize> {
+        if ty == "()" {
+            return Some(0);
+        }
+        if SINGLE_FELT_TYPES.contains(&ty) {
+            return Some(1);
+        }
+
+        self.items.iter().find_map(|item| match item {
+            AbiItem::Struct { name, members } if name == ty => members
+                .iter()
+                .map(|member| self.serialized_length(&member.ty))
+                .sum(),
+            // Enums are serialized as the variant index followed by its data,
+            // so the length is only fixed if all variants have data of the same length
+            AbiItem::Enum { name, variants } if name == ty => {
+                let mut lengths = variants
+                    .iter()
+                    .map(|variant| self.serialized_length(&variant.ty));
+                let first = lengths.next().unwrap_or(Some(0))?;
+                lengths
+                    .all(|length| length == Some(first))
+                    .then_some(first + 1)
+            }
+            _ => None,
+        })
+    }
+}
+
+fn collect_function_names(items: &[AbiItem], names: &mut HashMap<Felt252, String>) {
+    for item in items {
+        match item {
+            AbiItem::Function { name } | AbiItem::L1Handler { name } => {
+                names.insert(felt_selector_from_name(name), name.clone());
+            }
+            AbiItem::Interface { items, .. } => collect_function_names(items, names),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn abi_from_json(json: &str) -> ContractAbi {
+        ContractAbi::from_sierra(&format!(r#"{{"abi": {json}}}"#)).unwrap()
+    }
+
+    #[test]
+    fn constructor_calldata_length_with_structs() {
+        let abi = abi_from_json(
+            r#"[
+                {"type": "function", "name": "foo", "inputs": [], "outputs": [], "state_mutability": "view"},
+                {"type": "struct", "name": "core::integer::u256", "members": [
+                    {"name": "low", "type": "core::integer::u128"},
+                    {"name": "high", "type": "core::integer::u128"}
+                ]},
+                {"type": "constructor", "name": "constructor", "inputs": [
+                    {"name": "name", "type": "core::felt252"},
+                    {"name": "supply", "type": "core::integer::u256"},
+                    {"name": "owner", "type": "core::starknet::contract_address::ContractAddress"}
+                ]}
+            ]"#,
+        );
+
+        assert_eq!(abi.constructor_calldata_length(), Some(4));
+    }
+
+    #[test]
+    fn constructor_calldata_length_with_enums() {
+        let abi = abi_from_json(
+            r#"[
+                {"type": "enum", "name": "core::bool", "variants": [
+                    {"name": "False", "type": "()"},
+                    {"name": "True", "type": "()"}
+                ]},
+                {"type": "enum", "name": "test::Mixed", "variants": [
+                    {"name": "A", "type": "()"},
+                    {"name": "B", "type": "core::felt252"}
+                ]},
+                {"type": "constructor", "name": "constructor", "inputs": [
+                    {"name": "flag", "type": "core::bool"}
+                ]}
+            ]"#,
+        );
+        assert_eq!(abi.constructor_calldata_length(), Some(1));
+        assert_eq!(abi.serialized_length("test::Mixed"), None);
+    }
+
+    #[test]
+    fn constructor_calldata_length_unknown() {
+        let abi = abi_from_json(
+            r#"[
+                {"type": "constructor", "name": "constructor", "inputs": [
+                    {"name": "values", "type": "core::array::Array::<core::felt252>"}
+                ]}
+            ]"#,
+        );
+        assert_eq!(abi.constructor_calldata_length(), None);
+
+        let abi = abi_from_json("[]");
+        assert_eq!(abi.constructor_calldata_length(), None);
+    }
+
+    #[test]
+    fn function_names_from_interfaces() {
+        let abi = abi_from_json(
+            r#"[
+                {"type": "impl", "name": "CounterImpl", "interface_name": "test::ICounter"},
+                {"type": "interface", "name": "test::ICounter", "items": [
+                    {"type": "function", "name": "increase", "inputs": [], "outputs": [], "state_mutability": "external"}
+                ]},
+                {"type": "function", "name": "get", "inputs": [], "outputs": [], "state_mutability": "view"},
+                {"type": "l1_handler", "name": "handle", "inputs": [], "outputs": [], "state_mutability": "external"}
+            ]"#,
+        );
+
+        assert_eq!(
+            abi.function_names(),
+            HashMap::from(
+                ["increase", "get", "handle"]
+                    .map(|name| (felt_selector_from_name(name), name.to_string()))
+            )
+        );
+    }
+}
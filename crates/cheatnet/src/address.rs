@@ -0,0 +1,87 @@
+use blockifier::execution::execution_utils::felt_to_stark_felt;
+use cairo_felt::Felt252;
+use starknet_api::core::{ClassHash, ContractAddress};
+use starknet_api::transaction::{Calldata, ContractAddressSalt};
+
+/// Calculates the address of a contract deployed by `deployer_address`, as described in the Starknet spec:
+/// `pedersen("STARKNET_CONTRACT_ADDRESS", deployer_address, salt, class_hash, pedersen(constructor_calldata))`
+/// modulo `2**251 - 256`, where `pedersen` hashes the elements followed by their count.
+#[must_use]
+pub fn calculate_contract_address(
+    salt: &Felt252,
+    class_hash: &ClassHash,
+    constructor_calldata: &[Felt252],
+    deployer_address: ContractAddress,
+) -> ContractAddress {
+    let salt = ContractAddressSalt(felt_to_stark_felt(salt));
+    let constructor_calldata = Calldata(
+        constructor_calldata
+            .iter()
+            .map(felt_to_stark_felt)
+            .collect::<Vec<_>>()
+            .into(),
+    );
+
+    starknet_api::core::calculate_contract_address(
+        salt,
+        *class_hash,
+        &constructor_calldata,
+        deployer_address,
+    )
+    .expect("Address reduced modulo 2**251 - 256 is always valid")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use starknet_api::hash::StarkFelt;
+
+    fn contract_address(hex: &str) -> ContractAddress {
+        ContractAddress::try_from(StarkFelt::try_from(hex).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn calculate_contract_address_without_calldata() {
+        let address = calculate_contract_address(
+            &Felt252::from(0),
+            &ClassHash(StarkFelt::from(1_u8)),
+            &[],
+            ContractAddress::default(),
+        );
+
+        assert_eq!(
+            address,
+            contract_address("0x42df23ea8cbcd7777efe093c7b9d5d0423d84ff4f81f5808a8959f08401d699")
+        );
+    }
+
+    #[test]
+    fn calculate_contract_address_with_calldata() {
+        let address = calculate_contract_address(
+            &Felt252::from(5),
+            &ClassHash(StarkFelt::from(0x123_u32)),
+            &[Felt252::from(1), Felt252::from(2), Felt252::from(3)],
+            ContractAddress::default(),
+        );
+
+        assert_eq!(
+            address,
+            contract_address("0x49915af01e6f3446025696563c38b23641746d5b43fe07c8f65661751220f4e")
+        );
+    }
+
+    #[test]
+    fn calculate_contract_address_with_deployer() {
+        let address = calculate_contract_address(
+            &Felt252::from(0x1234),
+            &ClassHash(StarkFelt::from(0x2a_u8)),
+            &[Felt252::from(100)],
+            contract_address("0x1e8480"),
+        );
+
+        assert_eq!(
+            address,
+            contract_address("0x25c64f4454a5709af46f8572a008841505c9f0667b1a2a290eb8359e4a1e7b3")
+        );
+    }
+}
@@ -5,16 +5,34 @@ use cairo_felt::Felt252;
 use cairo_vm::vm::errors::hint_errors::HintError;
 use cairo_vm::vm::errors::memory_errors::MemoryError;
 use cairo_vm::vm::errors::vm_errors::VirtualMachineError;
+use starknet_api::core::{ClassHash, ContractAddress};
 use starknet_api::StarknetApiError;
 use thiserror::Error;
 
+use crate::conversions::{class_hash_to_felt, felt_from_short_string};
+use crate::panic_data::PanicData;
+
+pub mod active_cheats;
+pub mod call;
+pub mod cheat_block_hash;
+pub mod cheat_execution_info;
 pub mod declare;
 pub mod deploy;
+pub mod elect;
+pub mod erc20_transfer;
 pub mod get_class_hash;
+pub mod invoke;
+pub mod l1_handler_execute;
 pub mod mock_call;
+pub mod nonce;
 pub mod prank;
 pub mod precalculate_address;
+pub mod replace_class;
 pub mod roll;
+pub mod spoof;
+pub mod spy_events;
+pub mod spy_messages_to_l1;
+pub mod storage;
 pub mod warp;
 
 // All errors that can be thrown from the hint executor have to be added here,
@@ -52,7 +70,7 @@ impl From<EnhancedHintError> for HintError {
 
 #[derive(Debug)]
 pub enum CheatcodeError {
-    Recoverable(Vec<Felt252>),        // Return error result in cairo
+    Recoverable(CheatcodeFailure),    // Return error result in cairo
     Unrecoverable(EnhancedHintError), // Fail whole test
 }
 
@@ -62,8 +80,110 @@ impl From<EnhancedHintError> for CheatcodeError {
     }
 }
 
+impl From<CheatcodeFailure> for CheatcodeError {
+    fn from(failure: CheatcodeFailure) -> Self {
+        CheatcodeError::Recoverable(failure)
+    }
+}
+
+/// Reason of a recoverable cheatcode failure
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheatcodeFailure {
+    MissingConstructor,
+    ContractNotDeployed(ContractAddress),
+    ContractPanic(Vec<Felt252>),
+    AddressOccupied,
+    InvalidConstructorCalldata {
+        expected: usize,
+        actual: usize,
+    },
+    ClassNotDeclared(ClassHash),
+    InvalidContractAddress(Felt252),
+    OutOfGas {
+        gas_limit: u64,
+        n_steps: usize,
+    },
+    // Deployment number `index` of a `deploy_many` batch failed
+    DeployFailed {
+        index: usize,
+        failure: Box<CheatcodeFailure>,
+    },
+    // Message of a panic caught because of `PanicPolicy::Recover`
+    InternalPanic(String),
+    // A transaction rejected before or after its execution, e.g. because of an insufficient max fee
+    // or fee token balance
+    TransactionRejected(String),
+}
+
+impl CheatcodeFailure {
+    /// Felts returned to the Cairo code as the panic data of the failed cheatcode
+    #[must_use]
+    pub fn panic_data(&self) -> Vec<Felt252> {
+        match self {
+            CheatcodeFailure::MissingConstructor => {
+                vec![felt_from_short_string("No constructor in contract")]
+            }
+            CheatcodeFailure::ContractNotDeployed(_) => {
+                vec![felt_from_short_string("Contract not deployed")]
+            }
+            CheatcodeFailure::ContractPanic(panic_data) => panic_data.clone(),
+            CheatcodeFailure::AddressOccupied => {
+                vec![felt_from_short_string("Address is already taken")]
+            }
+            CheatcodeFailure::InvalidConstructorCalldata { expected, actual } => vec![
+                felt_from_short_string("Invalid constructor calldata"),
+                felt_from_short_string(&format!("expected {expected}, got {actual}")),
+            ],
+            CheatcodeFailure::ClassNotDeclared(class_hash) => PanicData::from_message(&format!(
+                "Class hash 0x{} not declared",
+                class_hash_to_felt(*class_hash).to_str_radix(16)
+            ))
+            .into_felts(),
+            CheatcodeFailure::InvalidContractAddress(_) => {
+                vec![felt_from_short_string("Invalid contract address")]
+            }
+            CheatcodeFailure::OutOfGas { .. } => vec![felt_from_short_string("Out of gas")],
+            CheatcodeFailure::DeployFailed { index, failure } => {
+                let mut panic_data = vec![felt_from_short_string(&format!(
+                    "Deployment {index} failed"
+                ))];
+                panic_data.extend(failure.panic_data());
+                panic_data
+            }
+            CheatcodeFailure::InternalPanic(message) => {
+                PanicData::from_message(&format!("Cheatnet panicked: {message}")).into_felts()
+            }
+            CheatcodeFailure::TransactionRejected(reason) => {
+                PanicData::from_message(&format!("Transaction rejected: {reason}")).into_felts()
+            }
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct ContractArtifacts {
     pub sierra: String,
     pub casm: String,
 }
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Event {
+    pub from: ContractAddress,
+    pub keys: Vec<Felt252>,
+    pub data: Vec<Felt252>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct L2ToL1Message {
+    pub from: ContractAddress,
+    pub to_address: Felt252,
+    pub payload: Vec<Felt252>,
+}
+
+/// A call made during an execution, e.g. the constructor of a contract deployed by a factory.
+#[derive(Debug, PartialEq, Clone)]
+pub struct InnerCall {
+    pub contract_address: ContractAddress,
+    pub selector: Felt252,
+    pub ret_data: Vec<Felt252>,
+}
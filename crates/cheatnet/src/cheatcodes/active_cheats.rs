@@ -0,0 +1,159 @@
+use crate::cheatcodes::mock_call::MockedResponse;
+use crate::cheatcodes::spoof::TxInfoMock;
+use crate::state::{CheatSpan, CheatStatus};
+use crate::CheatnetState;
+use blockifier::execution::execution_utils::stark_felt_to_felt;
+use cairo_felt::Felt252;
+use starknet_api::core::{ContractAddress, EntryPointSelector};
+use std::collections::{BTreeSet, HashMap};
+
+/// Value a cheat overrides.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheatValue {
+    Roll(Felt252),
+    Warp(Felt252),
+    Prank(ContractAddress),
+    Elect(ContractAddress),
+    Spoof(TxInfoMock),
+    /// `ret_data` is `None` if the response depends on the calldata.
+    MockCall {
+        selector: EntryPointSelector,
+        ret_data: Option<Vec<Felt252>>,
+    },
+}
+
+/// A cheat which is currently applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheatDescription {
+    /// `None` for cheats applied to all contracts, e.g. a global warp.
+    pub target: Option<ContractAddress>,
+    pub value: CheatValue,
+    pub span: CheatSpan,
+}
+
+impl CheatnetState {
+    /// Lists the active cheats, global ones first, then ordered by the target address.
+    /// Cheats of the same target are ordered as roll, warp, prank, elect, spoof
+    /// and mocked calls by their selector.
+    #[must_use]
+    pub fn active_cheats(&self) -> Vec<CheatDescription> {
+        let cheatcode_state = &self.cheatcode_state;
+        let mut cheats = vec![];
+
+        if let Some(block_number) = &cheatcode_state.global_roll {
+            cheats.push(global_cheat(CheatValue::Roll(block_number.clone())));
+        }
+        if let Some(timestamp) = &cheatcode_state.global_warp {
+            cheats.push(global_cheat(CheatValue::Warp(timestamp.clone())));
+        }
+
+        let targets: BTreeSet<ContractAddress> = cheatcode_state
+            .rolled_contracts
+            .keys()
+            .chain(cheatcode_state.warped_contracts.keys())
+            .chain(cheatcode_state.pranked_contracts.keys())
+            .chain(cheatcode_state.elected_contracts.keys())
+            .chain(cheatcode_state.spoofed_contracts.keys())
+            .chain(cheatcode_state.mocked_functions.keys())
+            .copied()
+            .collect();
+
+        for target in targets {
+            push_cheat(
+                &mut cheats,
+                &cheatcode_state.rolled_contracts,
+                target,
+                |value| CheatValue::Roll(value.clone()),
+            );
+            push_cheat(
+                &mut cheats,
+                &cheatcode_state.warped_contracts,
+                target,
+                |value| CheatValue::Warp(value.clone()),
+            );
+            push_cheat(
+                &mut cheats,
+                &cheatcode_state.pranked_contracts,
+                target,
+                |value| CheatValue::Prank(*value),
+            );
+            push_cheat(
+                &mut cheats,
+                &cheatcode_state.elected_contracts,
+                target,
+                |value| CheatValue::Elect(*value),
+            );
+            push_cheat(
+                &mut cheats,
+                &cheatcode_state.spoofed_contracts,
+                target,
+                |value| CheatValue::Spoof(value.clone()),
+            );
+
+            if let Some(mocked_functions) = cheatcode_state.mocked_functions.get(&target) {
+                let mut mocked_functions: Vec<_> = mocked_functions.iter().collect();
+                mocked_functions.sort_by_key(|(selector, _)| **selector);
+
+                for (selector, response) in mocked_functions {
+                    let ret_data = match response {
+                        MockedResponse::RetData(ret_data) => Some(
+                            ret_data
+                                .iter()
+                                .map(|data| stark_felt_to_felt(*data))
+                                .collect(),
+                        ),
+                        MockedResponse::Matcher(_) => None,
+                    };
+                    cheats.push(CheatDescription {
+                        target: Some(target),
+                        value: CheatValue::MockCall {
+                            selector: *selector,
+                            ret_data,
+                        },
+                        span: CheatSpan::Indefinite,
+                    });
+                }
+            }
+        }
+
+        cheats
+    }
+
+    /// Stops every cheat listed by `active_cheats`, together with the cheated block hashes.
+    /// Deployed contracts, their storage and the event and message spies are kept.
+    pub fn clear_cheats(&mut self) {
+        let cheatcode_state = &mut self.cheatcode_state;
+        cheatcode_state.rolled_contracts.clear();
+        cheatcode_state.warped_contracts.clear();
+        cheatcode_state.pranked_contracts.clear();
+        cheatcode_state.elected_contracts.clear();
+        cheatcode_state.spoofed_contracts.clear();
+        cheatcode_state.mocked_functions.clear();
+        cheatcode_state.global_roll = None;
+        cheatcode_state.global_warp = None;
+        cheatcode_state.cheated_block_hashes.clear();
+    }
+}
+
+fn global_cheat(value: CheatValue) -> CheatDescription {
+    CheatDescription {
+        target: None,
+        value,
+        span: CheatSpan::Indefinite,
+    }
+}
+
+fn push_cheat<T>(
+    cheats: &mut Vec<CheatDescription>,
+    cheated_contracts: &HashMap<ContractAddress, CheatStatus<T>>,
+    target: ContractAddress,
+    to_value: impl Fn(&T) -> CheatValue,
+) {
+    if let Some(CheatStatus { value, span }) = cheated_contracts.get(&target) {
+        cheats.push(CheatDescription {
+            target: Some(target),
+            value: to_value(value),
+            span: *span,
+        });
+    }
+}
@@ -0,0 +1,172 @@
+use crate::conversions::felt_from_short_string;
+use crate::rpc::{
+    call_entry_point_with_n_steps, external_call_entry_point, panic_data_from_execution_error,
+};
+use crate::CheatnetState;
+use anyhow::anyhow;
+use blockifier::execution::entry_point::{CallEntryPoint, CallInfo};
+use blockifier::execution::errors::EntryPointExecutionError;
+use blockifier::execution::execution_utils::stark_felt_to_felt;
+use blockifier::state::cached_state::CachedState;
+use cairo_felt::Felt252;
+use cairo_lang_runner::short_string::as_cairo_short_string;
+use starknet_api::core::ContractAddress;
+
+use super::{CheatcodeError, CheatcodeFailure, EnhancedHintError};
+
+impl CheatnetState {
+    /// Calls the entry point of `contract_address` directly, without an account transaction,
+    /// and returns its return data. Changes the call makes to the state are discarded afterwards.
+    /// The call has no other side effects either: event and message spies don't see it,
+    /// and it doesn't count towards cheats limited to a number of calls.
+    pub fn call(
+        &mut self,
+        contract_address: ContractAddress,
+        selector: &Felt252,
+        calldata: &[Felt252],
+    ) -> Result<Vec<Felt252>, CheatcodeError> {
+        let entry_point = external_call_entry_point(
+            &contract_address,
+            selector,
+            calldata,
+            self.test_account_address(),
+            u64::MAX,
+        )
+        .map_err::<EnhancedHintError, _>(From::from)?;
+
+        self.call_entry_point_without_changes(entry_point)
+    }
+
+    // Works like `call`, but executes an arbitrary entry point, e.g. one with a different caller
+    pub(crate) fn call_entry_point_without_changes(
+        &mut self,
+        entry_point: CallEntryPoint,
+    ) -> Result<Vec<Felt252>, CheatcodeError> {
+        let (result, _) = self.execute_without_changes(entry_point);
+
+        match result {
+            Ok(call_info) => Ok(call_info
+                .execution
+                .retdata
+                .0
+                .into_iter()
+                .map(stark_felt_to_felt)
+                .collect()),
+            Err(error) => {
+                Err(CheatcodeFailure::ContractPanic(panic_data_from_execution_error(&error)).into())
+            }
+        }
+    }
+
+    /// Calls the entry point like `call` does and checks it panicked with exactly `expected`.
+    /// Fails unrecoverably, describing the actual outcome, if the call succeeded or
+    /// panicked with other data.
+    pub fn assert_reverts_with(
+        &mut self,
+        contract_address: ContractAddress,
+        selector: &Felt252,
+        calldata: &[Felt252],
+        expected: &[Felt252],
+    ) -> Result<(), CheatcodeError> {
+        let message = match self.call(contract_address, selector, calldata) {
+            Err(CheatcodeError::Recoverable(CheatcodeFailure::ContractPanic(panic_data)))
+                if panic_data == expected =>
+            {
+                return Ok(());
+            }
+            Err(CheatcodeError::Recoverable(CheatcodeFailure::ContractPanic(panic_data))) => {
+                format!(
+                    "Expected the call to revert with {}, but it reverted with {}",
+                    format_felts(expected),
+                    format_felts(&panic_data)
+                )
+            }
+            Err(error) => return Err(error),
+            Ok(ret_data) => format!(
+                "Expected the call to revert with {}, but it succeeded and returned {}",
+                format_felts(expected),
+                format_felts(&ret_data)
+            ),
+        };
+        Err(EnhancedHintError::from(anyhow!(message)).into())
+    }
+
+    /// Works like `call`, but the call can consume at most `gas_limit` gas.
+    /// Fails with `OutOfGas`, holding the number of steps executed until then, if the limit is exceeded.
+    pub fn call_with_gas(
+        &mut self,
+        contract_address: ContractAddress,
+        selector: &Felt252,
+        calldata: &[Felt252],
+        gas_limit: u64,
+    ) -> Result<Vec<Felt252>, CheatcodeError> {
+        let entry_point = external_call_entry_point(
+            &contract_address,
+            selector,
+            calldata,
+            self.test_account_address(),
+            gas_limit,
+        )
+        .map_err::<EnhancedHintError, _>(From::from)?;
+
+        let (result, n_steps) = self.execute_without_changes(entry_point);
+
+        match result {
+            Ok(call_info) => Ok(call_info
+                .execution
+                .retdata
+                .0
+                .into_iter()
+                .map(stark_felt_to_felt)
+                .collect()),
+            Err(error) => {
+                let panic_data = panic_data_from_execution_error(&error);
+                if panic_data == [felt_from_short_string("Out of gas")] {
+                    Err(CheatcodeFailure::OutOfGas { gas_limit, n_steps }.into())
+                } else {
+                    Err(CheatcodeFailure::ContractPanic(panic_data).into())
+                }
+            }
+        }
+    }
+
+    // Executes the entry point in a transactional state which is aborted afterwards,
+    // returning the number of VM steps executed like `call_entry_point_with_n_steps`
+    fn execute_without_changes(
+        &mut self,
+        entry_point: CallEntryPoint,
+    ) -> (Result<CallInfo, EntryPointExecutionError>, usize) {
+        let mut transactional_state = CachedState::create_transactional(&mut self.blockifier_state);
+        let discarding_changes =
+            std::mem::replace(&mut self.cheatcode_state.discarding_changes, true);
+
+        let output = call_entry_point_with_n_steps(
+            entry_point,
+            &mut transactional_state,
+            &mut self.cheatcode_state,
+            &self.block_context_config,
+        );
+
+        self.cheatcode_state.discarding_changes = discarding_changes;
+        transactional_state.abort();
+        output
+    }
+}
+
+// Felts which are printable short strings are shown as strings, e.g. `[0x0, 'Amount cannot be 0']`
+fn format_felts(felts: &[Felt252]) -> String {
+    let felts: Vec<String> = felts
+        .iter()
+        .map(|felt| {
+            as_cairo_short_string(felt)
+                .filter(|string| {
+                    !string.is_empty() && string.chars().all(|c| c.is_ascii_graphic() || c == ' ')
+                })
+                .map_or_else(
+                    || format!("0x{}", felt.to_str_radix(16)),
+                    |string| format!("'{string}'"),
+                )
+        })
+        .collect();
+    format!("[{}]", felts.join(", "))
+}
@@ -0,0 +1,49 @@
+use crate::{
+    cheatcodes::{CheatcodeError, EnhancedHintError},
+    CheatnetState,
+};
+use anyhow::anyhow;
+use cairo_felt::Felt252;
+
+impl CheatnetState {
+    /// Makes `get_block_hash_syscall` return `hash` for `block_number` in all contracts.
+    pub fn start_cheat_block_hash(&mut self, block_number: u64, hash: Felt252) {
+        self.cheatcode_state
+            .cheated_block_hashes
+            .insert(block_number, hash);
+    }
+
+    /// Works like `start_cheat_block_hash` for consecutive blocks: `start_block` gets the first
+    /// of `hashes`, every following block the next one.
+    /// Fails without cheating any block if the last block number doesn't fit in u64.
+    pub fn cheat_block_hashes(
+        &mut self,
+        start_block: u64,
+        hashes: &[Felt252],
+    ) -> Result<(), CheatcodeError> {
+        let block_numbers = (0..hashes.len())
+            .map(|i| {
+                u64::try_from(i)
+                    .ok()
+                    .and_then(|i| start_block.checked_add(i))
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| {
+                EnhancedHintError::from(anyhow!(
+                    "Cheating {} block hashes from block {start_block} overflows the block number",
+                    hashes.len()
+                ))
+            })?;
+
+        for (block_number, hash) in block_numbers.into_iter().zip(hashes) {
+            self.start_cheat_block_hash(block_number, hash.clone());
+        }
+        Ok(())
+    }
+
+    pub fn stop_cheat_block_hash(&mut self, block_number: u64) {
+        self.cheatcode_state
+            .cheated_block_hashes
+            .remove(&block_number);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::cheatcodes::spoof::TxInfoMock;
+use crate::CheatnetState;
+use cairo_felt::Felt252;
+use starknet_api::core::ContractAddress;
+
+/// Overrides of the fields of the block info, fields set to `None` keep their current values.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BlockInfoMock {
+    pub block_number: Option<Felt252>,
+    pub block_timestamp: Option<Felt252>,
+    pub sequencer_address: Option<ContractAddress>,
+}
+
+/// Overrides of the fields returned by `get_execution_info`,
+/// fields set to `None` keep their current values.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ExecutionInfoMock {
+    pub block_info: BlockInfoMock,
+    pub tx_info: TxInfoMock,
+    pub caller_address: Option<ContractAddress>,
+}
+
+impl CheatnetState {
+    /// Applies all overrides of `execution_info_mock` to `target` at once, until they are stopped.
+    /// Every field works like the corresponding `start_roll`, `start_warp`, `start_elect`,
+    /// `start_prank` or `start_spoof`, so the last override of a field wins,
+    /// no matter which of them set it. Fields of an active spoof which aren't overridden are kept.
+    pub fn cheat_execution_info(
+        &mut self,
+        target: ContractAddress,
+        execution_info_mock: ExecutionInfoMock,
+    ) {
+        let ExecutionInfoMock {
+            block_info:
+                BlockInfoMock {
+                    block_number,
+                    block_timestamp,
+                    sequencer_address,
+                },
+            tx_info,
+            caller_address,
+        } = execution_info_mock;
+
+        if let Some(block_number) = block_number {
+            self.start_roll(target, block_number);
+        }
+        if let Some(block_timestamp) = block_timestamp {
+            self.start_warp(target, block_timestamp);
+        }
+        if let Some(sequencer_address) = sequencer_address {
+            self.start_elect(target, sequencer_address);
+        }
+        if let Some(caller_address) = caller_address {
+            self.start_prank(target, caller_address);
+        }
+        if tx_info != TxInfoMock::default() {
+            let mut spoofed_tx_info = self
+                .cheatcode_state
+                .spoofed_contracts
+                .get(&target)
+                .map(|spoofed| spoofed.value.clone())
+                .unwrap_or_default();
+            spoofed_tx_info.override_with(tx_info);
+            self.start_spoof(target, spoofed_tx_info);
+        }
+    }
+}
@@ -1,14 +1,14 @@
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
 
-use crate::constants::{
-    build_block_context, build_declare_transaction, TEST_ACCOUNT_CONTRACT_ADDRESS,
-};
+use crate::abi::ContractAbi;
+use crate::constants::{build_block_context, build_declare_transaction};
 use crate::state::DictStateReader;
 use crate::{
     cheatcodes::{CheatcodeError, ContractArtifacts, EnhancedHintError},
     CheatnetState,
 };
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use blockifier::execution::contract_class::{
     ContractClass as BlockifierContractClass, ContractClassV1,
 };
@@ -18,24 +18,73 @@ use blockifier::transaction::account_transaction::AccountTransaction;
 use blockifier::transaction::transactions::{DeclareTransaction, ExecutableTransaction};
 use cairo_felt::Felt252;
 use serde_json;
-use starknet_api::core::{ClassHash, ContractAddress, PatriciaKey};
-use starknet_api::hash::{StarkFelt, StarkHash};
-use starknet_api::patricia_key;
+use sha3::{Digest, Keccak256};
+use starknet_api::core::ClassHash;
+use starknet_api::hash::StarkFelt;
 use starknet_api::transaction::TransactionHash;
 
 use cairo_lang_runner::short_string::as_cairo_short_string;
 use cairo_lang_starknet::casm_contract_class::CasmContractClass;
+use cairo_lang_starknet::compiler_version::current_compiler_version_id;
 use cairo_lang_starknet::contract_class::ContractClass;
 use starknet::core::types::contract::CompiledClass;
 
+// Artifacts compiled so far by all states in the process, by the Keccak hash of their Sierra json,
+// so tests declaring the same contract compile it only once
+static COMPILED_ARTIFACTS: OnceLock<Mutex<HashMap<[u8; 32], Arc<CompiledArtifact>>>> =
+    OnceLock::new();
+
+struct CompiledArtifact {
+    casm_json: String,
+    abi: Option<ContractAbi>,
+}
+
+impl CompiledArtifact {
+    fn compile(sierra_contract_class: ContractClass) -> Self {
+        let abi = ContractAbi::from_contract_class(&sierra_contract_class);
+
+        let casm_contract_class =
+            CasmContractClass::from_contract_class(sierra_contract_class, true)
+                .expect("Sierra to casm failed");
+        let casm_json = serde_json::to_string_pretty(&casm_contract_class)
+            .expect("Failed to serialize contract to casm");
+
+        CompiledArtifact { casm_json, abi }
+    }
+}
+
+fn compile_artifact(contract_artifact: &ContractArtifacts) -> Arc<CompiledArtifact> {
+    let sierra_hash: [u8; 32] = Keccak256::digest(&contract_artifact.sierra).into();
+    let cache = COMPILED_ARTIFACTS.get_or_init(Mutex::default);
+    if let Some(compiled) = cache
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&sierra_hash)
+    {
+        return compiled.clone();
+    }
+
+    // Compiled without holding the lock, so other contracts can be compiled in parallel
+    let sierra_contract_class: ContractClass = serde_json::from_str(&contract_artifact.sierra)
+        .unwrap_or_else(|_| panic!("Failed to parse json from artifact = {contract_artifact:?}"));
+    let compiled = Arc::new(CompiledArtifact::compile(sierra_contract_class));
+    cache
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(sierra_hash, compiled.clone());
+    compiled
+}
+
 impl CheatnetState {
+    /// Declares the contract with the given name and returns its class hash.
+    /// Declaring an already declared contract returns the same class hash again.
+    /// Artifacts are compiled once per process, further declarations of the same artifact,
+    /// also by other states, reuse the compiled class.
     pub fn declare(
         &mut self,
         contract_name: &Felt252,
         contracts: &HashMap<String, ContractArtifacts>,
     ) -> Result<ClassHash, CheatcodeError> {
-        let blockifier_state: &mut CachedState<DictStateReader> = &mut self.blockifier_state;
-
         let contract_name_as_short_str = as_cairo_short_string(contract_name)
             .context("Converting contract name to short string failed")
             .map_err::<EnhancedHintError, _>(From::from)?;
@@ -43,46 +92,81 @@ impl CheatnetState {
             format!("Failed to get contract artifact for name = {contract_name_as_short_str}. Make sure starknet target is correctly defined in Scarb.toml file.")
         }).map_err::<EnhancedHintError, _>(From::from)?;
 
-        let sierra_contract_class: ContractClass = serde_json::from_str(&contract_artifact.sierra)
-            .unwrap_or_else(|_| {
-                panic!("Failed to parse json from artifact = {contract_artifact:?}")
-            });
+        self.with_panic_policy(|state| {
+            let compiled = compile_artifact(contract_artifact);
+            state.declare_casm_with_abi(&compiled.casm_json, compiled.abi.clone())
+        })
+    }
 
-        let casm_contract_class =
-            CasmContractClass::from_contract_class(sierra_contract_class, true)
-                .expect("Sierra to casm failed");
-        let casm_serialized = serde_json::to_string_pretty(&casm_contract_class)
-            .expect("Failed to serialize contract to casm");
+    /// Declares the given Sierra contract class and returns its class hash.
+    /// Declaring an already declared class returns the same class hash again.
+    pub fn declare_contract_class(
+        &mut self,
+        sierra_contract_class: ContractClass,
+    ) -> Result<ClassHash, CheatcodeError> {
+        self.with_panic_policy(|state| {
+            let CompiledArtifact { casm_json, abi } =
+                CompiledArtifact::compile(sierra_contract_class);
+            state.declare_casm_with_abi(&casm_json, abi)
+        })
+    }
+
+    /// Declares a contract from its CASM contract class json, e.g. one compiled by a toolchain other than Scarb,
+    /// and returns its class hash. The class has to be compiled by Cairo of the same major version
+    /// cheatnet uses and not a newer one. The constructor calldata of such classes isn't validated by `deploy`,
+    /// as CASM doesn't hold the ABI.
+    pub fn declare_casm(&mut self, casm_json: &str) -> Result<ClassHash, CheatcodeError> {
+        let casm_contract_class: CasmContractClass = serde_json::from_str(casm_json)
+            .context("Failed to parse CASM contract class")
+            .map_err::<EnhancedHintError, _>(From::from)?;
+        check_casm_compiler_version(&casm_contract_class.compiler_version)
+            .map_err::<EnhancedHintError, _>(From::from)?;
 
-        let contract_class = ContractClassV1::try_from_json_string(&casm_serialized)
-            .expect("Failed to read contract class from json");
+        self.with_panic_policy(|state| state.declare_casm_with_abi(casm_json, None))
+    }
+
+    fn declare_casm_with_abi(
+        &mut self,
+        casm_json: &str,
+        abi: Option<ContractAbi>,
+    ) -> Result<ClassHash, CheatcodeError> {
+        let account_address = self.test_account_address();
+        let blockifier_state: &mut CachedState<DictStateReader> = &mut self.blockifier_state;
+
+        let contract_class = ContractClassV1::try_from_json_string(casm_json)
+            .context("Failed to read contract class from json")
+            .map_err::<EnhancedHintError, _>(From::from)?;
         let contract_class = BlockifierContractClass::V1(contract_class);
 
-        let class_hash =
-            get_class_hash(casm_serialized.as_str()).expect("Failed to get class hash");
+        let class_hash = get_class_hash(casm_json)
+            .context("Failed to get class hash")
+            .map_err::<EnhancedHintError, _>(From::from)?;
+
+        // Only the local state is checked, so no class is fetched from a forked network here
+        if blockifier_state
+            .state
+            .class_hash_to_class
+            .contains_key(&class_hash)
+        {
+            return Ok(class_hash);
+        }
 
         let nonce = blockifier_state
-            .get_nonce_at(ContractAddress(patricia_key!(
-                TEST_ACCOUNT_CONTRACT_ADDRESS
-            )))
+            .get_nonce_at(account_address)
             .expect("Failed to get nonce");
 
-        let declare_tx = build_declare_transaction(
-            nonce,
-            class_hash,
-            ContractAddress(patricia_key!(TEST_ACCOUNT_CONTRACT_ADDRESS)),
-        );
+        let declare_tx = build_declare_transaction(nonce, class_hash, account_address);
         let tx = DeclareTransaction::new(
             starknet_api::transaction::DeclareTransaction::V2(declare_tx),
             // TODO(#358)
             TransactionHash::default(),
-            contract_class,
+            contract_class.clone(),
         )
         .unwrap_or_else(|err| panic!("Unable to build transaction {err:?}"));
 
         let account_tx = AccountTransaction::Declare(tx);
-        let block_context = build_block_context();
-        match account_tx.execute(blockifier_state, &block_context, true, true) {
+        let block_context = build_block_context(&self.block_context_config);
+        match account_tx.execute(blockifier_state, &block_context, self.charge_fee, true) {
             Ok(_) => (),
             Err(e) => {
                 return Err(EnhancedHintError::Anyhow(anyhow!(format!(
@@ -91,11 +175,42 @@ impl CheatnetState {
                 .map_err(From::from)
             }
         };
+        // Kept in the state reader too, so the class survives rebuilding the cached state
+        blockifier_state
+            .state
+            .class_hash_to_class
+            .insert(class_hash, contract_class);
+        if let Some(abi) = abi {
+            self.function_names.insert(class_hash, abi.function_names());
+            self.class_abis.insert(class_hash, abi);
+        }
 
         Ok(class_hash)
     }
 }
 
+fn check_casm_compiler_version(compiler_version: &str) -> Result<()> {
+    let current = current_compiler_version_id();
+    // Pre-release suffixes, e.g. `-rc2`, are ignored
+    let version: Vec<usize> = compiler_version
+        .split(['.', '-'])
+        .take(3)
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .with_context(|| format!("Invalid CASM compiler version {compiler_version}"))?;
+
+    if version.len() != 3
+        || version[0] != current.major
+        || version.as_slice() > [current.major, current.minor, current.patch].as_slice()
+    {
+        bail!(
+            "Unsupported CASM compiler version {compiler_version}, expected a class compiled by Cairo {}.x not newer than {current}",
+            current.major
+        );
+    }
+    Ok(())
+}
+
 fn get_class_hash(casm_contract: &str) -> Result<ClassHash> {
     let compiled_class = serde_json::from_str::<CompiledClass>(casm_contract)?;
     let class_hash = compiled_class.class_hash()?;
@@ -115,8 +230,7 @@ mod test {
 
     use super::*;
 
-    #[test]
-    fn class_hash_correct() {
+    fn build_simple_package() -> TempDir {
         let temp = TempDir::new().unwrap();
         // TODO(#305) change to cheatnet data path
         temp.copy_from(
@@ -159,6 +273,12 @@ mod test {
             .unwrap();
         assert!(output.status.success());
 
+        temp
+    }
+
+    #[test]
+    fn class_hash_correct() {
+        let temp = build_simple_package();
         let temp_dir_path = temp.path();
 
         // expected_class_hash computed with
@@ -189,4 +309,42 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn compiled_artifact_is_reused() {
+        let temp = build_simple_package();
+        let artifact = |name: &str| {
+            std::fs::read_to_string(
+                temp.path()
+                    .join(format!("target/dev/simple_package_{name}")),
+            )
+            .unwrap()
+        };
+        let contract_artifact = ContractArtifacts {
+            sierra: artifact("HelloStarknet.sierra.json"),
+            casm: artifact("HelloStarknet.casm.json"),
+        };
+
+        let compiled = compile_artifact(&contract_artifact);
+        let cached = compile_artifact(&contract_artifact);
+
+        assert!(Arc::ptr_eq(&compiled, &cached));
+    }
+
+    #[test]
+    fn casm_compiler_versions() {
+        let current = current_compiler_version_id();
+
+        assert!(check_casm_compiler_version(&current.to_string()).is_ok());
+        assert!(check_casm_compiler_version(&format!("{}.0.0-rc0", current.major)).is_ok());
+
+        for unsupported in [
+            format!("{}.0.0", current.major - 1),
+            format!("{}.{}.0", current.major, current.minor + 1),
+            "2.x".to_string(),
+            "2".to_string(),
+        ] {
+            assert!(check_casm_compiler_version(&unsupported).is_err());
+        }
+    }
 }
@@ -1,169 +1,355 @@
-use crate::constants::{
-    build_block_context, build_invoke_transaction, TEST_ACCOUNT_CONTRACT_ADDRESS,
+use crate::abi::ContractAbi;
+use crate::cheatcodes::precalculate_address::calculate_deploy_address;
+use crate::rpc::{
+    call_entry_point_in_state, collect_events, collect_inner_calls, collect_l2_to_l1_messages,
+    panic_data_from_execution_error,
 };
 use crate::state::DictStateReader;
 use crate::{cheatcodes::EnhancedHintError, CheatnetState};
-use anyhow::{Context, Result};
-use blockifier::abi::abi_utils::selector_from_name;
+use anyhow::{anyhow, Result};
 use blockifier::execution::execution_utils::felt_to_stark_felt;
 
-use blockifier::execution::entry_point::CallInfo;
+use blockifier::execution::entry_point::{CallEntryPoint, CallType};
 use blockifier::state::cached_state::CachedState;
-use blockifier::state::state_api::StateReader;
-use blockifier::transaction::account_transaction::AccountTransaction;
-use blockifier::transaction::transactions::{ExecutableTransaction, InvokeTransaction};
+use blockifier::state::errors::StateError;
+use blockifier::state::state_api::{State, StateReader};
 use cairo_felt::Felt252;
+use cairo_lang_starknet::contract_class::ContractClass;
 
-use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector, PatriciaKey};
-use starknet_api::hash::{StarkFelt, StarkHash};
-use starknet_api::transaction::{
-    Calldata, ContractAddressSalt, InvokeTransactionV1, TransactionHash,
-};
-use starknet_api::{patricia_key, stark_felt};
+use starknet_api::core::{ClassHash, ContractAddress};
+use starknet_api::deprecated_contract_class::EntryPointType;
+use starknet_api::transaction::{Calldata, ContractAddressSalt};
+
+use super::{CheatcodeError, CheatcodeFailure, Event, InnerCall, L2ToL1Message};
 
-use super::CheatcodeError;
-use crate::conversions::felt_from_short_string;
-use crate::panic_data::try_extract_panic_data;
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeployResult {
+    pub contract_address: ContractAddress,
+    // Class the deployed contract was deployed with
+    pub class_hash: ClassHash,
+    pub gas_consumed: u64,
+    // Emitted by the constructor and the calls it made, in the order of emission
+    pub events: Vec<Event>,
+    pub l2_to_l1_messages: Vec<L2ToL1Message>,
+    // Calls made by the constructor, flattened in the order they were made
+    pub inner_calls: Vec<InnerCall>,
+}
+
+/// Controls how the address of a contract deployed with `deploy_opts` is derived.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeployOptions {
+    /// Salt of the address, the auto-incremented one `deploy` uses if `None`.
+    pub salt: Option<Felt252>,
+    /// Derives the address with 0 as the deployer, like the `deploy` syscall with
+    /// `deploy_from_zero` set does, instead of the test account address.
+    pub deploy_from_zero: bool,
+}
 
 impl CheatnetState {
+    /// Deploys a contract of the given class, salting the address with a counter incremented on
+    /// every deployment, so deploying the same class with the same calldata yields different addresses.
     pub fn deploy(
         &mut self,
         class_hash: &ClassHash,
         calldata: &[Felt252],
     ) -> Result<ContractAddress, CheatcodeError> {
-        // Deploy a contract using syscall deploy.
-        let account_address = ContractAddress(patricia_key!(TEST_ACCOUNT_CONTRACT_ADDRESS));
-        let block_context = build_block_context();
-        let entry_point_selector = selector_from_name("deploy_contract");
+        self.deploy_detailed(class_hash, calldata)
+            .map(|deploy_result| deploy_result.contract_address)
+    }
+
+    /// Deploys the given classes with their calldata in order, like `deploy` does.
+    /// Stops at the first failing deployment, reporting its index; the contracts deployed
+    /// before it stay deployed.
+    pub fn deploy_many(
+        &mut self,
+        deploys: &[(ClassHash, Vec<Felt252>)],
+    ) -> Result<Vec<ContractAddress>, CheatcodeError> {
+        deploys
+            .iter()
+            .enumerate()
+            .map(|(index, (class_hash, calldata))| {
+                self.deploy(class_hash, calldata)
+                    .map_err(|error| match error {
+                        CheatcodeError::Recoverable(failure) => CheatcodeFailure::DeployFailed {
+                            index,
+                            failure: Box::new(failure),
+                        }
+                        .into(),
+                        CheatcodeError::Unrecoverable(error) => EnhancedHintError::from(anyhow!(
+                            "Failed to deploy contract at index {index}: {error}"
+                        ))
+                        .into(),
+                    })
+            })
+            .collect()
+    }
+
+    /// Declares `contract_class`, unless it is already declared, and deploys it.
+    /// Returns the class hash together with the address of the deployed contract.
+    pub fn deploy_class(
+        &mut self,
+        contract_class: ContractClass,
+        calldata: &[Felt252],
+    ) -> Result<(ClassHash, ContractAddress), CheatcodeError> {
+        let class_hash = self.declare_contract_class(contract_class)?;
+        let contract_address = self.deploy(&class_hash, calldata)?;
+        Ok((class_hash, contract_address))
+    }
+
+    /// Deploys an account of the given class with the given salt and sets its balance of
+    /// `fee_token` to the `u256` amount given as its low and high parts, so the account can pay
+    /// for transactions. Returns the address of the account.
+    pub fn deploy_and_fund_account(
+        &mut self,
+        account_class: &ClassHash,
+        salt: Felt252,
+        constructor_calldata: &[Felt252],
+        fee_token: ContractAddress,
+        amount_low: u128,
+        amount_high: u128,
+    ) -> Result<ContractAddress, CheatcodeError> {
+        let account_address = self.deploy_with_salt(account_class, constructor_calldata, salt)?;
+        self.set_balance(fee_token, account_address, amount_low, amount_high)?;
+        Ok(account_address)
+    }
+
+    /// Works like `deploy`, but also returns the details of the deployment execution,
+    /// e.g. the events emitted by the constructor.
+    pub fn deploy_detailed(
+        &mut self,
+        class_hash: &ClassHash,
+        calldata: &[Felt252],
+    ) -> Result<DeployResult, CheatcodeError> {
         let salt = self.get_salt();
         self.increment_deploy_salt_base();
 
-        let blockifier_state: &mut CachedState<DictStateReader> = &mut self.blockifier_state;
+        let deployer_address = self.test_account_address();
+        self.deploy_with_contract_address_salt(class_hash, calldata, &salt, deployer_address)
+    }
+
+    /// Deploys a contract using the provided salt instead of the auto-incremented one.
+    /// The salt counter used by `deploy` is left untouched.
+    /// Deploying the same class with the same salt and calldata always yields the same address.
+    pub fn deploy_with_salt(
+        &mut self,
+        class_hash: &ClassHash,
+        calldata: &[Felt252],
+        salt: Felt252,
+    ) -> Result<ContractAddress, CheatcodeError> {
+        let salt = ContractAddressSalt(felt_to_stark_felt(&salt));
+
+        let deployer_address = self.test_account_address();
+        self.deploy_with_contract_address_salt(class_hash, calldata, &salt, deployer_address)
+            .map(|deploy_result| deploy_result.contract_address)
+    }
+
+    /// Deploys a contract with the address derived as described by `options`,
+    /// so tests can match the addresses of contracts deployed by the UDC or the `deploy` syscall.
+    pub fn deploy_opts(
+        &mut self,
+        class_hash: &ClassHash,
+        calldata: &[Felt252],
+        options: DeployOptions,
+    ) -> Result<ContractAddress, CheatcodeError> {
+        let salt = match options.salt {
+            Some(salt) => ContractAddressSalt(felt_to_stark_felt(&salt)),
+            None => {
+                let salt = self.get_salt();
+                self.increment_deploy_salt_base();
+                salt
+            }
+        };
+        let deployer_address = if options.deploy_from_zero {
+            ContractAddress::default()
+        } else {
+            self.test_account_address()
+        };
+
+        self.deploy_with_contract_address_salt(class_hash, calldata, &salt, deployer_address)
+            .map(|deploy_result| deploy_result.contract_address)
+    }
+
+    /// Deploys a contract of the given class at `contract_address` instead of the address
+    /// derived from the salt. The constructor is still executed, with the test account as the caller.
+    pub fn deploy_at(
+        &mut self,
+        class_hash: &ClassHash,
+        calldata: &[Felt252],
+        contract_address: ContractAddress,
+    ) -> Result<ContractAddress, CheatcodeError> {
+        self.deploy_from_test_account(class_hash, calldata, contract_address)
+            .map(|deploy_result| deploy_result.contract_address)
+    }
 
-        let contract_class = blockifier_state
+    /// Places a contract of the given class at `contract_address` without executing its constructor,
+    /// e.g. to set up the state after an upgrade with `store`. Its storage stays empty,
+    /// so the contract may be left in a state its constructor would never allow.
+    pub fn deploy_skip_constructor(
+        &mut self,
+        class_hash: &ClassHash,
+        contract_address: ContractAddress,
+    ) -> Result<ContractAddress, CheatcodeError> {
+        match self
+            .blockifier_state
             .get_compiled_contract_class(class_hash)
-            .map_err::<EnhancedHintError, _>(From::from)?;
-        if contract_class.constructor_selector().is_none() && !calldata.is_empty() {
-            return Err(CheatcodeError::Recoverable(vec![felt_from_short_string(
-                "No constructor in contract",
-            )]));
-        }
+        {
+            Err(StateError::UndeclaredClassHash(_)) => {
+                return Err(CheatcodeFailure::ClassNotDeclared(*class_hash).into());
+            }
+            result => result.map_err::<EnhancedHintError, _>(From::from)?,
+        };
 
-        let execute_calldata = create_execute_calldata(
-            calldata,
-            class_hash,
-            &account_address,
-            &entry_point_selector,
-            &salt,
-        );
-
-        let nonce = blockifier_state
-            .get_nonce_at(account_address)
-            .context("Failed to get nonce")
+        let deployed_class_hash = self
+            .blockifier_state
+            .get_class_hash_at(contract_address)
             .map_err::<EnhancedHintError, _>(From::from)?;
-        let tx = build_invoke_transaction(execute_calldata, account_address);
-        let tx = InvokeTransactionV1 { nonce, ..tx };
-        let account_tx = AccountTransaction::Invoke(InvokeTransaction {
-            tx: starknet_api::transaction::InvokeTransaction::V1(tx),
-            tx_hash: TransactionHash::default(), // TODO(#358): Check if this is legit
-        });
-
-        let tx_info = account_tx
-            .execute(blockifier_state, &block_context, true, true)
-            .unwrap_or_else(|e| panic!("Unparseable transaction error: {e:?}"));
-
-        if let Some(CallInfo { execution, .. }) = tx_info.execute_call_info {
-            let contract_address = execution
-                .retdata
-                .0
-                .get(0)
-                .expect("Failed to get contract_address from return_data");
-
-            let contract_address = ContractAddress::try_from(*contract_address)
-                .expect("Failed to cast contract address into the right struct");
-
-            return Ok(contract_address);
+        if deployed_class_hash != ClassHash::default() {
+            return Err(CheatcodeFailure::AddressOccupied.into());
         }
 
-        let revert_error = tx_info
-            .revert_error
-            .expect("Unparseable tx info, {tx_info:?}");
-        let extracted_panic_data = try_extract_panic_data(&revert_error)
-            .expect("Unparseable error message, {revert_error}");
+        self.blockifier_state
+            .set_class_hash_at(contract_address, *class_hash)
+            .map_err::<EnhancedHintError, _>(From::from)?;
+        Ok(contract_address)
+    }
 
-        Err(CheatcodeError::Recoverable(extracted_panic_data))
+    fn deploy_with_contract_address_salt(
+        &mut self,
+        class_hash: &ClassHash,
+        calldata: &[Felt252],
+        salt: &ContractAddressSalt,
+        deployer_address: ContractAddress,
+    ) -> Result<DeployResult, CheatcodeError> {
+        let contract_address =
+            calculate_deploy_address(salt, class_hash, calldata, deployer_address);
+        self.deploy_from_test_account(class_hash, calldata, contract_address)
     }
-}
 
-fn create_execute_calldata(
-    calldata: &[Felt252],
-    class_hash: &ClassHash,
-    account_address: &ContractAddress,
-    entry_point_selector: &EntryPointSelector,
-    salt: &ContractAddressSalt,
-) -> Calldata {
-    let calldata_len = u128::try_from(calldata.len()).unwrap();
-    let mut execute_calldata = vec![
-        *account_address.0.key(),      // Contract address.
-        entry_point_selector.0,        // EP selector.
-        stark_felt!(calldata_len + 3), // Calldata length.
-        class_hash.0,                  // Calldata: class_hash.
-        salt.0,                        // Contract_address_salt.
-        stark_felt!(calldata_len),     // Constructor calldata length.
-    ];
-    let mut calldata: Vec<StarkFelt> = calldata.iter().map(felt_to_stark_felt).collect();
-    execute_calldata.append(&mut calldata);
-    Calldata(execute_calldata.into())
-}
+    fn deploy_from_test_account(
+        &mut self,
+        class_hash: &ClassHash,
+        calldata: &[Felt252],
+        contract_address: ContractAddress,
+    ) -> Result<DeployResult, CheatcodeError> {
+        self.with_panic_policy(|state| {
+            let deploy_result = state.execute_deployment(class_hash, calldata, contract_address)?;
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use std::sync::Arc;
-
-    #[test]
-    fn execute_calldata() {
-        let calldata = create_execute_calldata(
-            &[Felt252::from(100), Felt252::from(200)],
-            &ClassHash(StarkFelt::from(123_u32)),
-            &ContractAddress::try_from(StarkFelt::from(111_u32)).unwrap(),
-            &EntryPointSelector(StarkFelt::from(222_u32)),
-            &ContractAddressSalt(StarkFelt::from(333_u32)),
-        );
-        assert_eq!(
-            calldata,
-            Calldata(Arc::new(vec![
-                StarkFelt::from(111_u32),
-                StarkFelt::from(222_u32),
-                StarkFelt::from(5_u32),
-                StarkFelt::from(123_u32),
-                StarkFelt::from(333_u32),
-                StarkFelt::from(2_u32),
-                StarkFelt::from(100_u32),
-                StarkFelt::from(200_u32),
-            ]))
-        );
+            // Deployments are still accounted as transactions sent from the test account,
+            // failed ones leave the state untouched
+            let account_address = state.test_account_address();
+            state
+                .blockifier_state
+                .increment_nonce(account_address)
+                .map_err::<EnhancedHintError, _>(From::from)?;
+            Ok(deploy_result)
+        })
     }
 
-    #[test]
-    fn execute_calldata_no_entrypoint_calldata() {
-        let calldata = create_execute_calldata(
-            &[],
-            &ClassHash(StarkFelt::from(123_u32)),
-            &ContractAddress::try_from(StarkFelt::from(111_u32)).unwrap(),
-            &EntryPointSelector(StarkFelt::from(222_u32)),
-            &ContractAddressSalt(StarkFelt::from(333_u32)),
-        );
-        assert_eq!(
-            calldata,
-            Calldata(Arc::new(vec![
-                StarkFelt::from(111_u32),
-                StarkFelt::from(222_u32),
-                StarkFelt::from(3_u32),
-                StarkFelt::from(123_u32),
-                StarkFelt::from(333_u32),
-                StarkFelt::from(0_u32),
-            ]))
-        );
+    // Executes the constructor through the cheatable execution, so cheatcodes
+    // targeting the deployed address are already applied during its execution
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "deploy", level = "debug", skip(self), ret, err(Debug))
+    )]
+    fn execute_deployment(
+        &mut self,
+        class_hash: &ClassHash,
+        calldata: &[Felt252],
+        contract_address: ContractAddress,
+    ) -> Result<DeployResult, CheatcodeError> {
+        let account_address = self.test_account_address();
+        let blockifier_state: &mut CachedState<DictStateReader> = &mut self.blockifier_state;
+
+        let contract_class = match blockifier_state.get_compiled_contract_class(class_hash) {
+            Err(StateError::UndeclaredClassHash(_)) => {
+                return Err(CheatcodeFailure::ClassNotDeclared(*class_hash).into());
+            }
+            contract_class => contract_class.map_err::<EnhancedHintError, _>(From::from)?,
+        };
+        if contract_class.constructor_selector().is_none() && !calldata.is_empty() {
+            return Err(CheatcodeFailure::MissingConstructor.into());
+        }
+        if let Some(expected_length) = self
+            .class_abis
+            .get(class_hash)
+            .and_then(ContractAbi::constructor_calldata_length)
+        {
+            if expected_length != calldata.len() {
+                return Err(CheatcodeFailure::InvalidConstructorCalldata {
+                    expected: expected_length,
+                    actual: calldata.len(),
+                }
+                .into());
+            }
+        }
+
+        let deployed_class_hash = blockifier_state
+            .get_class_hash_at(contract_address)
+            .map_err::<EnhancedHintError, _>(From::from)?;
+        if deployed_class_hash != ClassHash::default() {
+            return Err(CheatcodeFailure::AddressOccupied.into());
+        }
+
+        let Some(constructor_selector) = contract_class.constructor_selector() else {
+            blockifier_state
+                .set_class_hash_at(contract_address, *class_hash)
+                .map_err::<EnhancedHintError, _>(From::from)?;
+            return Ok(DeployResult {
+                contract_address,
+                class_hash: *class_hash,
+                gas_consumed: 0,
+                events: vec![],
+                l2_to_l1_messages: vec![],
+                inner_calls: vec![],
+            });
+        };
+
+        let entry_point = CallEntryPoint {
+            class_hash: Some(*class_hash),
+            code_address: Some(contract_address),
+            entry_point_type: EntryPointType::Constructor,
+            entry_point_selector: constructor_selector,
+            calldata: Calldata(
+                calldata
+                    .iter()
+                    .map(felt_to_stark_felt)
+                    .collect::<Vec<_>>()
+                    .into(),
+            ),
+            storage_address: contract_address,
+            caller_address: account_address,
+            call_type: CallType::Call,
+            initial_gas: u64::MAX,
+        };
+
+        // Changes of a deployment whose constructor fails are discarded,
+        // so nothing is left deployed at the address
+        let mut transactional_state = CachedState::create_transactional(&mut self.blockifier_state);
+        transactional_state
+            .set_class_hash_at(contract_address, *class_hash)
+            .map_err::<EnhancedHintError, _>(From::from)?;
+
+        match call_entry_point_in_state(
+            entry_point,
+            &mut transactional_state,
+            &mut self.cheatcode_state,
+            &self.block_context_config,
+        ) {
+            Ok(call_info) => {
+                transactional_state.commit();
+                Ok(DeployResult {
+                    contract_address,
+                    class_hash: *class_hash,
+                    gas_consumed: call_info.execution.gas_consumed,
+                    events: collect_events(&call_info),
+                    l2_to_l1_messages: collect_l2_to_l1_messages(&call_info),
+                    inner_calls: collect_inner_calls(&call_info),
+                })
+            }
+            Err(error) => {
+                transactional_state.abort();
+                Err(CheatcodeFailure::ContractPanic(panic_data_from_execution_error(&error)).into())
+            }
+        }
     }
 }
@@ -0,0 +1,35 @@
+use crate::state::{start_cheat, CheatSpan};
+use crate::CheatnetState;
+use starknet_api::core::ContractAddress;
+
+impl CheatnetState {
+    pub fn start_elect(
+        &mut self,
+        contract_address: ContractAddress,
+        sequencer_address: ContractAddress,
+    ) {
+        self.elect(contract_address, sequencer_address, CheatSpan::Indefinite);
+    }
+
+    /// Changes the sequencer address for calls into `contract_address` for the given `span`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self)))]
+    pub fn elect(
+        &mut self,
+        contract_address: ContractAddress,
+        sequencer_address: ContractAddress,
+        span: CheatSpan,
+    ) {
+        start_cheat(
+            &mut self.cheatcode_state.elected_contracts,
+            contract_address,
+            sequencer_address,
+            span,
+        );
+    }
+
+    pub fn stop_elect(&mut self, contract_address: ContractAddress) {
+        self.cheatcode_state
+            .elected_contracts
+            .remove(&contract_address);
+    }
+}
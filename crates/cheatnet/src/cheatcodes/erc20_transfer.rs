@@ -0,0 +1,85 @@
+use crate::cheatcodes::{CheatcodeError, CheatcodeFailure, EnhancedHintError};
+use crate::conversions::{contract_address_to_felt, felt_selector_from_name};
+use crate::rpc::{call_contract, CallContractOutput};
+use crate::state::CheatSpan;
+use crate::CheatnetState;
+use anyhow::anyhow;
+use cairo_felt::Felt252;
+use num_traits::ToPrimitive;
+use starknet_api::core::ContractAddress;
+
+/// Balances of the accounts after `erc20_transfer`, as the low and high parts of the `u256`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferBalances {
+    pub from: (u128, u128),
+    pub to: (u128, u128),
+}
+
+impl CheatnetState {
+    /// Transfers the `u256` amount, given as its low and high parts, of the ERC20 `token` from `from`
+    /// to `to`, by calling `transfer` with the caller pranked to `from`. A prank of `token` active
+    /// before the call is restored afterwards. Fails recoverably if the transfer panics.
+    pub fn erc20_transfer(
+        &mut self,
+        token: ContractAddress,
+        from: ContractAddress,
+        to: ContractAddress,
+        amount_low: u128,
+        amount_high: u128,
+    ) -> Result<TransferBalances, CheatcodeError> {
+        let previous_prank = self.cheatcode_state.pranked_contracts.remove(&token);
+        self.prank(token, from, CheatSpan::TargetCalls(1));
+
+        let output = call_contract(
+            &token,
+            &felt_selector_from_name("transfer"),
+            &[
+                contract_address_to_felt(to),
+                Felt252::from(amount_low),
+                Felt252::from(amount_high),
+            ],
+            self,
+        );
+
+        self.stop_prank(token);
+        if let Some(previous_prank) = previous_prank {
+            self.cheatcode_state
+                .pranked_contracts
+                .insert(token, previous_prank);
+        }
+
+        if let CallContractOutput::Panic { panic_data } =
+            output.map_err::<EnhancedHintError, _>(From::from)?
+        {
+            return Err(CheatcodeFailure::ContractPanic(panic_data).into());
+        }
+
+        Ok(TransferBalances {
+            from: self.erc20_balance(token, from)?,
+            to: self.erc20_balance(token, to)?,
+        })
+    }
+
+    fn erc20_balance(
+        &mut self,
+        token: ContractAddress,
+        account: ContractAddress,
+    ) -> Result<(u128, u128), CheatcodeError> {
+        let ret_data = self.call(
+            token,
+            &felt_selector_from_name("balance_of"),
+            &[contract_address_to_felt(account)],
+        )?;
+
+        match ret_data.as_slice() {
+            [low, high] => low.to_u128().zip(high.to_u128()),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            EnhancedHintError::from(anyhow!(
+                "Invalid balance_of return data {ret_data:?}, expected a u256"
+            ))
+            .into()
+        })
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    cheatcodes::{CheatcodeError, EnhancedHintError},
+    cheatcodes::{CheatcodeError, CheatcodeFailure, EnhancedHintError},
     CheatnetState,
 };
 use blockifier::state::state_api::StateReader;
@@ -7,11 +7,15 @@ use starknet_api::core::{ClassHash, ContractAddress};
 
 impl CheatnetState {
     /// Gets the class hash at the given address.
+    /// Fails recoverably if there is no contract deployed at the address.
     pub fn get_class_hash(
         &mut self,
         contract_address: ContractAddress,
     ) -> Result<ClassHash, CheatcodeError> {
         match self.blockifier_state.get_class_hash_at(contract_address) {
+            Ok(class_hash) if class_hash == ClassHash::default() => {
+                Err(CheatcodeFailure::ContractNotDeployed(contract_address).into())
+            }
             Ok(class_hash) => Ok(class_hash),
             Err(e) => Err(CheatcodeError::Unrecoverable(EnhancedHintError::State(e))),
         }
@@ -0,0 +1,400 @@
+use crate::cheatcodes::spoof::TxInfoMock;
+use crate::constants::{build_block_context, build_invoke_transaction};
+use crate::conversions::felt_selector_from_name;
+use crate::panic_data::{try_extract_panic_data, PanicData};
+use crate::rpc::{external_call_entry_point, panic_data_from_execution_error};
+use crate::state::CheatSpan;
+use crate::{cheatcodes::EnhancedHintError, CheatnetState};
+use anyhow::{anyhow, Context, Result};
+use blockifier::abi::constants::GAS_USAGE;
+use blockifier::execution::entry_point::Retdata;
+use blockifier::execution::execution_utils::{felt_to_stark_felt, stark_felt_to_felt};
+use blockifier::state::state_api::StateReader;
+use blockifier::transaction::account_transaction::AccountTransaction;
+use blockifier::transaction::errors::TransactionExecutionError;
+use blockifier::transaction::objects::TransactionExecutionInfo;
+use blockifier::transaction::transactions::{ExecutableTransaction, InvokeTransaction};
+use cairo_felt::Felt252;
+use starknet_api::core::ContractAddress;
+use starknet_api::hash::StarkFelt;
+use starknet_api::transaction::{
+    Calldata, Fee, InvokeTransactionV1, TransactionHash, TransactionSignature,
+};
+use std::collections::HashMap;
+
+use super::{CheatcodeError, CheatcodeFailure};
+
+/// Resources used by the execution of an invoked entry point, together with its inner calls.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UsedResources {
+    pub l1_gas: usize,
+    pub n_steps: usize,
+    pub n_memory_holes: usize,
+    pub builtin_instance_counter: HashMap<String, usize>,
+}
+
+/// A call sent with `invoke_signed` or as a part of a multicall sent with `invoke_multi`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Call {
+    pub to: ContractAddress,
+    pub selector: Felt252,
+    pub calldata: Vec<Felt252>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvokeResult {
+    pub ret_data: Vec<Felt252>,
+    pub used_resources: UsedResources,
+    // Calculated with the gas price of the block context
+    pub actual_fee: u128,
+}
+
+impl CheatnetState {
+    /// Invokes the entry point in a transaction sent from the test account,
+    /// the same way an invoke transaction is executed on the network.
+    /// Unlike `call_contract`, cheatcodes are not applied to the transaction: pranks don't change
+    /// its caller, and events and messages to L1 sent during it are not seen by event and message spies.
+    /// Fails recoverably with `TransactionRejected` if the transaction is rejected,
+    /// e.g. because the test account can't pay its fee.
+    pub fn invoke(
+        &mut self,
+        contract_address: &ContractAddress,
+        entry_point_selector: &Felt252,
+        calldata: &[Felt252],
+    ) -> Result<InvokeResult, CheatcodeError> {
+        self.invoke_with_signature(
+            contract_address,
+            entry_point_selector,
+            calldata,
+            &[],
+            true,
+            None,
+        )
+    }
+
+    /// Works like `invoke`, but the transaction is signed with `signature`,
+    /// which the test account can check in its `__validate__`.
+    /// If `__validate__` rejects the transaction, it fails recoverably with its panic data.
+    pub fn invoke_signed(
+        &mut self,
+        call: &Call,
+        signature: Vec<Felt252>,
+    ) -> Result<InvokeResult, CheatcodeError> {
+        self.invoke_with_signature(
+            &call.to,
+            &call.selector,
+            &call.calldata,
+            &signature,
+            true,
+            None,
+        )
+    }
+
+    /// Works like `invoke_signed`, but the test account's `__validate__` is skipped,
+    /// so `__execute__` can be tested regardless of the validation logic.
+    pub fn execute_only(&mut self, call: &Call) -> Result<InvokeResult, CheatcodeError> {
+        self.invoke_with_signature(&call.to, &call.selector, &call.calldata, &[], false, None)
+    }
+
+    /// Works like `invoke`, but the transaction is sent with `max_fee` instead of the default one.
+    /// When fees are charged, see `set_charge_fee`, this checks how the transaction behaves
+    /// when the max fee is insufficient or exactly sufficient.
+    /// An insufficient max fee fails recoverably with `TransactionRejected`.
+    pub fn invoke_with_max_fee(
+        &mut self,
+        call: &Call,
+        max_fee: u128,
+    ) -> Result<InvokeResult, CheatcodeError> {
+        self.invoke_with_signature(
+            &call.to,
+            &call.selector,
+            &call.calldata,
+            &[],
+            true,
+            Some(max_fee),
+        )
+    }
+
+    /// Calls only the test account's `__validate__` for a transaction sending `call`
+    /// signed with `signature`, without executing the call.
+    /// Returns the data returned by `__validate__`, or fails recoverably if it panics,
+    /// e.g. because it rejected the signature. Changes made by the validation are discarded.
+    /// Like in a transaction, `__validate__` is called with a zero caller address, and the fields
+    /// of an active spoof of the test account other than the signature are kept.
+    pub fn validate_only(
+        &mut self,
+        call: &Call,
+        signature: Vec<Felt252>,
+    ) -> Result<Vec<Felt252>, CheatcodeError> {
+        let account_address = self.test_account_address();
+        let validate_calldata: Vec<Felt252> =
+            create_execute_calldata(&call.to, &call.selector, &call.calldata)
+                .0
+                .iter()
+                .map(|data| stark_felt_to_felt(*data))
+                .collect();
+        // Like the protocol, `__validate__` is called with a zero caller address
+        let entry_point = external_call_entry_point(
+            &account_address,
+            &felt_selector_from_name("__validate__"),
+            &validate_calldata,
+            ContractAddress::default(),
+            u64::MAX,
+        )
+        .map_err::<EnhancedHintError, _>(From::from)?;
+
+        // The signature is read by `__validate__` from the transaction info,
+        // other fields of an active spoof of the account are kept
+        let previous_spoof = self
+            .cheatcode_state
+            .spoofed_contracts
+            .remove(&account_address);
+        let mut tx_info_mock = previous_spoof
+            .as_ref()
+            .map(|spoofed| spoofed.value.clone())
+            .unwrap_or_default();
+        tx_info_mock.override_with(TxInfoMock {
+            signature: Some(signature),
+            ..Default::default()
+        });
+        self.spoof(account_address, tx_info_mock, CheatSpan::TargetCalls(1));
+
+        let result = self.call_entry_point_without_changes(entry_point);
+
+        self.stop_spoof(account_address);
+        if let Some(previous_spoof) = previous_spoof {
+            self.cheatcode_state
+                .spoofed_contracts
+                .insert(account_address, previous_spoof);
+        }
+        result
+    }
+
+    fn invoke_with_signature(
+        &mut self,
+        contract_address: &ContractAddress,
+        entry_point_selector: &Felt252,
+        calldata: &[Felt252],
+        signature: &[Felt252],
+        validate: bool,
+        max_fee: Option<u128>,
+    ) -> Result<InvokeResult, CheatcodeError> {
+        let execute_calldata =
+            create_execute_calldata(contract_address, entry_point_selector, calldata);
+        let tx_info =
+            self.execute_invoke_transaction(execute_calldata, signature, validate, max_fee)?;
+
+        let execute_call_info = tx_info
+            .execute_call_info
+            .context("Successful invoke transaction has no execute call info")
+            .map_err::<EnhancedHintError, _>(From::from)?;
+        // The account's `__execute__` calls the invoked entry point as its only inner call
+        let invoked_call_info = execute_call_info
+            .inner_calls
+            .first()
+            .context("The test account's __execute__ didn't call the invoked entry point")
+            .map_err::<EnhancedHintError, _>(From::from)?;
+
+        let vm_resources = &execute_call_info.vm_resources;
+        let used_resources = UsedResources {
+            l1_gas: tx_info
+                .actual_resources
+                .0
+                .get(GAS_USAGE)
+                .copied()
+                .unwrap_or_default(),
+            n_steps: vm_resources.n_steps,
+            n_memory_holes: vm_resources.n_memory_holes,
+            builtin_instance_counter: vm_resources.builtin_instance_counter.clone(),
+        };
+
+        Ok(InvokeResult {
+            ret_data: retdata_to_felts(&invoked_call_info.execution.retdata),
+            used_resources,
+            actual_fee: tx_info.actual_fee.0,
+        })
+    }
+
+    /// Invokes all `calls` in a single transaction, using the multicall calldata format of
+    /// `__execute__(calls: Array<Call>)`, so the test account has to support multicalls,
+    /// e.g. one set with `with_test_account_address`.
+    /// The calls are atomic: if any of them panics, none of them is applied.
+    /// Returns the data returned by each of the calls.
+    pub fn invoke_multi(&mut self, calls: &[Call]) -> Result<Vec<Vec<Felt252>>, CheatcodeError> {
+        let execute_calldata = create_multicall_execute_calldata(calls);
+        let tx_info = self.execute_invoke_transaction(execute_calldata, &[], true, None)?;
+
+        let execute_call_info = tx_info
+            .execute_call_info
+            .context("Successful invoke transaction has no execute call info")
+            .map_err::<EnhancedHintError, _>(From::from)?;
+        // The account's `__execute__` calls the invoked entry points as its inner calls, in order
+        Ok(execute_call_info
+            .inner_calls
+            .iter()
+            .map(|call_info| retdata_to_felts(&call_info.execution.retdata))
+            .collect())
+    }
+
+    fn execute_invoke_transaction(
+        &mut self,
+        execute_calldata: Calldata,
+        signature: &[Felt252],
+        validate: bool,
+        // Keeps the max fee of `build_invoke_transaction` when `None`
+        max_fee: Option<u128>,
+    ) -> Result<TransactionExecutionInfo, CheatcodeError> {
+        let account_address = self.test_account_address();
+        let block_context = build_block_context(&self.block_context_config);
+
+        let nonce = self
+            .blockifier_state
+            .get_nonce_at(account_address)
+            .map_err::<EnhancedHintError, _>(From::from)?;
+        let tx = build_invoke_transaction(execute_calldata, account_address);
+        let tx = InvokeTransactionV1 {
+            nonce,
+            signature: TransactionSignature(signature.iter().map(felt_to_stark_felt).collect()),
+            max_fee: max_fee.map_or(tx.max_fee, Fee),
+            ..tx
+        };
+        let account_tx = AccountTransaction::Invoke(InvokeTransaction {
+            tx: starknet_api::transaction::InvokeTransaction::V1(tx),
+            tx_hash: TransactionHash::default(),
+        });
+
+        let tx_info = account_tx
+            .execute(
+                &mut self.blockifier_state,
+                &block_context,
+                self.charge_fee,
+                validate,
+            )
+            .map_err(transaction_failure)?;
+
+        if let Some(revert_error) = &tx_info.revert_error {
+            let panic_data = try_extract_panic_data(revert_error)
+                .unwrap_or_else(|| PanicData::from_message(revert_error));
+            return Err(CheatcodeFailure::ContractPanic(panic_data.into_felts()).into());
+        }
+
+        Ok(tx_info)
+    }
+}
+
+// Transactions rejected by the protocol, e.g. because the fee can't be paid, fail recoverably,
+// errors of the state itself fail the whole test
+fn transaction_failure(error: TransactionExecutionError) -> CheatcodeError {
+    match error {
+        // Reported like in `validate_only`, with the panic data of `__validate__`
+        TransactionExecutionError::ValidateTransactionError(error) => {
+            CheatcodeFailure::ContractPanic(panic_data_from_execution_error(&error)).into()
+        }
+        TransactionExecutionError::StateError(_)
+        | TransactionExecutionError::StarknetApiError(_) => EnhancedHintError::Anyhow(anyhow!(
+            format!("Failed to execute invoke transaction:\n    {error}")
+        ))
+        .into(),
+        error => CheatcodeFailure::TransactionRejected(error_chain_message(&error)).into(),
+    }
+}
+
+// Wrapping errors, e.g. of a failed fee transfer, don't show their source in their message
+fn error_chain_message(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(error) = source {
+        message.push_str(&format!(": {error}"));
+        source = error.source();
+    }
+    message
+}
+
+fn retdata_to_felts(retdata: &Retdata) -> Vec<Felt252> {
+    retdata
+        .0
+        .iter()
+        .map(|data| stark_felt_to_felt(*data))
+        .collect()
+}
+
+fn create_execute_calldata(
+    contract_address: &ContractAddress,
+    entry_point_selector: &Felt252,
+    calldata: &[Felt252],
+) -> Calldata {
+    let mut execute_calldata = vec![
+        *contract_address.0.key(),                // Contract address.
+        felt_to_stark_felt(entry_point_selector), // EP selector.
+        StarkFelt::from(u64::try_from(calldata.len()).unwrap()), // Calldata length.
+    ];
+    execute_calldata.extend(calldata.iter().map(felt_to_stark_felt));
+    Calldata(execute_calldata.into())
+}
+
+// Serialized `Array<Call>`: the number of calls followed by every call
+// with its address, selector and length-prefixed calldata
+fn create_multicall_execute_calldata(calls: &[Call]) -> Calldata {
+    let mut execute_calldata = vec![StarkFelt::from(u64::try_from(calls.len()).unwrap())];
+    for call in calls {
+        execute_calldata.push(*call.to.0.key());
+        execute_calldata.push(felt_to_stark_felt(&call.selector));
+        execute_calldata.push(StarkFelt::from(u64::try_from(call.calldata.len()).unwrap()));
+        execute_calldata.extend(call.calldata.iter().map(felt_to_stark_felt));
+    }
+    Calldata(execute_calldata.into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn execute_calldata() {
+        let calldata = create_execute_calldata(
+            &ContractAddress::try_from(StarkFelt::from(111_u32)).unwrap(),
+            &Felt252::from(222),
+            &[Felt252::from(100), Felt252::from(200)],
+        );
+        assert_eq!(
+            calldata,
+            Calldata(Arc::new(vec![
+                StarkFelt::from(111_u32),
+                StarkFelt::from(222_u32),
+                StarkFelt::from(2_u32),
+                StarkFelt::from(100_u32),
+                StarkFelt::from(200_u32),
+            ]))
+        );
+    }
+
+    #[test]
+    fn multicall_execute_calldata() {
+        let calldata = create_multicall_execute_calldata(&[
+            Call {
+                to: ContractAddress::try_from(StarkFelt::from(111_u32)).unwrap(),
+                selector: Felt252::from(222),
+                calldata: vec![Felt252::from(100)],
+            },
+            Call {
+                to: ContractAddress::try_from(StarkFelt::from(333_u32)).unwrap(),
+                selector: Felt252::from(444),
+                calldata: vec![],
+            },
+        ]);
+        assert_eq!(
+            calldata,
+            Calldata(Arc::new(vec![
+                StarkFelt::from(2_u32),
+                StarkFelt::from(111_u32),
+                StarkFelt::from(222_u32),
+                StarkFelt::from(1_u32),
+                StarkFelt::from(100_u32),
+                StarkFelt::from(333_u32),
+                StarkFelt::from(444_u32),
+                StarkFelt::from(0_u32),
+            ]))
+        );
+    }
+}
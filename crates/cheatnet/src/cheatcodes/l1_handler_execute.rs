@@ -0,0 +1,48 @@
+use crate::rpc::{call_entry_point, panic_data_from_execution_error};
+use crate::CheatnetState;
+use blockifier::execution::entry_point::{CallEntryPoint, CallType};
+use blockifier::execution::execution_utils::felt_to_stark_felt;
+use cairo_felt::Felt252;
+use starknet_api::core::{ContractAddress, EntryPointSelector};
+use starknet_api::deprecated_contract_class::EntryPointType;
+use starknet_api::transaction::Calldata;
+
+use super::{CheatcodeError, CheatcodeFailure};
+
+impl CheatnetState {
+    /// Executes the `#[l1_handler]` entry point of `contract_address` as if a message with `payload`
+    /// was sent to it from `from_address` on L1.
+    pub fn l1_handler_execute(
+        &mut self,
+        contract_address: ContractAddress,
+        selector: &Felt252,
+        from_address: &Felt252,
+        payload: &[Felt252],
+    ) -> Result<(), CheatcodeError> {
+        // The sender of the message is passed to the handler as the first argument
+        let calldata: Vec<_> = std::iter::once(from_address)
+            .chain(payload)
+            .map(felt_to_stark_felt)
+            .collect();
+
+        let entry_point = CallEntryPoint {
+            class_hash: None,
+            code_address: Some(contract_address),
+            entry_point_type: EntryPointType::L1Handler,
+            entry_point_selector: EntryPointSelector(felt_to_stark_felt(selector)),
+            calldata: Calldata(calldata.into()),
+            storage_address: contract_address,
+            // L1 handlers are not called by any Starknet contract
+            caller_address: ContractAddress::default(),
+            call_type: CallType::Call,
+            initial_gas: u64::MAX,
+        };
+
+        match call_entry_point(entry_point, self) {
+            Ok(_) => Ok(()),
+            Err(error) => {
+                Err(CheatcodeFailure::ContractPanic(panic_data_from_execution_error(&error)).into())
+            }
+        }
+    }
+}
@@ -1,22 +1,53 @@
 use crate::CheatnetState;
+use cairo_felt::Felt252;
 use starknet_api::core::{ContractAddress, EntryPointSelector};
 use starknet_api::hash::StarkFelt;
 use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Computes the data returned by a mocked function from its calldata.
+/// Returning `None` executes the real function instead.
+pub type CalldataMatcher = Arc<dyn Fn(&[Felt252]) -> Option<Vec<Felt252>> + Send + Sync>;
+
+#[derive(Clone)]
+pub enum MockedResponse {
+    RetData(Vec<StarkFelt>),
+    Matcher(CalldataMatcher),
+}
 
 impl CheatnetState {
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self)))]
     pub fn start_mock_call(
         &mut self,
         contract_address: ContractAddress,
         function_name: EntryPointSelector,
         ret_data: Vec<StarkFelt>,
     ) {
-        let contract_mocked_functions = self
-            .cheatcode_state
-            .mocked_functions
-            .entry(contract_address)
-            .or_insert_with(HashMap::new);
+        self.insert_mocked_response(
+            contract_address,
+            function_name,
+            MockedResponse::RetData(ret_data),
+        );
+    }
 
-        contract_mocked_functions.insert(function_name, ret_data);
+    /// Mocks the function with data depending on the calldata it is called with,
+    /// e.g. an ERC20 `balance_of` returning a different balance for every account.
+    /// Calls for which `matcher` returns `None` are executed by the real function.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip(self, matcher))
+    )]
+    pub fn start_mock_call_with_matcher(
+        &mut self,
+        contract_address: ContractAddress,
+        function_name: EntryPointSelector,
+        matcher: impl Fn(&[Felt252]) -> Option<Vec<Felt252>> + Send + Sync + 'static,
+    ) {
+        self.insert_mocked_response(
+            contract_address,
+            function_name,
+            MockedResponse::Matcher(Arc::new(matcher)),
+        );
     }
 
     pub fn stop_mock_call(
@@ -33,4 +64,19 @@ impl CheatnetState {
             contract_mocked_functions.remove(&function_name);
         }
     }
+
+    fn insert_mocked_response(
+        &mut self,
+        contract_address: ContractAddress,
+        function_name: EntryPointSelector,
+        response: MockedResponse,
+    ) {
+        let contract_mocked_functions = self
+            .cheatcode_state
+            .mocked_functions
+            .entry(contract_address)
+            .or_insert_with(HashMap::new);
+
+        contract_mocked_functions.insert(function_name, response);
+    }
 }
@@ -0,0 +1,32 @@
+use crate::{
+    cheatcodes::{CheatcodeError, EnhancedHintError},
+    CheatnetState,
+};
+use blockifier::execution::execution_utils::{felt_to_stark_felt, stark_felt_to_felt};
+use blockifier::state::cached_state::{CachedState, GlobalContractCache};
+use blockifier::state::state_api::StateReader;
+use cairo_felt::Felt252;
+use starknet_api::core::{ContractAddress, Nonce};
+
+impl CheatnetState {
+    /// Reads the nonce of `target`. Addresses which never sent a transaction have nonce 0.
+    pub fn get_nonce(&mut self, target: ContractAddress) -> Result<Felt252, CheatcodeError> {
+        let nonce = self
+            .blockifier_state
+            .get_nonce_at(target)
+            .map_err::<EnhancedHintError, _>(From::from)?;
+        Ok(stark_felt_to_felt(nonce.0))
+    }
+
+    /// Sets the nonce of `target` to `value`, which can also be lower than the current one.
+    pub fn set_nonce(&mut self, target: ContractAddress, value: &Felt252) {
+        // Blockifier state can only increment nonces, so the value is written
+        // to the underlying state reader, with the pending changes applied first
+        let mut state_reader = self.flattened_state_reader();
+        state_reader
+            .address_to_nonce
+            .insert(target, Nonce(felt_to_stark_felt(value)));
+
+        self.blockifier_state = CachedState::new(state_reader, GlobalContractCache::default());
+    }
+}
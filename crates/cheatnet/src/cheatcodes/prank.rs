@@ -1,3 +1,4 @@
+use crate::state::{start_cheat, CheatSpan};
 use crate::CheatnetState;
 use starknet_api::core::ContractAddress;
 
@@ -7,9 +8,26 @@ impl CheatnetState {
         contract_address: ContractAddress,
         caller_address: ContractAddress,
     ) {
-        self.cheatcode_state
-            .pranked_contracts
-            .insert(contract_address, caller_address);
+        self.prank(contract_address, caller_address, CheatSpan::Indefinite);
+    }
+
+    /// Changes the caller address seen by `contract_address` for the given `span`.
+    /// The prank replaces the immediate caller of `contract_address`, whoever calls it:
+    /// when the test calls A which calls B, a prank of B makes B see `caller_address` instead of A,
+    /// and a prank of A makes only A see `caller_address`, B still sees A as its caller.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self)))]
+    pub fn prank(
+        &mut self,
+        contract_address: ContractAddress,
+        caller_address: ContractAddress,
+        span: CheatSpan,
+    ) {
+        start_cheat(
+            &mut self.cheatcode_state.pranked_contracts,
+            contract_address,
+            caller_address,
+            span,
+        );
     }
 
     pub fn stop_prank(&mut self, contract_address: ContractAddress) {
@@ -1,31 +1,35 @@
-use crate::constants::TEST_ACCOUNT_CONTRACT_ADDRESS;
-
+use crate::address::calculate_contract_address;
 use crate::CheatnetState;
-use blockifier::execution::execution_utils::felt_to_stark_felt;
+use blockifier::execution::execution_utils::stark_felt_to_felt;
 use cairo_felt::Felt252;
-use starknet_api::core::PatriciaKey;
-use starknet_api::core::{calculate_contract_address, ClassHash, ContractAddress};
-use starknet_api::hash::{StarkFelt, StarkHash};
-use starknet_api::transaction::Calldata;
-
-use starknet_api::patricia_key;
+use starknet_api::core::{ClassHash, ContractAddress};
+use starknet_api::transaction::ContractAddressSalt;
 
 impl CheatnetState {
+    /// Calculates the address the next `deploy` of the given class with the given calldata will use.
+    /// The salt counter is not incremented.
     #[must_use]
     pub fn precalculate_address(
         &self,
         class_hash: &ClassHash,
         calldata: &[Felt252],
     ) -> ContractAddress {
-        let account_address = ContractAddress(patricia_key!(TEST_ACCOUNT_CONTRACT_ADDRESS));
         let salt = self.get_salt();
 
-        let execute_calldata = create_execute_calldata(calldata);
-        calculate_contract_address(salt, *class_hash, &execute_calldata, account_address).unwrap()
+        calculate_deploy_address(&salt, class_hash, calldata, self.test_account_address())
     }
 }
 
-fn create_execute_calldata(calldata: &[Felt252]) -> Calldata {
-    let calldata: Vec<StarkFelt> = calldata.iter().map(felt_to_stark_felt).collect();
-    Calldata(calldata.into())
+pub(crate) fn calculate_deploy_address(
+    salt: &ContractAddressSalt,
+    class_hash: &ClassHash,
+    calldata: &[Felt252],
+    deployer_address: ContractAddress,
+) -> ContractAddress {
+    calculate_contract_address(
+        &stark_felt_to_felt(salt.0),
+        class_hash,
+        calldata,
+        deployer_address,
+    )
 }
@@ -0,0 +1,43 @@
+use crate::{
+    cheatcodes::{CheatcodeError, CheatcodeFailure, EnhancedHintError},
+    CheatnetState,
+};
+use blockifier::state::errors::StateError;
+use blockifier::state::state_api::{State, StateReader};
+use starknet_api::core::{ClassHash, ContractAddress};
+
+impl CheatnetState {
+    /// Replaces the class of the contract deployed at `target` with `new_class_hash`,
+    /// keeping its storage. The new class has to be declared
+    /// and fails with `ContractNotDeployed` if no contract is deployed at `target`.
+    pub fn replace_class(
+        &mut self,
+        target: ContractAddress,
+        new_class_hash: ClassHash,
+    ) -> Result<(), CheatcodeError> {
+        match self
+            .blockifier_state
+            .get_compiled_contract_class(&new_class_hash)
+        {
+            Ok(_) => {}
+            Err(StateError::UndeclaredClassHash(_)) => {
+                return Err(CheatcodeFailure::ClassNotDeclared(new_class_hash).into());
+            }
+            Err(e) => return Err(CheatcodeError::Unrecoverable(EnhancedHintError::State(e))),
+        }
+
+        // Otherwise the class would be placed at the address without running its constructor
+        let class_hash = self
+            .blockifier_state
+            .get_class_hash_at(target)
+            .map_err::<EnhancedHintError, _>(From::from)?;
+        if class_hash == ClassHash::default() {
+            return Err(CheatcodeFailure::ContractNotDeployed(target).into());
+        }
+
+        self.blockifier_state
+            .set_class_hash_at(target, new_class_hash)
+            .map_err::<EnhancedHintError, _>(From::from)?;
+        Ok(())
+    }
+}
@@ -1,12 +1,37 @@
+use crate::state::{start_cheat, CheatSpan};
 use crate::CheatnetState;
 use cairo_felt::Felt252;
+use num_traits::ToPrimitive;
 use starknet_api::core::ContractAddress;
 
 impl CheatnetState {
     pub fn start_roll(&mut self, contract_address: ContractAddress, block_number: Felt252) {
+        self.roll(contract_address, block_number, CheatSpan::Indefinite);
+    }
+
+    /// Changes the block number for calls into `contract_address` for the given `span`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self)))]
+    pub fn roll(
+        &mut self,
+        contract_address: ContractAddress,
+        block_number: Felt252,
+        span: CheatSpan,
+    ) {
+        start_cheat(
+            &mut self.cheatcode_state.rolled_contracts,
+            contract_address,
+            block_number,
+            span,
+        );
+    }
+
+    /// The block number calls into `contract_address` currently see, if it is rolled,
+    /// either by `roll` or globally. Returns `None` if the value doesn't fit in `u64`.
+    #[must_use]
+    pub fn get_roll(&self, contract_address: ContractAddress) -> Option<u64> {
         self.cheatcode_state
-            .rolled_contracts
-            .insert(contract_address, block_number);
+            .rolled_block_number(&contract_address)
+            .and_then(ToPrimitive::to_u64)
     }
 
     pub fn stop_roll(&mut self, contract_address: ContractAddress) {
@@ -14,4 +39,14 @@ impl CheatnetState {
             .rolled_contracts
             .remove(&contract_address);
     }
+
+    /// Changes the block number for calls into all contracts until `stop_roll_global`.
+    /// Contracts rolled with `roll` see their own block number instead.
+    pub fn start_roll_global(&mut self, block_number: Felt252) {
+        self.cheatcode_state.global_roll = Some(block_number);
+    }
+
+    pub fn stop_roll_global(&mut self) {
+        self.cheatcode_state.global_roll = None;
+    }
 }
@@ -0,0 +1,67 @@
+use crate::state::{start_cheat, CheatSpan};
+use crate::CheatnetState;
+use cairo_felt::Felt252;
+use starknet_api::core::ContractAddress;
+
+/// Overrides of the fields returned by `get_tx_info`, fields set to `None` keep their original values.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TxInfoMock {
+    pub version: Option<Felt252>,
+    pub account_contract_address: Option<Felt252>,
+    pub max_fee: Option<Felt252>,
+    pub signature: Option<Vec<Felt252>>,
+    pub transaction_hash: Option<Felt252>,
+    pub chain_id: Option<Felt252>,
+    pub nonce: Option<Felt252>,
+}
+
+impl TxInfoMock {
+    // Replaces the fields which are set in `overrides`, keeping the others
+    pub(crate) fn override_with(&mut self, overrides: TxInfoMock) {
+        let TxInfoMock {
+            version,
+            account_contract_address,
+            max_fee,
+            signature,
+            transaction_hash,
+            chain_id,
+            nonce,
+        } = overrides;
+        self.version = version.or(self.version.take());
+        self.account_contract_address =
+            account_contract_address.or(self.account_contract_address.take());
+        self.max_fee = max_fee.or(self.max_fee.take());
+        self.signature = signature.or(self.signature.take());
+        self.transaction_hash = transaction_hash.or(self.transaction_hash.take());
+        self.chain_id = chain_id.or(self.chain_id.take());
+        self.nonce = nonce.or(self.nonce.take());
+    }
+}
+
+impl CheatnetState {
+    pub fn start_spoof(&mut self, contract_address: ContractAddress, tx_info_mock: TxInfoMock) {
+        self.spoof(contract_address, tx_info_mock, CheatSpan::Indefinite);
+    }
+
+    /// Changes the transaction info for calls into `contract_address` for the given `span`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self)))]
+    pub fn spoof(
+        &mut self,
+        contract_address: ContractAddress,
+        tx_info_mock: TxInfoMock,
+        span: CheatSpan,
+    ) {
+        start_cheat(
+            &mut self.cheatcode_state.spoofed_contracts,
+            contract_address,
+            tx_info_mock,
+            span,
+        );
+    }
+
+    pub fn stop_spoof(&mut self, contract_address: ContractAddress) {
+        self.cheatcode_state
+            .spoofed_contracts
+            .remove(&contract_address);
+    }
+}
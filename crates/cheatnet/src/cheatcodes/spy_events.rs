@@ -0,0 +1,126 @@
+use crate::cheatcodes::Event;
+use crate::CheatnetState;
+use cairo_felt::Felt252;
+use starknet_api::core::ContractAddress;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Pattern of an event asserted with `EventSpy::expect`, fields set to `None` match any value.
+/// `keys` and `data` only match events with as many elements, `None` elements match any felt.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ExpectedEvent {
+    pub from: Option<ContractAddress>,
+    pub keys: Option<Vec<Option<Felt252>>>,
+    pub data: Option<Vec<Option<Felt252>>>,
+}
+
+impl ExpectedEvent {
+    #[must_use]
+    pub fn matches(&self, event: &Event) -> bool {
+        self.from.map_or(true, |from| from == event.from)
+            && felts_match(self.keys.as_deref(), &event.keys)
+            && felts_match(self.data.as_deref(), &event.data)
+    }
+}
+
+/// Matches exactly the given event.
+impl From<&Event> for ExpectedEvent {
+    fn from(event: &Event) -> Self {
+        let exact = |felts: &[Felt252]| Some(felts.iter().cloned().map(Some).collect());
+        ExpectedEvent {
+            from: Some(event.from),
+            keys: exact(&event.keys),
+            data: exact(&event.data),
+        }
+    }
+}
+
+fn felts_match(expected: Option<&[Option<Felt252>]>, felts: &[Felt252]) -> bool {
+    expected.map_or(true, |expected| {
+        expected.len() == felts.len()
+            && expected
+                .iter()
+                .zip(felts)
+                .all(|(expected, felt)| expected.as_ref().map_or(true, |expected| expected == felt))
+    })
+}
+
+/// Records events emitted by calls and deployments executed after the spy was created.
+#[derive(Debug, Clone, Default)]
+pub struct EventSpy {
+    events: Rc<RefCell<Vec<Event>>>,
+}
+
+impl EventSpy {
+    /// Returns all captured events, in the order they were emitted.
+    #[must_use]
+    pub fn get_events(&self) -> Vec<Event> {
+        self.events.borrow().clone()
+    }
+
+    /// Returns captured events emitted by the contract at `address`, in the order they were emitted.
+    #[must_use]
+    pub fn events_from(&self, address: ContractAddress) -> Vec<Event> {
+        self.filter_events(|event| event.from == address)
+    }
+
+    /// Returns captured events whose first key, the selector of the event name, is `selector`.
+    #[must_use]
+    pub fn events_with_selector(&self, selector: &Felt252) -> Vec<Event> {
+        self.filter_events(|event| event.keys.first() == Some(selector))
+    }
+
+    /// Returns the number of captured events.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.events.borrow().len()
+    }
+
+    fn filter_events(&self, predicate: impl Fn(&Event) -> bool) -> Vec<Event> {
+        self.events
+            .borrow()
+            .iter()
+            .filter(|event| predicate(event))
+            .cloned()
+            .collect()
+    }
+
+    /// Panics if `event` wasn't captured by the spy.
+    pub fn assert_emitted(&self, event: &Event) {
+        let events = self.events.borrow();
+        assert!(
+            events.contains(event),
+            "Event {event:?} was not emitted, emitted events: {events:?}"
+        );
+    }
+
+    /// Panics unless events matching `expected` were captured in the same order.
+    /// Other events may have been emitted before, between and after them.
+    pub fn expect(&self, expected: &[ExpectedEvent]) {
+        let events = self.events.borrow();
+        let mut remaining_events = events.iter();
+        for (index, expected_event) in expected.iter().enumerate() {
+            assert!(
+                remaining_events.any(|event| expected_event.matches(event)),
+                "Expected event {index} {expected_event:?} was not emitted after the previous expected events, emitted events: {events:?}"
+            );
+        }
+    }
+
+    pub(crate) fn record(&self, events: &[Event]) {
+        self.events.borrow_mut().extend_from_slice(events);
+    }
+
+    // Drops the events captured after the first `count` ones
+    pub(crate) fn truncate(&self, count: usize) {
+        self.events.borrow_mut().truncate(count);
+    }
+}
+
+impl CheatnetState {
+    pub fn spy_events(&mut self) -> EventSpy {
+        let spy = EventSpy::default();
+        self.cheatcode_state.event_spies.push(spy.clone());
+        spy
+    }
+}
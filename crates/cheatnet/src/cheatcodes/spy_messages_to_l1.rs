@@ -0,0 +1,50 @@
+use crate::cheatcodes::L2ToL1Message;
+use crate::CheatnetState;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Records messages sent to L1 by calls and deployments executed after the spy was created.
+#[derive(Debug, Clone, Default)]
+pub struct MessageSpy {
+    messages: Rc<RefCell<Vec<L2ToL1Message>>>,
+}
+
+impl MessageSpy {
+    /// Returns all captured messages, in the order they were sent.
+    #[must_use]
+    pub fn get_messages(&self) -> Vec<L2ToL1Message> {
+        self.messages.borrow().clone()
+    }
+
+    /// Panics if `message` wasn't captured by the spy.
+    pub fn assert_sent(&self, message: &L2ToL1Message) {
+        let messages = self.messages.borrow();
+        assert!(
+            messages.contains(message),
+            "Message {message:?} was not sent, sent messages: {messages:?}"
+        );
+    }
+
+    /// Returns the number of captured messages.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.messages.borrow().len()
+    }
+
+    pub(crate) fn record(&self, messages: &[L2ToL1Message]) {
+        self.messages.borrow_mut().extend_from_slice(messages);
+    }
+
+    // Drops the messages captured after the first `count` ones
+    pub(crate) fn truncate(&self, count: usize) {
+        self.messages.borrow_mut().truncate(count);
+    }
+}
+
+impl CheatnetState {
+    pub fn spy_messages_to_l1(&mut self) -> MessageSpy {
+        let spy = MessageSpy::default();
+        self.cheatcode_state.message_spies.push(spy.clone());
+        spy
+    }
+}
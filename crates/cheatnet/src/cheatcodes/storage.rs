@@ -0,0 +1,129 @@
+use crate::{
+    cheatcodes::{CheatcodeError, EnhancedHintError},
+    conversions::{contract_address_to_felt, map_entry_address, storage_address_from_base},
+    CheatnetState,
+};
+use anyhow::anyhow;
+use blockifier::execution::execution_utils::{felt_to_stark_felt, stark_felt_to_felt};
+use blockifier::state::state_api::{State, StateReader};
+use cairo_felt::Felt252;
+use num_traits::ToPrimitive;
+use starknet_api::core::{ContractAddress, PatriciaKey};
+use starknet_api::hash::StarkFelt;
+use starknet_api::state::StorageKey;
+
+impl CheatnetState {
+    /// Writes `value` directly to the storage of `target`, bypassing the contract logic.
+    /// Storage of addresses without a deployed contract can be written as well.
+    pub fn store(
+        &mut self,
+        target: ContractAddress,
+        storage_address: &Felt252,
+        value: &Felt252,
+    ) -> Result<(), CheatcodeError> {
+        let storage_key = storage_key_from_felt(storage_address)?;
+
+        self.blockifier_state
+            .set_storage_at(target, storage_key, felt_to_stark_felt(value));
+        Ok(())
+    }
+
+    /// Reads the value stored at `storage_address` of `target`.
+    /// Slots which were never written to are zero.
+    pub fn load(
+        &mut self,
+        target: ContractAddress,
+        storage_address: &Felt252,
+    ) -> Result<Felt252, CheatcodeError> {
+        let storage_key = storage_key_from_felt(storage_address)?;
+
+        let value = self
+            .blockifier_state
+            .get_storage_at(target, storage_key)
+            .map_err::<EnhancedHintError, _>(From::from)?;
+        Ok(stark_felt_to_felt(value))
+    }
+
+    /// Writes `values` to the storage variable `var_name` of `target` holding an array:
+    /// its length is stored at the address of the variable and the elements in the following slots.
+    /// An empty array is written as a zero length.
+    pub fn store_felt_array(
+        &mut self,
+        target: ContractAddress,
+        var_name: &str,
+        values: &[Felt252],
+    ) -> Result<(), CheatcodeError> {
+        let base = map_entry_address(var_name, &[]);
+        self.store(target, &base, &Felt252::from(values.len()))?;
+        for (offset, value) in (1_usize..).zip(values) {
+            self.store(target, &(base.clone() + Felt252::from(offset)), value)?;
+        }
+        Ok(())
+    }
+
+    /// Reads the array stored in the storage variable `var_name` of `target`,
+    /// laid out the way `store_felt_array` writes it.
+    pub fn load_felt_array(
+        &mut self,
+        target: ContractAddress,
+        var_name: &str,
+    ) -> Result<Vec<Felt252>, CheatcodeError> {
+        let base = map_entry_address(var_name, &[]);
+        let len = self.load(target, &base)?;
+        let len = len.to_usize().ok_or_else(|| {
+            EnhancedHintError::from(anyhow!(
+                "Invalid length {len} of the array stored in {var_name}"
+            ))
+        })?;
+
+        (1..=len)
+            .map(|offset| self.load(target, &(base.clone() + Felt252::from(offset))))
+            .collect()
+    }
+
+    /// Sets the balance of `account` in the ERC20 `token` which keeps balances in `ERC20_balances`,
+    /// like the fee token does. The `u256` amount is given as its low and high parts.
+    pub fn set_balance(
+        &mut self,
+        token: ContractAddress,
+        account: ContractAddress,
+        amount_low: u128,
+        amount_high: u128,
+    ) -> Result<(), CheatcodeError> {
+        let low_address = map_entry_address("ERC20_balances", &[contract_address_to_felt(account)]);
+        let high_address = storage_address_from_base(&low_address, 1);
+
+        self.store(token, &low_address, &Felt252::from(amount_low))?;
+        self.store(token, &high_address, &Felt252::from(amount_high))
+    }
+
+    /// Lists every non-zero storage slot of `target` together with its value, ordered by the slot address.
+    /// Only the local state is included, storage of a forked network is not fetched.
+    #[must_use]
+    pub fn dump_storage(&self, target: ContractAddress) -> Vec<(Felt252, Felt252)> {
+        let mut storage: Vec<_> = self
+            .flattened_state_reader()
+            .storage_view
+            .into_iter()
+            .filter(|((contract_address, _), value)| {
+                *contract_address == target && *value != StarkFelt::default()
+            })
+            .map(|((_, storage_key), value)| (*storage_key.0.key(), value))
+            .collect();
+        storage.sort();
+
+        storage
+            .into_iter()
+            .map(|(storage_address, value)| {
+                (
+                    stark_felt_to_felt(storage_address),
+                    stark_felt_to_felt(value),
+                )
+            })
+            .collect()
+    }
+}
+
+fn storage_key_from_felt(felt: &Felt252) -> Result<StorageKey, EnhancedHintError> {
+    Ok(StorageKey(PatriciaKey::try_from(felt_to_stark_felt(felt))?))
+}
@@ -1,12 +1,32 @@
+use crate::state::{start_cheat, CheatSpan};
 use crate::CheatnetState;
 use cairo_felt::Felt252;
+use num_traits::ToPrimitive;
 use starknet_api::core::ContractAddress;
 
 impl CheatnetState {
     pub fn start_warp(&mut self, contract_address: ContractAddress, timestamp: Felt252) {
+        self.warp(contract_address, timestamp, CheatSpan::Indefinite);
+    }
+
+    /// Changes the block timestamp for calls into `contract_address` for the given `span`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self)))]
+    pub fn warp(&mut self, contract_address: ContractAddress, timestamp: Felt252, span: CheatSpan) {
+        start_cheat(
+            &mut self.cheatcode_state.warped_contracts,
+            contract_address,
+            timestamp,
+            span,
+        );
+    }
+
+    /// The block timestamp calls into `contract_address` currently see, if it is warped,
+    /// either by `warp` or globally. Returns `None` if the value doesn't fit in `u64`.
+    #[must_use]
+    pub fn get_warp(&self, contract_address: ContractAddress) -> Option<u64> {
         self.cheatcode_state
-            .warped_contracts
-            .insert(contract_address, timestamp);
+            .warped_timestamp(&contract_address)
+            .and_then(ToPrimitive::to_u64)
     }
 
     pub fn stop_warp(&mut self, contract_address: ContractAddress) {
@@ -14,4 +34,14 @@ impl CheatnetState {
             .warped_contracts
             .remove(&contract_address);
     }
+
+    /// Changes the block timestamp for calls into all contracts until `stop_warp_global`.
+    /// Contracts warped with `warp` see their own timestamp instead.
+    pub fn start_warp_global(&mut self, timestamp: Felt252) {
+        self.cheatcode_state.global_warp = Some(timestamp);
+    }
+
+    pub fn stop_warp_global(&mut self) {
+        self.cheatcode_state.global_warp = None;
+    }
 }
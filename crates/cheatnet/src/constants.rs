@@ -46,8 +46,30 @@ pub const TEST_FAULTY_ACCOUNT_CONTRACT_CLASS_HASH: &str = "0x113";
 pub const SECURITY_TEST_CLASS_HASH: &str = "0x114";
 pub const TEST_ERC20_CONTRACT_CLASS_HASH: &str = "0x1010";
 
+/// Parameters of the block in which the tests are executed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockContextConfig {
+    pub chain_id: ChainId,
+    pub block_number: BlockNumber,
+    pub block_timestamp: BlockTimestamp,
+    pub sequencer_address: ContractAddress,
+    pub gas_price: u128,
+}
+
+impl Default for BlockContextConfig {
+    fn default() -> Self {
+        BlockContextConfig {
+            chain_id: ChainId("SN_GOERLI".to_string()),
+            block_number: BlockNumber(2000),
+            block_timestamp: BlockTimestamp::default(),
+            sequencer_address: ContractAddress(patricia_key!(TEST_SEQUENCER_ADDRESS)),
+            gas_price: 100 * u128::pow(10, 9),
+        }
+    }
+}
+
 #[must_use]
-pub fn build_block_context() -> BlockContext {
+pub fn build_block_context(config: &BlockContextConfig) -> BlockContext {
     // blockifier::test_utils::create_for_account_testing
     let vm_resource_fee_cost = Arc::new(HashMap::from([
         (constants::N_STEPS_RESOURCE.to_string(), 1_f64),
@@ -61,13 +83,13 @@ pub fn build_block_context() -> BlockContext {
     ]));
 
     BlockContext {
-        chain_id: ChainId("SN_GOERLI".to_string()),
-        block_number: BlockNumber(2000),
-        block_timestamp: BlockTimestamp::default(),
-        sequencer_address: ContractAddress(patricia_key!(TEST_SEQUENCER_ADDRESS)),
+        chain_id: config.chain_id.clone(),
+        block_number: config.block_number,
+        block_timestamp: config.block_timestamp,
+        sequencer_address: config.sequencer_address,
         fee_token_address: ContractAddress(patricia_key!(TEST_ERC20_CONTRACT_ADDRESS)),
         vm_resource_fee_cost,
-        gas_price: 100 * u128::pow(10, 9),
+        gas_price: config.gas_price,
         invoke_tx_max_n_steps: 1_000_000,
         validate_max_n_steps: 1_000_000,
         max_recursion_depth: 50,
@@ -143,7 +165,6 @@ pub fn build_testing_state(predeployed_contracts: &Utf8PathBuf) -> CachedState<D
         predeployed_contracts,
         "erc20_contract_without_some_syscalls_compiled.json",
     );
-    let block_context = build_block_context();
     let test_account_class_hash = ClassHash(stark_felt!(TEST_ACCOUNT_CONTRACT_CLASS_HASH));
     let test_erc20_class_hash = ClassHash(stark_felt!(TEST_ERC20_CONTRACT_CLASS_HASH));
 
@@ -155,7 +176,7 @@ pub fn build_testing_state(predeployed_contracts: &Utf8PathBuf) -> CachedState<D
     // A random address that is unlikely to equal the result of the calculation of a contract
     // address.
     let test_account_address = ContractAddress(patricia_key!(TEST_ACCOUNT_CONTRACT_ADDRESS));
-    let test_erc20_address = block_context.fee_token_address;
+    let test_erc20_address = ContractAddress(patricia_key!(TEST_ERC20_CONTRACT_ADDRESS));
     let address_to_class_hash = HashMap::from([
         (test_account_address, test_account_class_hash),
         (test_erc20_address, test_erc20_class_hash),
@@ -1,7 +1,15 @@
-use blockifier::execution::execution_utils::stark_felt_to_felt;
+use crate::abi::SINGLE_FELT_TYPES;
+use crate::cheatcodes::{CheatcodeError, CheatcodeFailure};
+use anyhow::{anyhow, bail, Result};
+use blockifier::abi::abi_utils::get_storage_var_address;
+use blockifier::execution::execution_utils::{felt_to_stark_felt, stark_felt_to_felt};
 use cairo_felt::Felt252;
+use num_traits::ToPrimitive;
 use starknet::core::utils::get_selector_from_name;
 use starknet_api::core::{ClassHash, ContractAddress};
+use starknet_api::hash::StarkFelt;
+
+const BYTES_IN_BYTES31: usize = 31;
 
 #[must_use]
 pub fn felt_selector_from_name(name: &str) -> Felt252 {
@@ -9,27 +17,362 @@ pub fn felt_selector_from_name(name: &str) -> Felt252 {
     Felt252::from_bytes_be(&selector.to_bytes_be())
 }
 
+/// Encodes `short_str` like a Cairo short string literal, without validating it.
+/// Strings longer than 31 bytes are reduced modulo the field prime instead of failing,
+/// use `felt_from_short_string_checked` for strings which aren't known to be valid.
 #[must_use]
 pub fn felt_from_short_string(short_str: &str) -> Felt252 {
     return Felt252::from_bytes_be(short_str.as_bytes());
 }
 
+/// Encodes `short_str` like a Cairo short string literal.
+/// Fails if it contains non-ASCII characters or is longer than 31 characters.
+pub fn felt_from_short_string_checked(short_str: &str) -> Result<Felt252> {
+    if !short_str.is_ascii() {
+        bail!("Short string {short_str:?} contains non-ASCII characters");
+    }
+    if short_str.len() > BYTES_IN_BYTES31 {
+        bail!(
+            "Short string {short_str:?} is {} characters long, at most {BYTES_IN_BYTES31} fit in a felt",
+            short_str.len()
+        );
+    }
+    Ok(felt_from_short_string(short_str))
+}
+
+/// Decodes `felt` into the ASCII short string it encodes, the inverse of `felt_from_short_string`.
+/// Decoding stops at the first non-printable character. Fails on non-ASCII bytes.
+pub fn felt_to_short_string(felt: &Felt252) -> Result<String> {
+    let bytes = felt.to_bytes_be();
+    let bytes = bytes.strip_prefix(&[0_u8][..]).unwrap_or(&bytes);
+    if bytes.len() > BYTES_IN_BYTES31 {
+        bail!("Felt {felt} is too big to be a short string");
+    }
+    if let Some(byte) = bytes.iter().find(|byte| !byte.is_ascii()) {
+        bail!("Felt {felt} contains a non-ASCII byte {byte:#x}");
+    }
+
+    Ok(bytes
+        .iter()
+        .take_while(|byte| byte.is_ascii_graphic() || **byte == b' ')
+        .map(|byte| char::from(*byte))
+        .collect())
+}
+
+/// Serializes `string` the way Cairo serializes a `ByteArray`:
+/// `[num_full_words, ...full_words, pending_word, pending_word_len]`,
+/// where full words are 31 byte chunks of the string and the pending word holds the rest.
+#[must_use]
+pub fn byte_array_from_string(string: &str) -> Vec<Felt252> {
+    let chunks: Vec<&[u8]> = string.as_bytes().chunks(BYTES_IN_BYTES31).collect();
+    let (full_words, pending_word) = match chunks.split_last() {
+        Some((last, full_words)) if last.len() < BYTES_IN_BYTES31 => (full_words, *last),
+        _ => (chunks.as_slice(), &[][..]),
+    };
+
+    let mut felts = vec![Felt252::from(full_words.len())];
+    felts.extend(full_words.iter().map(|word| Felt252::from_bytes_be(word)));
+    felts.push(Felt252::from_bytes_be(pending_word));
+    felts.push(Felt252::from(pending_word.len()));
+    felts
+}
+
+/// Decodes a string serialized as a Cairo `ByteArray`, the inverse of `byte_array_from_string`.
+pub fn string_from_byte_array(felts: &[Felt252]) -> Result<String> {
+    let (full_words_len, rest) = felts
+        .split_first()
+        .ok_or_else(|| anyhow!("ByteArray is empty"))?;
+    let full_words_len = full_words_len
+        .to_usize()
+        .ok_or_else(|| anyhow!("Invalid number of ByteArray words"))?;
+    // The number of words, the words, the pending word and its length
+    let expected_len = full_words_len
+        .checked_add(3)
+        .ok_or_else(|| anyhow!("Invalid number of ByteArray words"))?;
+    if felts.len() != expected_len {
+        bail!(
+            "Expected {expected_len} felts in ByteArray, got {}",
+            felts.len()
+        );
+    }
+    let (full_words, pending) = rest.split_at(full_words_len);
+    let pending_word_len = pending[1]
+        .to_usize()
+        .filter(|len| *len < BYTES_IN_BYTES31)
+        .ok_or_else(|| anyhow!("Invalid ByteArray pending word length"))?;
+
+    let mut bytes = vec![];
+    for word in full_words {
+        bytes.extend(felt_to_bytes(word, BYTES_IN_BYTES31)?);
+    }
+    bytes.extend(felt_to_bytes(&pending[0], pending_word_len)?);
+
+    String::from_utf8(bytes).map_err(|_| anyhow!("ByteArray is not a valid UTF-8 string"))
+}
+
+// Big-endian encoding of `felt` on exactly `len` bytes
+fn felt_to_bytes(felt: &Felt252, len: usize) -> Result<Vec<u8>> {
+    let bytes = felt.to_bytes_be();
+    let bytes = bytes.strip_prefix(&[0_u8][..]).unwrap_or(&bytes);
+    if bytes.len() > len {
+        bail!("ByteArray word does not fit in {len} bytes");
+    }
+    let mut padded = vec![0; len - bytes.len()];
+    padded.extend_from_slice(bytes);
+    Ok(padded)
+}
+
 #[must_use]
 pub fn contract_address_to_felt(contract_address: ContractAddress) -> Felt252 {
     stark_felt_to_felt(*contract_address.0.key())
 }
 
+/// Fails recoverably if the felt is out of the range of contract addresses, so a malformed value
+/// coming from the Cairo code doesn't abort the whole test run.
+pub fn contract_address_from_felt(felt: &Felt252) -> Result<ContractAddress, CheatcodeError> {
+    ContractAddress::try_from(felt_to_stark_felt(felt))
+        .map_err(|_| CheatcodeFailure::InvalidContractAddress(felt.clone()).into())
+}
+
 #[must_use]
 pub fn class_hash_to_felt(class_hash: ClassHash) -> Felt252 {
     stark_felt_to_felt(class_hash.0)
 }
 
+/// Calculates the storage address of a storage variable, e.g. `balance`, or its entry under
+/// the given keys if the variable is a `LegacyMap`, e.g. `balances[account]`.
+#[must_use]
+pub fn map_entry_address(var_name: &str, keys: &[Felt252]) -> Felt252 {
+    let keys: Vec<StarkFelt> = keys.iter().map(felt_to_stark_felt).collect();
+    let storage_key = get_storage_var_address(var_name, &keys)
+        .unwrap_or_else(|_| panic!("Failed to calculate storage address of {var_name}"));
+    stark_felt_to_felt(*storage_key.0.key())
+}
+
+/// Calculates the address of a value stored in consecutive slots after `base`,
+/// e.g. the high part of a stored `u256` is at offset 1.
+#[must_use]
+pub fn storage_address_from_base(base: &Felt252, offset: u8) -> Felt252 {
+    base.clone() + Felt252::from(offset)
+}
+
+/// Calculates the address of the value at `offset` of a storage variable, or its entry under
+/// the given keys, e.g. the second member of a struct stored in `positions[key]` is at offset 1.
+#[must_use]
+pub fn storage_var_address(var_name: &str, keys: &[Felt252], offset: u8) -> Felt252 {
+    storage_address_from_base(&map_entry_address(var_name, keys), offset)
+}
+
+/// Serializes a `u256` given as its lower and upper 128 bits, low first, like Cairo does.
+#[must_use]
+pub fn u256_to_felts(low: u128, high: u128) -> [Felt252; 2] {
+    [Felt252::from(low), Felt252::from(high)]
+}
+
+/// Builds calldata by appending values serialized the way Cairo `Serde` does.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CalldataBuilder {
+    calldata: Vec<Felt252>,
+}
+
+impl CalldataBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn add_felt(mut self, felt: Felt252) -> Self {
+        self.calldata.push(felt);
+        self
+    }
+
+    #[must_use]
+    pub fn add_u256(mut self, low: u128, high: u128) -> Self {
+        self.calldata.extend(u256_to_felts(low, high));
+        self
+    }
+
+    /// Appends an enum value: the index of its variant, followed by the variant's serialized payload.
+    #[must_use]
+    pub fn add_enum(mut self, variant_index: usize, payload: &[Felt252]) -> Self {
+        self.calldata.push(Felt252::from(variant_index));
+        self.calldata.extend_from_slice(payload);
+        self
+    }
+
+    /// Appends an `Array` or a `Span`, prefixed with its length.
+    #[must_use]
+    pub fn add_array(mut self, array: &[Felt252]) -> Self {
+        self.calldata.push(Felt252::from(array.len()));
+        self.calldata.extend_from_slice(array);
+        self
+    }
+
+    #[must_use]
+    pub fn add_short_string(self, short_str: &str) -> Self {
+        self.add_felt(felt_from_short_string(short_str))
+    }
+
+    #[must_use]
+    pub fn add_byte_array(mut self, string: &str) -> Self {
+        self.calldata.extend(byte_array_from_string(string));
+        self
+    }
+
+    #[must_use]
+    pub fn build(self) -> Vec<Felt252> {
+        self.calldata
+    }
+}
+
+/// Type of a value returned by a contract, describing how to decode its return data.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutputType {
+    Felt252,
+    U256,
+    Bool,
+    ShortString,
+    /// An `Array` or a `Span`.
+    Array(Box<OutputType>),
+    /// A struct or a tuple, serialized as its members one after another.
+    Struct(Vec<OutputType>),
+}
+
+impl OutputType {
+    /// Parses a type as it's named in a contract ABI, e.g. `core::array::Array::<core::felt252>`.
+    /// Types which are serialized into a single felt, e.g. `ContractAddress`, are decoded as felts.
+    /// Returns `None` for structs and other types which can't be resolved from the name alone.
+    #[must_use]
+    pub fn from_abi_type(ty: &str) -> Option<Self> {
+        if let Some(inner) = ty
+            .strip_prefix("core::array::Array::<")
+            .or_else(|| ty.strip_prefix("core::array::Span::<"))
+            .and_then(|rest| rest.strip_suffix('>'))
+        {
+            return Some(OutputType::Array(Box::new(Self::from_abi_type(inner)?)));
+        }
+        match ty {
+            "()" => Some(OutputType::Struct(vec![])),
+            "core::integer::u256" => Some(OutputType::U256),
+            "core::bool" => Some(OutputType::Bool),
+            ty if SINGLE_FELT_TYPES.contains(&ty) => Some(OutputType::Felt252),
+            _ => None,
+        }
+    }
+}
+
+/// Value decoded from return data with `decode_retdata`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodedValue {
+    Felt252(Felt252),
+    U256 { low: u128, high: u128 },
+    Bool(bool),
+    ShortString(String),
+    Array(Vec<DecodedValue>),
+    Struct(Vec<DecodedValue>),
+}
+
+/// Decodes return data serialized the way Cairo `Serde` does, the inverse of `CalldataBuilder`.
+/// Fails if the data doesn't match `output_type` or there is data left after decoding it.
+pub fn decode_retdata(output_type: &OutputType, retdata: &[Felt252]) -> Result<DecodedValue> {
+    let mut remaining = retdata;
+    let value = decode_value(output_type, &mut remaining)?;
+    if !remaining.is_empty() {
+        bail!(
+            "Expected {} felts of return data, got {}",
+            retdata.len() - remaining.len(),
+            retdata.len()
+        );
+    }
+    Ok(value)
+}
+
+fn decode_value(output_type: &OutputType, retdata: &mut &[Felt252]) -> Result<DecodedValue> {
+    match output_type {
+        OutputType::Felt252 => Ok(DecodedValue::Felt252(next_felt(retdata)?)),
+        OutputType::U256 => {
+            let low = next_u128(retdata)?;
+            let high = next_u128(retdata)?;
+            Ok(DecodedValue::U256 { low, high })
+        }
+        OutputType::Bool => {
+            let felt = next_felt(retdata)?;
+            match felt.to_u8() {
+                Some(0) => Ok(DecodedValue::Bool(false)),
+                Some(1) => Ok(DecodedValue::Bool(true)),
+                _ => bail!("Felt {felt} is not a valid bool"),
+            }
+        }
+        OutputType::ShortString => Ok(DecodedValue::ShortString(felt_to_short_string(
+            &next_felt(retdata)?,
+        )?)),
+        OutputType::Array(item_type) => {
+            let len = next_felt(retdata)?;
+            let len = len
+                .to_usize()
+                .ok_or_else(|| anyhow!("Invalid array length {len}"))?;
+            let items = (0..len)
+                .map(|_| decode_value(item_type, retdata))
+                .collect::<Result<_>>()?;
+            Ok(DecodedValue::Array(items))
+        }
+        OutputType::Struct(member_types) => {
+            let members = member_types
+                .iter()
+                .map(|member_type| decode_value(member_type, retdata))
+                .collect::<Result<_>>()?;
+            Ok(DecodedValue::Struct(members))
+        }
+    }
+}
+
+fn next_felt(retdata: &mut &[Felt252]) -> Result<Felt252> {
+    let (felt, rest) = retdata
+        .split_first()
+        .ok_or_else(|| anyhow!("Return data ended unexpectedly"))?;
+    *retdata = rest;
+    Ok(felt.clone())
+}
+
+fn next_u128(retdata: &mut &[Felt252]) -> Result<u128> {
+    let felt = next_felt(retdata)?;
+    felt.to_u128()
+        .ok_or_else(|| anyhow!("Felt {felt} does not fit in u128"))
+}
+
 #[cfg(test)]
 mod test {
-    use starknet_api::hash::StarkFelt;
-
     use super::*;
 
+    #[test]
+    fn serializing_u256() {
+        assert_eq!(
+            u256_to_felts(u128::MAX, 1),
+            [Felt252::from(u128::MAX), Felt252::from(1)]
+        );
+        assert_eq!(
+            CalldataBuilder::new().add_u256(2, 3).build(),
+            u256_to_felts(2, 3).to_vec()
+        );
+    }
+
+    #[test]
+    fn serializing_enum() {
+        assert_eq!(
+            CalldataBuilder::new()
+                .add_enum(2, &[Felt252::from(5), Felt252::from(6)])
+                .add_enum(0, &[])
+                .build(),
+            vec![
+                Felt252::from(2),
+                Felt252::from(5),
+                Felt252::from(6),
+                Felt252::from(0)
+            ]
+        );
+    }
+
     #[test]
     fn parsing_felt_from_short_string() {
         let cases = [
@@ -43,6 +386,221 @@ mod test {
         }
     }
 
+    #[test]
+    fn parsing_felt_from_short_string_checked() {
+        let full_length_string = "a short string of 31 characters";
+        assert_eq!(
+            felt_from_short_string_checked(full_length_string).unwrap(),
+            felt_from_short_string(full_length_string)
+        );
+        assert_eq!(
+            felt_from_short_string_checked("").unwrap(),
+            Felt252::from(0)
+        );
+
+        let too_long = format!("{full_length_string}!");
+        assert!(felt_from_short_string_checked(&too_long).is_err());
+        assert!(felt_from_short_string_checked("zażółć").is_err());
+    }
+
+    #[test]
+    fn decoding_felt_to_short_string() {
+        let full_length_string = "a short string of 31 characters";
+        let cases = ["", "{", "PANIK", full_length_string];
+
+        for str in cases {
+            assert_eq!(
+                felt_to_short_string(&felt_from_short_string(str)).unwrap(),
+                str
+            );
+        }
+        assert_eq!(felt_to_short_string(&Felt252::from(0)).unwrap(), "");
+    }
+
+    #[test]
+    fn decoding_felt_to_short_string_stops_at_non_printable() {
+        let felt = felt_from_short_string("abc\ndef");
+
+        assert_eq!(felt_to_short_string(&felt).unwrap(), "abc");
+    }
+
+    #[test]
+    fn decoding_invalid_felt_to_short_string() {
+        let non_ascii = Felt252::from_bytes_be(&[b'a', 0xff]);
+        assert!(felt_to_short_string(&non_ascii).is_err());
+
+        let too_long = Felt252::from_bytes_be(&[1; 32]);
+        assert!(felt_to_short_string(&too_long).is_err());
+    }
+
+    #[test]
+    fn byte_array_round_trip() {
+        let cases = [
+            "",
+            "a",
+            "thirty bytes long string here.",
+            "exactly thirty one bytes string",
+            "this string is longer than thirty one bytes, it spans three words...",
+            "zażółć gęślą jaźń",
+        ];
+
+        for string in cases {
+            let felts = byte_array_from_string(string);
+            assert_eq!(string_from_byte_array(&felts).unwrap(), string);
+        }
+    }
+
+    #[test]
+    fn byte_array_layout() {
+        assert_eq!(
+            byte_array_from_string(""),
+            vec![Felt252::from(0), Felt252::from(0), Felt252::from(0)]
+        );
+        assert_eq!(
+            byte_array_from_string("abc"),
+            vec![
+                Felt252::from(0),
+                felt_from_short_string("abc"),
+                Felt252::from(3)
+            ]
+        );
+
+        let full_word = "a".repeat(31);
+        assert_eq!(
+            byte_array_from_string(&format!("{full_word}bc")),
+            vec![
+                Felt252::from(1),
+                felt_from_short_string(&full_word),
+                felt_from_short_string("bc"),
+                Felt252::from(2)
+            ]
+        );
+        assert_eq!(
+            byte_array_from_string(&full_word),
+            vec![
+                Felt252::from(1),
+                felt_from_short_string(&full_word),
+                Felt252::from(0),
+                Felt252::from(0)
+            ]
+        );
+    }
+
+    #[test]
+    fn string_from_invalid_byte_array() {
+        assert!(string_from_byte_array(&[]).is_err());
+        // Missing pending word length
+        assert!(string_from_byte_array(&[Felt252::from(0), Felt252::from(0)]).is_err());
+        // Pending word longer than its length
+        assert!(string_from_byte_array(&[
+            Felt252::from(0),
+            felt_from_short_string("abc"),
+            Felt252::from(2)
+        ])
+        .is_err());
+        // Number of words which overflows the expected length
+        assert!(string_from_byte_array(&[
+            Felt252::from(usize::MAX),
+            Felt252::from(0),
+            Felt252::from(0)
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn calldata_builder_struct_with_array() {
+        // struct Order { id: felt252, amount: u256, items: Array<felt252>, note: felt252 }
+        let calldata = CalldataBuilder::new()
+            .add_felt(Felt252::from(7))
+            .add_u256(100, 1)
+            .add_array(&[Felt252::from(10), Felt252::from(20), Felt252::from(30)])
+            .add_short_string("note")
+            .build();
+
+        assert_eq!(
+            calldata,
+            vec![
+                Felt252::from(7),
+                Felt252::from(100),
+                Felt252::from(1),
+                Felt252::from(3),
+                Felt252::from(10),
+                Felt252::from(20),
+                Felt252::from(30),
+                felt_from_short_string("note"),
+            ]
+        );
+    }
+
+    #[test]
+    fn calldata_builder_empty_array_and_byte_array() {
+        let calldata = CalldataBuilder::new()
+            .add_array(&[])
+            .add_byte_array("abc")
+            .build();
+
+        let mut expected = vec![Felt252::from(0)];
+        expected.extend(byte_array_from_string("abc"));
+        assert_eq!(calldata, expected);
+    }
+
+    #[test]
+    fn decode_retdata_struct_with_array() {
+        let retdata = CalldataBuilder::new()
+            .add_u256(100, 1)
+            .add_array(&[felt_from_short_string("a"), felt_from_short_string("b")])
+            .add_felt(Felt252::from(1))
+            .build();
+        let output_type = OutputType::Struct(vec![
+            OutputType::U256,
+            OutputType::Array(Box::new(OutputType::ShortString)),
+            OutputType::Bool,
+        ]);
+
+        assert_eq!(
+            decode_retdata(&output_type, &retdata).unwrap(),
+            DecodedValue::Struct(vec![
+                DecodedValue::U256 { low: 100, high: 1 },
+                DecodedValue::Array(vec![
+                    DecodedValue::ShortString("a".to_string()),
+                    DecodedValue::ShortString("b".to_string()),
+                ]),
+                DecodedValue::Bool(true),
+            ])
+        );
+    }
+
+    #[test]
+    fn decode_retdata_invalid() {
+        // Too short
+        assert!(decode_retdata(&OutputType::U256, &[Felt252::from(1)]).is_err());
+        // Too long
+        assert!(
+            decode_retdata(&OutputType::Felt252, &[Felt252::from(1), Felt252::from(2)]).is_err()
+        );
+        assert!(decode_retdata(&OutputType::Bool, &[Felt252::from(2)]).is_err());
+        // Array length larger than the data
+        let array = OutputType::Array(Box::new(OutputType::Felt252));
+        assert!(decode_retdata(&array, &[Felt252::from(2), Felt252::from(1)]).is_err());
+    }
+
+    #[test]
+    fn output_type_from_abi_type() {
+        assert_eq!(
+            OutputType::from_abi_type("core::array::Span::<core::integer::u256>"),
+            Some(OutputType::Array(Box::new(OutputType::U256)))
+        );
+        assert_eq!(
+            OutputType::from_abi_type("core::starknet::contract_address::ContractAddress"),
+            Some(OutputType::Felt252)
+        );
+        assert_eq!(
+            OutputType::from_abi_type("core::bool"),
+            Some(OutputType::Bool)
+        );
+        assert_eq!(OutputType::from_abi_type("test::Point"), None);
+    }
+
     #[test]
     fn test_contract_address_to_felt() {
         let cases = [
@@ -72,4 +630,51 @@ mod test {
             assert_eq!(class_hash_to_felt(input), expected);
         }
     }
+
+    #[test]
+    fn storage_var_address() {
+        assert_eq!(
+            map_entry_address("balance", &[]),
+            Felt252::parse_bytes(
+                b"206f38f7e4f15e87567361213c28f235cccdaa1d7fd34c9db1dfe9489c6a091",
+                16
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn address_from_base_and_offset() {
+        let base = map_entry_address("balance", &[]);
+
+        assert_eq!(storage_address_from_base(&base, 0), base);
+        assert_eq!(
+            storage_address_from_base(&base, 1),
+            base.clone() + Felt252::from(1)
+        );
+        assert_ne!(
+            map_entry_address("balances", &[Felt252::from(1)]),
+            map_entry_address("balances", &[Felt252::from(2)])
+        );
+    }
+
+    #[test]
+    fn contract_address_from_out_of_range_felt() {
+        assert_eq!(
+            contract_address_from_felt(&Felt252::from(123)).unwrap(),
+            ContractAddress::from(123_u128)
+        );
+
+        // 2**251 is the first felt out of the range of addresses
+        let felt = Felt252::parse_bytes(
+            b"800000000000000000000000000000000000000000000000000000000000000",
+            16,
+        )
+        .unwrap();
+        assert!(matches!(
+            contract_address_from_felt(&felt),
+            Err(CheatcodeError::Recoverable(CheatcodeFailure::InvalidContractAddress(value)))
+                if value == felt
+        ));
+    }
 }
@@ -0,0 +1,273 @@
+use anyhow::{Context, Result};
+use blockifier::execution::execution_utils::{felt_to_stark_felt, stark_felt_to_felt};
+use cairo_felt::Felt252;
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::{Deserialize, Serialize};
+use starknet::core::types::ContractClass;
+use starknet_api::block::BlockNumber;
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
+use starknet_api::hash::StarkFelt;
+use starknet_api::state::StorageKey;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use url::Url;
+
+/// Bumped whenever the layout of the cache file changes, files with other versions are discarded.
+const CACHE_VERSION: usize = 2;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct ForkCacheContent {
+    cache_version: usize,
+    storage_at: HashMap<String, HashMap<String, String>>,
+    nonce_at: HashMap<String, String>,
+    class_hash_at: HashMap<String, String>,
+    // Classes as returned by the node, they are compiled again after loading
+    class: HashMap<String, ContractClass>,
+}
+
+impl ForkCacheContent {
+    // Values at a pinned block never change, so the entries of both caches are kept
+    fn extend(&mut self, other: ForkCacheContent) {
+        for (contract_address, storage) in other.storage_at {
+            let cached_storage = self.storage_at.entry(contract_address).or_default();
+            for (key, value) in storage {
+                cached_storage.entry(key).or_insert(value);
+            }
+        }
+        for (contract_address, nonce) in other.nonce_at {
+            self.nonce_at.entry(contract_address).or_insert(nonce);
+        }
+        for (contract_address, class_hash) in other.class_hash_at {
+            self.class_hash_at
+                .entry(contract_address)
+                .or_insert(class_hash);
+        }
+        for (class_hash, contract_class) in other.class {
+            self.class.entry(class_hash).or_insert(contract_class);
+        }
+    }
+}
+
+/// Values fetched from a forked network at a single block.
+/// State at a pinned block never changes, so cached values never have to be invalidated.
+/// Clones share the cached values, which are saved to disk with `save`.
+/// Values fetched after the last `save` are saved when the last clone is dropped,
+/// errors of that save are only reported through the `tracing` feature.
+#[derive(Debug, Clone)]
+pub struct ForkCache {
+    shared: Arc<SharedForkCache>,
+}
+
+#[derive(Debug)]
+struct SharedForkCache {
+    fork_cache_content: Mutex<ForkCacheContent>,
+    cache_file: Option<Utf8PathBuf>,
+    // Set when values were fetched since the cache was loaded or saved
+    dirty: AtomicBool,
+}
+
+impl ForkCache {
+    /// Loads the cache of `url` at `block_number` from `cache_dir`.
+    /// Without `cache_dir` values are only cached in memory.
+    #[must_use]
+    pub fn load_or_new(url: &Url, block_number: BlockNumber, cache_dir: Option<&Utf8Path>) -> Self {
+        let cache_file = cache_dir.map(|cache_dir| cache_file_path(cache_dir, url, block_number));
+
+        let fork_cache_content = cache_file
+            .as_deref()
+            .and_then(read_cache_file)
+            .unwrap_or_else(|| ForkCacheContent {
+                cache_version: CACHE_VERSION,
+                ..Default::default()
+            });
+
+        ForkCache {
+            shared: Arc::new(SharedForkCache {
+                fork_cache_content: Mutex::new(fork_cache_content),
+                cache_file,
+                dirty: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    #[must_use]
+    pub fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> Option<StarkFelt> {
+        self.content()
+            .storage_at
+            .get(&stark_felt_to_string(*contract_address.0.key()))?
+            .get(&stark_felt_to_string(*key.0.key()))
+            .and_then(|value| stark_felt_from_string(value))
+    }
+
+    pub fn cache_get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+        value: StarkFelt,
+    ) {
+        self.content_to_update()
+            .storage_at
+            .entry(stark_felt_to_string(*contract_address.0.key()))
+            .or_default()
+            .insert(
+                stark_felt_to_string(*key.0.key()),
+                stark_felt_to_string(value),
+            );
+    }
+
+    #[must_use]
+    pub fn get_nonce_at(&self, contract_address: ContractAddress) -> Option<Nonce> {
+        self.content()
+            .nonce_at
+            .get(&stark_felt_to_string(*contract_address.0.key()))
+            .and_then(|nonce| stark_felt_from_string(nonce))
+            .map(Nonce)
+    }
+
+    pub fn cache_get_nonce_at(&self, contract_address: ContractAddress, nonce: Nonce) {
+        self.content_to_update().nonce_at.insert(
+            stark_felt_to_string(*contract_address.0.key()),
+            stark_felt_to_string(nonce.0),
+        );
+    }
+
+    #[must_use]
+    pub fn get_class_hash_at(&self, contract_address: ContractAddress) -> Option<ClassHash> {
+        self.content()
+            .class_hash_at
+            .get(&stark_felt_to_string(*contract_address.0.key()))
+            .and_then(|class_hash| stark_felt_from_string(class_hash))
+            .map(ClassHash)
+    }
+
+    pub fn cache_get_class_hash_at(
+        &self,
+        contract_address: ContractAddress,
+        class_hash: ClassHash,
+    ) {
+        self.content_to_update().class_hash_at.insert(
+            stark_felt_to_string(*contract_address.0.key()),
+            stark_felt_to_string(class_hash.0),
+        );
+    }
+
+    #[must_use]
+    pub fn get_class(&self, class_hash: ClassHash) -> Option<ContractClass> {
+        self.content()
+            .class
+            .get(&stark_felt_to_string(class_hash.0))
+            .cloned()
+    }
+
+    pub fn cache_get_class(&self, class_hash: ClassHash, contract_class: &ContractClass) {
+        self.content_to_update()
+            .class
+            .insert(stark_felt_to_string(class_hash.0), contract_class.clone());
+    }
+
+    /// Saves the values fetched since the cache was loaded to the cache file, together with
+    /// the values saved there in the meantime, e.g. by tests running in parallel.
+    /// Does nothing if nothing was fetched or the cache is kept only in memory.
+    pub fn save(&self) -> Result<()> {
+        self.shared.save()
+    }
+
+    fn content(&self) -> MutexGuard<'_, ForkCacheContent> {
+        self.shared
+            .fork_cache_content
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn content_to_update(&self) -> MutexGuard<'_, ForkCacheContent> {
+        self.shared.dirty.store(true, Ordering::SeqCst);
+        self.content()
+    }
+}
+
+impl SharedForkCache {
+    fn save(&self) -> Result<()> {
+        let Some(cache_file) = &self.cache_file else {
+            return Ok(());
+        };
+        let mut fork_cache_content = self
+            .fork_cache_content
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if !self.dirty.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        if let Some(saved_content) = read_cache_file(cache_file) {
+            fork_cache_content.extend(saved_content);
+        }
+        let cache_dir = cache_file
+            .parent()
+            .context("Fork cache file has no parent directory")?;
+        fs::create_dir_all(cache_dir).context("Failed to create the fork cache directory")?;
+
+        // Written to a temporary file first, so that a concurrently running test never reads
+        // a partially written cache
+        let mut temp_file = tempfile::NamedTempFile::new_in(cache_dir)
+            .context("Failed to create a temporary fork cache file")?;
+        temp_file
+            .write_all(
+                serde_json::to_string(&*fork_cache_content)
+                    .context("Failed to serialize the fork cache")?
+                    .as_bytes(),
+            )
+            .context("Failed to write the fork cache")?;
+        temp_file
+            .persist(cache_file)
+            .context("Failed to save the fork cache")?;
+
+        self.dirty.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+impl Drop for SharedForkCache {
+    fn drop(&mut self) {
+        // Failing to save the cache only makes the next run fetch the values again
+        let result = self.save();
+        #[cfg(feature = "tracing")]
+        if let Err(error) = &result {
+            tracing::warn!("Failed to save the fork cache: {error:#}");
+        }
+        let _ = result;
+    }
+}
+
+// Files which can't be read, e.g. written by a different cache version, are ignored
+fn read_cache_file(cache_file: &Utf8Path) -> Option<ForkCacheContent> {
+    let content = fs::read_to_string(cache_file).ok()?;
+    serde_json::from_str::<ForkCacheContent>(&content)
+        .ok()
+        .filter(|content| content.cache_version == CACHE_VERSION)
+}
+
+fn cache_file_path(cache_dir: &Utf8Path, url: &Url, block_number: BlockNumber) -> Utf8PathBuf {
+    let network: String = url
+        .as_str()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    cache_dir.join(format!("{network}_{}.json", block_number.0))
+}
+
+fn stark_felt_to_string(value: StarkFelt) -> String {
+    format!("0x{}", stark_felt_to_felt(value).to_str_radix(16))
+}
+
+// Invalid values, e.g. in a manually edited cache file, are treated as not cached
+fn stark_felt_from_string(value: &str) -> Option<StarkFelt> {
+    let value = value.trim_start_matches("0x");
+    Felt252::parse_bytes(value.as_bytes(), 16).map(|value| felt_to_stark_felt(&value))
+}
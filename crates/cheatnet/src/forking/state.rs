@@ -0,0 +1,412 @@
+use crate::forking::cache::ForkCache;
+use anyhow::{Context, Result};
+use blockifier::execution::contract_class::{
+    ContractClass as BlockifierContractClass, ContractClassV1,
+};
+use blockifier::state::errors::StateError;
+use blockifier::state::state_api::StateResult;
+use cairo_lang_starknet::casm_contract_class::CasmContractClass;
+use camino::Utf8PathBuf;
+use serde_json::Value;
+use starknet::core::types::{
+    BlockId, ContractClass, FieldElement, FlattenedSierraClass, StarknetError,
+};
+use starknet::providers::jsonrpc::{HttpTransport, JsonRpcClient};
+use starknet::providers::{
+    MaybeUnknownErrorCode, Provider, ProviderError, StarknetErrorWithMessage,
+};
+use starknet_api::block::BlockNumber;
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
+use starknet_api::hash::StarkFelt;
+use starknet_api::state::StorageKey;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+use url::Url;
+
+/// Node and block the state is forked from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForkConfig {
+    pub url: Url,
+    /// Pinned so that repeated runs observe the same on-chain state.
+    pub block_number: BlockNumber,
+    /// Directory where fetched values are cached between runs, nothing is saved to disk if `None`.
+    pub cache_dir: Option<Utf8PathBuf>,
+}
+
+/// Source of the forked state, fetching values from the network at the pinned block.
+pub(crate) trait ForkClient: Debug {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<StarkFelt>;
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce>;
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash>;
+    fn get_class(&self, class_hash: ClassHash) -> StateResult<ContractClass>;
+}
+
+#[derive(Debug)]
+struct JsonRpcForkClient {
+    client: JsonRpcClient<HttpTransport>,
+    runtime: Runtime,
+    block_id: BlockId,
+}
+
+impl ForkClient for JsonRpcForkClient {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<StarkFelt> {
+        let response = self.runtime.block_on(self.client.get_storage_at(
+            field_element_from_stark_felt(*contract_address.0.key()),
+            field_element_from_stark_felt(*key.0.key()),
+            self.block_id,
+        ));
+        match response {
+            Ok(value) => Ok(stark_felt_from_field_element(value)),
+            Err(error) if is_contract_not_found(&error) => Ok(StarkFelt::default()),
+            Err(error) => Err(state_read_error("storage", &error)),
+        }
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        let response = self.runtime.block_on(self.client.get_nonce(
+            self.block_id,
+            field_element_from_stark_felt(*contract_address.0.key()),
+        ));
+        match response {
+            Ok(nonce) => Ok(Nonce(stark_felt_from_field_element(nonce))),
+            Err(error) if is_contract_not_found(&error) => Ok(Nonce::default()),
+            Err(error) => Err(state_read_error("nonce", &error)),
+        }
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        let response = self.runtime.block_on(self.client.get_class_hash_at(
+            self.block_id,
+            field_element_from_stark_felt(*contract_address.0.key()),
+        ));
+        match response {
+            Ok(class_hash) => Ok(ClassHash(stark_felt_from_field_element(class_hash))),
+            Err(error) if is_contract_not_found(&error) => Ok(ClassHash::default()),
+            Err(error) => Err(state_read_error("class hash", &error)),
+        }
+    }
+
+    fn get_class(&self, class_hash: ClassHash) -> StateResult<ContractClass> {
+        let response = self.runtime.block_on(
+            self.client
+                .get_class(self.block_id, field_element_from_stark_felt(class_hash.0)),
+        );
+        match response {
+            Ok(contract_class) => Ok(contract_class),
+            Err(ProviderError::StarknetError(StarknetErrorWithMessage {
+                code: MaybeUnknownErrorCode::Known(StarknetError::ClassHashNotFound),
+                ..
+            })) => Err(StateError::UndeclaredClassHash(class_hash)),
+            Err(error) => Err(state_read_error("class", &error)),
+        }
+    }
+}
+
+/// Reads state of a live Starknet network at a pinned block,
+/// caching every fetched value in a [`ForkCache`].
+#[derive(Debug, Clone)]
+pub struct ForkStateReader {
+    client: Arc<dyn ForkClient>,
+    cache: ForkCache,
+    // Compiling the fetched classes is expensive, so they are compiled once per reader
+    compiled_classes: HashMap<ClassHash, BlockifierContractClass>,
+}
+
+impl ForkStateReader {
+    #[must_use]
+    pub fn new(config: ForkConfig) -> Self {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to create a runtime for the fork state reader");
+        let cache = ForkCache::load_or_new(
+            &config.url,
+            config.block_number,
+            config.cache_dir.as_deref(),
+        );
+        let client = JsonRpcForkClient {
+            client: JsonRpcClient::new(HttpTransport::new(config.url)),
+            runtime,
+            block_id: BlockId::Number(config.block_number.0),
+        };
+
+        Self::with_client(Arc::new(client), cache)
+    }
+
+    pub(crate) fn with_client(client: Arc<dyn ForkClient>, cache: ForkCache) -> Self {
+        ForkStateReader {
+            client,
+            cache,
+            compiled_classes: HashMap::new(),
+        }
+    }
+
+    /// Saves the values fetched so far to the cache directory, see [`ForkCache::save`].
+    pub fn save_cache(&self) -> Result<()> {
+        self.cache.save()
+    }
+
+    pub fn get_storage_at(
+        &mut self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<StarkFelt> {
+        if let Some(value) = self.cache.get_storage_at(contract_address, key) {
+            return Ok(value);
+        }
+
+        let value = self.client.get_storage_at(contract_address, key)?;
+        self.cache
+            .cache_get_storage_at(contract_address, key, value);
+        Ok(value)
+    }
+
+    pub fn get_nonce_at(&mut self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        if let Some(nonce) = self.cache.get_nonce_at(contract_address) {
+            return Ok(nonce);
+        }
+
+        let nonce = self.client.get_nonce_at(contract_address)?;
+        self.cache.cache_get_nonce_at(contract_address, nonce);
+        Ok(nonce)
+    }
+
+    pub fn get_class_hash_at(
+        &mut self,
+        contract_address: ContractAddress,
+    ) -> StateResult<ClassHash> {
+        if let Some(class_hash) = self.cache.get_class_hash_at(contract_address) {
+            return Ok(class_hash);
+        }
+
+        let class_hash = self.client.get_class_hash_at(contract_address)?;
+        self.cache
+            .cache_get_class_hash_at(contract_address, class_hash);
+        Ok(class_hash)
+    }
+
+    /// Fetches the class declared on the forked network and compiles it,
+    /// so contracts deployed there can be called and library called.
+    /// Cairo 0 classes are not supported, so reading them fails.
+    pub fn get_compiled_contract_class(
+        &mut self,
+        class_hash: &ClassHash,
+    ) -> StateResult<BlockifierContractClass> {
+        if let Some(contract_class) = self.compiled_classes.get(class_hash) {
+            return Ok(contract_class.clone());
+        }
+
+        let contract_class = match self.cache.get_class(*class_hash) {
+            Some(contract_class) => contract_class,
+            None => {
+                let contract_class = self.client.get_class(*class_hash)?;
+                self.cache.cache_get_class(*class_hash, &contract_class);
+                contract_class
+            }
+        };
+        let compiled_class = match contract_class {
+            ContractClass::Sierra(sierra_class) => {
+                compile_sierra_class(&sierra_class).map_err(|error| {
+                    StateError::StateReadError(format!(
+                        "Failed to compile class {} fetched from the forked node: {error:#}",
+                        class_hash.0
+                    ))
+                })?
+            }
+            ContractClass::Legacy(_) => return Err(legacy_class_error(class_hash)),
+        };
+        self.compiled_classes
+            .insert(*class_hash, compiled_class.clone());
+        Ok(compiled_class)
+    }
+}
+
+fn compile_sierra_class(sierra_class: &FlattenedSierraClass) -> Result<BlockifierContractClass> {
+    let mut sierra_class = serde_json::to_value(sierra_class)?;
+    // The node returns the ABI as a string, it isn't needed to execute the class anyway
+    sierra_class["abi"] = Value::Null;
+    let sierra_class: cairo_lang_starknet::contract_class::ContractClass =
+        serde_json::from_value(sierra_class).context("Failed to parse Sierra class")?;
+
+    let casm_class = CasmContractClass::from_contract_class(sierra_class, true)
+        .context("Failed to compile Sierra class to CASM")?;
+    let casm_class = serde_json::to_string(&casm_class)?;
+    Ok(BlockifierContractClass::V1(
+        ContractClassV1::try_from_json_string(&casm_class)?,
+    ))
+}
+
+// Cairo 0 classes can't be executed, calls into contracts of such classes fail with this error
+fn legacy_class_error(class_hash: &ClassHash) -> StateError {
+    StateError::StateReadError(format!(
+        "Class {} fetched from the forked node is a Cairo 0 class, Cairo 0 classes are not supported",
+        class_hash.0
+    ))
+}
+
+fn is_contract_not_found<E>(error: &ProviderError<E>) -> bool {
+    matches!(
+        error,
+        ProviderError::StarknetError(StarknetErrorWithMessage {
+            code: MaybeUnknownErrorCode::Known(StarknetError::ContractNotFound),
+            ..
+        })
+    )
+}
+
+fn state_read_error<E: std::error::Error>(what: &str, error: &ProviderError<E>) -> StateError {
+    StateError::StateReadError(format!(
+        "Failed to fetch {what} from the forked node: {error}"
+    ))
+}
+
+fn field_element_from_stark_felt(value: StarkFelt) -> FieldElement {
+    FieldElement::from_bytes_be(&value.bytes().try_into().unwrap()).unwrap()
+}
+
+fn stark_felt_from_field_element(value: FieldElement) -> StarkFelt {
+    StarkFelt::new(value.to_bytes_be()).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    #[derive(Debug, Default)]
+    struct CountingForkClient {
+        calls: AtomicUsize,
+    }
+
+    impl ForkClient for CountingForkClient {
+        fn get_storage_at(
+            &self,
+            _contract_address: ContractAddress,
+            _key: StorageKey,
+        ) -> StateResult<StarkFelt> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(StarkFelt::from(123_u32))
+        }
+
+        fn get_nonce_at(&self, _contract_address: ContractAddress) -> StateResult<Nonce> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(Nonce(StarkFelt::from(3_u32)))
+        }
+
+        fn get_class_hash_at(&self, _contract_address: ContractAddress) -> StateResult<ClassHash> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(ClassHash(StarkFelt::from(456_u32)))
+        }
+
+        fn get_class(&self, class_hash: ClassHash) -> StateResult<ContractClass> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Err(StateError::UndeclaredClassHash(class_hash))
+        }
+    }
+
+    fn load_cache(cache_dir: &TempDir, block_number: u64) -> ForkCache {
+        let cache_dir = Utf8PathBuf::from_path_buf(cache_dir.path().to_path_buf()).unwrap();
+        ForkCache::load_or_new(
+            &Url::parse("http://localhost:5050/rpc").unwrap(),
+            BlockNumber(block_number),
+            Some(&cache_dir),
+        )
+    }
+
+    #[test]
+    fn second_run_reads_from_disk_cache() {
+        let cache_dir = TempDir::new().unwrap();
+        let contract_address = ContractAddress::from(1_u128);
+        let key = StorageKey::from(2_u128);
+
+        let client = Arc::new(CountingForkClient::default());
+        let mut reader = ForkStateReader::with_client(client.clone(), load_cache(&cache_dir, 1234));
+        reader.get_storage_at(contract_address, key).unwrap();
+        reader.get_nonce_at(contract_address).unwrap();
+        reader.get_class_hash_at(contract_address).unwrap();
+        assert_eq!(client.calls.load(Ordering::SeqCst), 3);
+        reader.save_cache().unwrap();
+
+        let client = Arc::new(CountingForkClient::default());
+        let mut reader = ForkStateReader::with_client(client.clone(), load_cache(&cache_dir, 1234));
+        assert_eq!(
+            reader.get_storage_at(contract_address, key).unwrap(),
+            StarkFelt::from(123_u32)
+        );
+        assert_eq!(
+            reader.get_nonce_at(contract_address).unwrap(),
+            Nonce(StarkFelt::from(3_u32))
+        );
+        assert_eq!(
+            reader.get_class_hash_at(contract_address).unwrap(),
+            ClassHash(StarkFelt::from(456_u32))
+        );
+        assert_eq!(client.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn cache_is_kept_per_block() {
+        let cache_dir = TempDir::new().unwrap();
+        let contract_address = ContractAddress::from(1_u128);
+        let key = StorageKey::from(2_u128);
+
+        let client = Arc::new(CountingForkClient::default());
+        let mut reader = ForkStateReader::with_client(client.clone(), load_cache(&cache_dir, 1234));
+        reader.get_storage_at(contract_address, key).unwrap();
+
+        let mut reader = ForkStateReader::with_client(client.clone(), load_cache(&cache_dir, 1235));
+        reader.get_storage_at(contract_address, key).unwrap();
+
+        assert_eq!(client.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn caches_of_parallel_runs_are_merged() {
+        let cache_dir = TempDir::new().unwrap();
+        let contract_address = ContractAddress::from(1_u128);
+        let key = StorageKey::from(2_u128);
+
+        let client = Arc::new(CountingForkClient::default());
+        let mut first_reader =
+            ForkStateReader::with_client(client.clone(), load_cache(&cache_dir, 1234));
+        let mut second_reader =
+            ForkStateReader::with_client(client.clone(), load_cache(&cache_dir, 1234));
+        first_reader.get_storage_at(contract_address, key).unwrap();
+        second_reader.get_nonce_at(contract_address).unwrap();
+        drop(first_reader);
+        drop(second_reader);
+
+        let client = Arc::new(CountingForkClient::default());
+        let mut reader = ForkStateReader::with_client(client.clone(), load_cache(&cache_dir, 1234));
+        reader.get_storage_at(contract_address, key).unwrap();
+        reader.get_nonce_at(contract_address).unwrap();
+        assert_eq!(client.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn clones_share_the_cache() {
+        let cache_dir = TempDir::new().unwrap();
+        let contract_address = ContractAddress::from(1_u128);
+        let key = StorageKey::from(2_u128);
+
+        let client = Arc::new(CountingForkClient::default());
+        let mut reader = ForkStateReader::with_client(client.clone(), load_cache(&cache_dir, 1234));
+        let mut clone = reader.clone();
+        clone.get_storage_at(contract_address, key).unwrap();
+        drop(clone);
+
+        reader.get_storage_at(contract_address, key).unwrap();
+        assert_eq!(client.calls.load(Ordering::SeqCst), 1);
+    }
+}
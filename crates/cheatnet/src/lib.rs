@@ -1,39 +1,412 @@
+use abi::ContractAbi;
+use anyhow::anyhow;
+use blockifier::block_context::BlockContext;
 use blockifier::state::cached_state::CachedState;
+use cairo_felt::Felt252;
+use cairo_lang_runner::short_string::as_cairo_short_string;
+use cairo_lang_starknet::contract_class::ContractClass;
 use camino::Utf8PathBuf;
-use constants::build_testing_state;
-use starknet_api::hash::StarkFelt;
+use cheatcodes::{CheatcodeError, CheatcodeFailure, EnhancedHintError};
+use constants::{
+    build_block_context, build_testing_state, BlockContextConfig, INITIAL_BALANCE,
+    TEST_ACCOUNT_CONTRACT_ADDRESS, TEST_ERC20_CONTRACT_ADDRESS,
+};
+use forking::state::{ForkConfig, ForkStateReader};
+use num_traits::ToPrimitive;
+use panic_data::PanicTrace;
+use reentrancy::{ReentrancyReport, ReentrancyTracker};
+use starknet_api::block::{BlockNumber, BlockTimestamp};
+use starknet_api::core::{ChainId, ClassHash, ContractAddress, PatriciaKey};
+use starknet_api::hash::{StarkFelt, StarkHash};
+use starknet_api::patricia_key;
 use starknet_api::transaction::ContractAddressSalt;
-use state::{CheatcodeState, DictStateReader};
+use state::{CheatcodeState, DictStateReader, PanicPolicy, UnknownSelectorBehavior};
+use std::any::Any;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
 
+pub mod abi;
+pub mod address;
 pub mod cheatcodes;
 pub mod constants;
 pub mod conversions;
+pub mod forking;
 pub mod panic_data;
+pub mod reentrancy;
 pub mod rpc;
+pub mod scarb;
+pub mod snapshot;
 pub mod state;
 
+/// State of a single test: the blockifier state and the cheatcodes applied to it.
+/// Every test is executed with a fresh instance, which is never shared with other tests,
+/// so deployments in one test don't affect addresses of contracts deployed in the others.
 pub struct CheatnetState {
     cheatcode_state: CheatcodeState,
     blockifier_state: CachedState<DictStateReader>,
+    // ABIs of the classes declared with `declare`
+    class_abis: HashMap<ClassHash, ContractAbi>,
+    // Names of the functions of the declared classes by their selectors, see `selector_to_name`
+    function_names: HashMap<ClassHash, HashMap<Felt252, String>>,
+    block_context_config: BlockContextConfig,
+    // Account sending the transactions and deploying the contracts
+    test_account_address: ContractAddress,
+    // Copy of the state reader the state was created with, used by `reset`
+    initial_state_reader: DictStateReader,
+    // Salt of the next contract deployed with `deploy`, starts at 0 for every state
     pub deploy_salt_base: u32,
+    // Mixed into the salt of every contract deployed with `deploy`, see `new_with_seed`
+    deploy_salt_seed: u64,
+    // Whether `declare` and `invoke` transactions pay their fee from the test account
+    charge_fee: bool,
+    panic_policy: PanicPolicy,
 }
 
 impl CheatnetState {
+    /// Creates the state with the test account and fee token predeployed.
+    /// When `fork_config` is given, state that isn't present locally is read from the forked network.
     #[must_use]
-    pub fn new(predeployed_contracts: &Utf8PathBuf) -> Self {
+    pub fn new(predeployed_contracts: &Utf8PathBuf, fork_config: Option<ForkConfig>) -> Self {
+        Self::new_with_block_context_config(
+            predeployed_contracts,
+            fork_config,
+            BlockContextConfig::default(),
+        )
+    }
+
+    /// Works like `new`, but executes everything in a block described by `block_context_config`.
+    #[must_use]
+    pub fn new_with_block_context_config(
+        predeployed_contracts: &Utf8PathBuf,
+        fork_config: Option<ForkConfig>,
+        block_context_config: BlockContextConfig,
+    ) -> Self {
+        let mut blockifier_state = build_testing_state(predeployed_contracts);
+        blockifier_state.state.fork_state_reader = fork_config.map(ForkStateReader::new);
+
         CheatnetState {
             cheatcode_state: CheatcodeState::new(),
-            blockifier_state: build_testing_state(predeployed_contracts),
+            initial_state_reader: blockifier_state.state.clone(),
+            blockifier_state,
+            class_abis: HashMap::new(),
+            function_names: HashMap::new(),
+            block_context_config,
+            test_account_address: ContractAddress(patricia_key!(TEST_ACCOUNT_CONTRACT_ADDRESS)),
             deploy_salt_base: 0,
+            deploy_salt_seed: 0,
+            charge_fee: false,
+            panic_policy: PanicPolicy::default(),
+        }
+    }
+
+    /// Works like `new`, but the salts the contracts are deployed with by `deploy` are derived from `seed`,
+    /// so their addresses are the same on every machine as long as the contracts are deployed in the same order.
+    /// Changing the seed changes the addresses of all such contracts. `new` uses seed 0.
+    #[must_use]
+    pub fn new_with_seed(
+        predeployed_contracts: &Utf8PathBuf,
+        fork_config: Option<ForkConfig>,
+        seed: u64,
+    ) -> Self {
+        let mut state = Self::new(predeployed_contracts, fork_config);
+        state.deploy_salt_seed = seed;
+        state
+    }
+
+    /// Works like `new`, but also deploys an account of `account_class` at `account_address`
+    /// and funds it with fee tokens, so the transactions sent with `declare` and `invoke`
+    /// go through its `__validate__` and `__execute__`.
+    /// The account is kept by `reset`. Its `__execute__` has to take a single call,
+    /// `(contract_address, selector, calldata)`, like the predeployed account does.
+    pub fn new_with_predeployed_account(
+        predeployed_contracts: &Utf8PathBuf,
+        fork_config: Option<ForkConfig>,
+        account_class: ContractClass,
+        account_address: ContractAddress,
+        constructor_calldata: &[Felt252],
+    ) -> Result<Self, CheatcodeError> {
+        let mut state = Self::new(predeployed_contracts, fork_config);
+
+        let class_hash = state.declare_contract_class(account_class)?;
+        state.deploy_at(&class_hash, constructor_calldata, account_address)?;
+        state.set_balance(
+            ContractAddress(patricia_key!(TEST_ERC20_CONTRACT_ADDRESS)),
+            account_address,
+            INITIAL_BALANCE,
+            0,
+        )?;
+        state.initial_state_reader = state.flattened_state_reader();
+
+        Ok(state.with_test_account_address(account_address))
+    }
+
+    /// Uses the account deployed at `test_account_address` instead of the predeployed one,
+    /// e.g. when an account of a custom class is needed.
+    /// Transactions sent with `declare` and `invoke` will be executed by this account.
+    #[must_use]
+    pub fn with_test_account_address(mut self, test_account_address: ContractAddress) -> Self {
+        self.test_account_address = test_account_address;
+        self
+    }
+
+    #[must_use]
+    pub fn block_context_config(&self) -> &BlockContextConfig {
+        &self.block_context_config
+    }
+
+    /// Saves the values fetched from the forked network so far to the fork cache directory.
+    /// Does nothing if the state isn't forked. Values which weren't saved explicitly are saved
+    /// when the state is dropped, but errors of that save aren't returned.
+    pub fn save_fork_cache(&self) -> anyhow::Result<()> {
+        self.blockifier_state
+            .state
+            .fork_state_reader
+            .as_ref()
+            .map_or(Ok(()), ForkStateReader::save_cache)
+    }
+
+    /// Block context seen by the calls into `target`, with its active `roll`, `warp` and `elect`
+    /// applied. Returns the context without any cheats if `target` is `None`.
+    /// Fails if the rolled block number or warped timestamp doesn't fit in u64.
+    pub fn current_block_context(
+        &self,
+        target: Option<ContractAddress>,
+    ) -> Result<BlockContext, CheatcodeError> {
+        let mut block_context = build_block_context(&self.block_context_config);
+        let Some(target) = target else {
+            return Ok(block_context);
+        };
+
+        if let Some(rolled) = self.cheatcode_state.rolled_block_number(&target) {
+            let block_number = rolled.to_u64().ok_or_else(|| {
+                EnhancedHintError::from(anyhow!("Rolled block number {rolled} does not fit in u64"))
+            })?;
+            block_context.block_number = BlockNumber(block_number);
+        }
+        if let Some(warped) = self.cheatcode_state.warped_timestamp(&target) {
+            let block_timestamp = warped.to_u64().ok_or_else(|| {
+                EnhancedHintError::from(anyhow!(
+                    "Warped block timestamp {warped} does not fit in u64"
+                ))
+            })?;
+            block_context.block_timestamp = BlockTimestamp(block_timestamp);
         }
+        if let Some(elected) = self.cheatcode_state.elected_contracts.get(&target) {
+            block_context.sequencer_address = elected.value;
+        }
+
+        Ok(block_context)
+    }
+
+    /// Address of the account used to send transactions and deploy contracts.
+    /// Contracts deployed with `deploy` see this address as the caller of their constructor,
+    /// unless it is pranked.
+    #[must_use]
+    pub fn test_account_address(&self) -> ContractAddress {
+        self.test_account_address
+    }
+
+    /// Changes the gas price used by the following transactions.
+    /// Setting it to 0 makes the transactions free.
+    pub fn set_gas_price(&mut self, gas_price: u128) {
+        self.block_context_config.gas_price = gas_price;
+    }
+
+    /// Moves the block the following transactions and calls are executed in `count` blocks forward,
+    /// with every block taking `seconds_per_block` seconds.
+    /// Active `roll` and `warp` cheats still take precedence over the new block number and timestamp.
+    /// Fails without changing the block if the new block number or timestamp doesn't fit in u64.
+    pub fn advance_blocks(
+        &mut self,
+        count: u64,
+        seconds_per_block: u64,
+    ) -> Result<(), CheatcodeError> {
+        let BlockNumber(block_number) = self.block_context_config.block_number;
+        let BlockTimestamp(block_timestamp) = self.block_context_config.block_timestamp;
+
+        let new_block_number = block_number.checked_add(count).ok_or_else(|| {
+            EnhancedHintError::from(anyhow!(
+                "Advancing block {block_number} by {count} blocks overflows the block number"
+            ))
+        })?;
+        let new_block_timestamp = count
+            .checked_mul(seconds_per_block)
+            .and_then(|elapsed| block_timestamp.checked_add(elapsed))
+            .ok_or_else(|| {
+                EnhancedHintError::from(anyhow!(
+                    "Advancing timestamp {block_timestamp} by {count} blocks of {seconds_per_block} seconds overflows the block timestamp"
+                ))
+            })?;
+
+        self.block_context_config.block_number = BlockNumber(new_block_number);
+        self.block_context_config.block_timestamp = BlockTimestamp(new_block_timestamp);
+        Ok(())
+    }
+
+    /// Changes the chain id seen by the following transactions, calls and constructors.
+    /// `chain_id` has to be a short string, e.g. `'SN_MAIN'`, otherwise the chain id is left unchanged.
+    pub fn set_chain_id(&mut self, chain_id: Felt252) -> Result<(), CheatcodeError> {
+        let chain_id = as_cairo_short_string(&chain_id).ok_or_else(|| {
+            EnhancedHintError::from(anyhow!(
+                "Chain id {chain_id} has to be a valid short string"
+            ))
+        })?;
+        self.block_context_config.chain_id = ChainId(chain_id);
+        Ok(())
+    }
+
+    /// Makes the following `declare` and `invoke` transactions pay their fee from the test account,
+    /// failing if it can't afford it. Fees are not charged by default,
+    /// so the test account doesn't have to be funded.
+    pub fn set_charge_fee(&mut self, charge_fee: bool) {
+        self.charge_fee = charge_fee;
+    }
+
+    /// Changes what happens when `declare` or `deploy` panic internally.
+    pub fn set_panic_policy(&mut self, panic_policy: PanicPolicy) {
+        self.panic_policy = panic_policy;
+    }
+
+    // Runs `body` at the boundary of a cheatcode, catching its panics if the policy is `Recover`
+    pub(crate) fn with_panic_policy<T>(
+        &mut self,
+        body: impl FnOnce(&mut CheatnetState) -> Result<T, CheatcodeError>,
+    ) -> Result<T, CheatcodeError> {
+        match self.panic_policy {
+            PanicPolicy::Abort => body(self),
+            PanicPolicy::Recover => {
+                let call_stack_len = self.cheatcode_state.call_stack.len();
+                let discarding_changes = self.cheatcode_state.discarding_changes;
+                panic::catch_unwind(AssertUnwindSafe(|| body(self))).unwrap_or_else(|payload| {
+                    // Calls interrupted by the panic never left the call stacks
+                    self.cheatcode_state.call_stack.truncate(call_stack_len);
+                    self.cheatcode_state.discarding_changes = discarding_changes;
+                    if let Some(reentrancy_tracker) = &mut self.cheatcode_state.reentrancy_tracker {
+                        reentrancy_tracker.reset();
+                    }
+                    Err(CheatcodeFailure::InternalPanic(panic_message(payload.as_ref())).into())
+                })
+            }
+        }
+    }
+
+    /// Changes what happens when the following calls use a selector the called contract doesn't have.
+    pub fn set_unknown_selector_behavior(&mut self, behavior: UnknownSelectorBehavior) {
+        self.cheatcode_state.unknown_selector_behavior = behavior;
     }
 
     pub fn increment_deploy_salt_base(&mut self) {
         self.deploy_salt_base += 1;
     }
 
+    /// Where the last failed call or deployment panicked, if the last one failed.
+    #[must_use]
+    pub fn last_panic_trace(&self) -> Option<&PanicTrace> {
+        self.cheatcode_state.last_panic_trace.as_ref()
+    }
+
+    /// Addresses of all contracts deployed in the local state, including the predeployed ones,
+    /// ordered by address, with the class hashes they have. Contracts of a forked network are not listed.
+    #[must_use]
+    pub fn deployed_contracts(&self) -> Vec<(ContractAddress, ClassHash)> {
+        let mut contracts: Vec<_> = self
+            .flattened_state_reader()
+            .address_to_class_hash
+            .into_iter()
+            .collect();
+        contracts.sort();
+        contracts
+    }
+
+    /// Name of the function with `selector` in one of the classes declared with an ABI,
+    /// e.g. to show which function failed in a `PanicTrace`.
+    #[must_use]
+    pub fn selector_to_name(&self, selector: &Felt252) -> Option<String> {
+        self.function_names
+            .values()
+            .find_map(|names| names.get(selector))
+            .cloned()
+    }
+
+    /// Error of the last failed call or deployment as reported by blockifier, if the last one failed.
+    /// Useful when the panic data couldn't be decoded from it.
+    #[must_use]
+    pub fn last_revert_raw(&self) -> Option<&str> {
+        self.cheatcode_state.last_revert_raw.as_deref()
+    }
+
+    /// Starts capturing the lines printed with `print` by the executed contracts, so they are
+    /// returned by `take_prints` instead of being written to stdout.
+    pub fn capture_prints(&mut self) {
+        self.cheatcode_state.prints.get_or_insert_with(Vec::new);
+    }
+
+    /// Returns the lines printed with `print` by the executed contracts since the last `take_prints`,
+    /// one for every printed felt: printable short strings as they are, other felts as hex numbers.
+    /// Returns nothing unless capturing was started with `capture_prints`.
+    pub fn take_prints(&mut self) -> Vec<String> {
+        self.cheatcode_state
+            .prints
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Starts tracking the call stack of the following calls and deployments,
+    /// so re-entered calls can be inspected with `last_call_reentrancy_report`.
+    pub fn track_reentrancy(&mut self) {
+        self.cheatcode_state.reentrancy_tracker = Some(ReentrancyTracker::default());
+    }
+
+    /// Max call depth and re-entered calls of the last call or deployment.
+    /// Returns `None` unless tracking was started with `track_reentrancy`.
+    #[must_use]
+    pub fn last_call_reentrancy_report(&self) -> Option<&ReentrancyReport> {
+        self.cheatcode_state
+            .reentrancy_tracker
+            .as_ref()
+            .map(|tracker| &tracker.report)
+    }
+
     #[must_use]
     pub fn get_salt(&self) -> ContractAddressSalt {
-        ContractAddressSalt(StarkFelt::from(self.deploy_salt_base))
+        ContractAddressSalt(StarkFelt::from(
+            u128::from(self.deploy_salt_seed) << 32 | u128::from(self.deploy_salt_base),
+        ))
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Unknown panic".to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn recovered_panic_restores_call_stack() {
+        let mut state = CheatnetState::new(&Utf8PathBuf::from("predeployed-contracts"), None);
+        state.set_panic_policy(PanicPolicy::Recover);
+        state.track_reentrancy();
+
+        let result: Result<(), CheatcodeError> = state.with_panic_policy(|state| {
+            state
+                .cheatcode_state
+                .call_stack
+                .push(ContractAddress::from(123_u128));
+            panic!("Panicked during a call");
+        });
+
+        assert!(matches!(
+            result,
+            Err(CheatcodeError::Recoverable(CheatcodeFailure::InternalPanic(message)))
+                if message == "Panicked during a call"
+        ));
+        assert!(state.cheatcode_state.call_stack.is_empty());
     }
 }
@@ -1,34 +1,163 @@
-use super::conversions::felt_from_short_string;
+use super::conversions::{byte_array_from_string, felt_from_short_string, string_from_byte_array};
 use cairo_felt::Felt252;
+use cairo_lang_runner::short_string::as_cairo_short_string;
+use num_traits::Num;
 use regex::Regex;
+use starknet_api::core::{ContractAddress, EntryPointSelector};
+
+// First felt of the panic data of panics with a `ByteArray` message
+const BYTE_ARRAY_MAGIC: &str = "46a6158a16a947e5916b2a2ca68501a45e93d7110e81aa2d6438b1c57c879a3";
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum PanicData {
+    /// A single short string, e.g. from `panic_with_felt252`, `assert` or a failed `Result::unwrap`
+    ShortString {
+        felts: Vec<Felt252>,
+        message: String,
+    },
+    /// A serialized `ByteArray` preceded by the byte array magic, produced by long string panics
+    ByteArray {
+        felts: Vec<Felt252>,
+        message: String,
+    },
+    Felts(Vec<Felt252>),
+}
+
+impl PanicData {
+    #[must_use]
+    pub fn from_felts(felts: Vec<Felt252>) -> Self {
+        if let Some(message) = try_decode_byte_array(&felts) {
+            return PanicData::ByteArray { felts, message };
+        }
+        if let [felt] = felts.as_slice() {
+            if let Some(message) = as_cairo_short_string(felt) {
+                return PanicData::ShortString { felts, message };
+            }
+        }
+        PanicData::Felts(felts)
+    }
+
+    /// Panic data carrying `message` as a `ByteArray`, used for errors which don't have
+    /// a panic data of their own, e.g. reverts in a format that couldn't be parsed.
+    #[must_use]
+    pub fn from_message(message: &str) -> Self {
+        let mut felts = vec![Felt252::from_str_radix(BYTE_ARRAY_MAGIC, 16).unwrap()];
+        felts.extend(byte_array_from_string(message));
+        PanicData::ByteArray {
+            felts,
+            message: message.to_string(),
+        }
+    }
+
+    #[must_use]
+    pub fn felts(&self) -> &[Felt252] {
+        match self {
+            PanicData::ShortString { felts, .. }
+            | PanicData::ByteArray { felts, .. }
+            | PanicData::Felts(felts) => felts,
+        }
+    }
+
+    #[must_use]
+    pub fn into_felts(self) -> Vec<Felt252> {
+        match self {
+            PanicData::ShortString { felts, .. }
+            | PanicData::ByteArray { felts, .. }
+            | PanicData::Felts(felts) => felts,
+        }
+    }
+
+    /// Human-readable message of the panic, if its data encodes one.
+    #[must_use]
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            PanicData::ShortString { message, .. } | PanicData::ByteArray { message, .. } => {
+                Some(message)
+            }
+            PanicData::Felts(_) => None,
+        }
+    }
+}
+
+/// A call which was being executed when a panic happened.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CallFrame {
+    pub contract_address: ContractAddress,
+    pub selector: EntryPointSelector,
+}
+
+/// Panic data of the innermost failing call,
+/// together with the calls which led to it, from the outermost to the innermost one.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PanicTrace {
+    pub panic_data: Vec<Felt252>,
+    pub frames: Vec<CallFrame>,
+}
+
+impl PanicTrace {
+    /// Address of the contract in which the panic happened.
+    #[must_use]
+    pub fn contract_address(&self) -> Option<ContractAddress> {
+        self.frames.last().map(|frame| frame.contract_address)
+    }
+}
 
 #[allow(clippy::module_name_repetitions)]
-pub fn try_extract_panic_data(err: &str) -> Option<Vec<Felt252>> {
+pub fn try_extract_panic_data(err: &str) -> Option<PanicData> {
     let re = Regex::new(r#"(?m)^Got an exception while executing a hint: Custom Hint Error: Execution failed\. Failure reason: "(.*)"\.$"#)
         .expect("Could not create panic_data matching regex");
 
     if let Some(captures) = re.captures(err) {
         if let Some(panic_data_match) = captures.get(1) {
             if panic_data_match.as_str().is_empty() {
-                return Some(vec![]);
+                return Some(PanicData::Felts(vec![]));
             }
             let panic_data_felts: Vec<Felt252> = panic_data_match
                 .as_str()
                 .split(", ")
-                .map(felt_from_short_string)
+                .map(felt_from_panic_data_element)
                 .collect();
 
-            return Some(panic_data_felts);
+            return Some(PanicData::from_felts(panic_data_felts));
         }
     }
     None
 }
 
+// Felts that aren't valid short strings are printed as hex numbers
+fn felt_from_panic_data_element(element: &str) -> Felt252 {
+    element
+        .strip_prefix("0x")
+        .and_then(|hex| Felt252::from_str_radix(hex, 16).ok())
+        .unwrap_or_else(|| felt_from_short_string(element))
+}
+
+fn try_decode_byte_array(felts: &[Felt252]) -> Option<String> {
+    let (magic, serialized) = felts.split_first()?;
+    if *magic != Felt252::from_str_radix(BYTE_ARRAY_MAGIC, 16).unwrap() {
+        return None;
+    }
+    string_from_byte_array(serialized).ok()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use cairo_felt::Felt252;
 
+    #[test]
+    fn panic_data_from_message() {
+        let message = "Some error which could not be parsed into a panic data";
+
+        let panic_data = PanicData::from_message(message);
+
+        assert_eq!(panic_data.message(), Some(message));
+        assert_eq!(
+            PanicData::from_felts(panic_data.clone().into_felts()),
+            panic_data
+        );
+    }
+
     #[test]
     fn string_extracting_panic_data() {
         let cases: [(&str, Option<Vec<Felt252>>); 4] = [
@@ -49,7 +178,85 @@ mod test {
         ];
 
         for (str, expected) in cases {
-            assert_eq!(try_extract_panic_data(str), expected);
+            assert_eq!(
+                try_extract_panic_data(str).map(PanicData::into_felts),
+                expected
+            );
         }
     }
+
+    #[test]
+    fn extracting_short_string_panic_data() {
+        let cases = [
+            (
+                "Got an exception while executing a hint: Custom Hint Error: Execution failed. Failure reason: \"PANIK\".",
+                "PANIK",
+            ),
+            (
+                "Got an exception while executing a hint: Custom Hint Error: Execution failed. Failure reason: \"Result::unwrap failed.\".",
+                "Result::unwrap failed.",
+            ),
+        ];
+
+        for (str, message) in cases {
+            assert_eq!(
+                try_extract_panic_data(str),
+                Some(PanicData::ShortString {
+                    felts: vec![felt_from_short_string(message)],
+                    message: message.to_string(),
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn extracting_byte_array_panic_data() {
+        // `panic!("This is a very long panic message that does not fit in a felt")`
+        let str = "Got an exception while executing a hint: Custom Hint Error: Execution failed. Failure reason: \"0x46a6158a16a947e5916b2a2ca68501a45e93d7110e81aa2d6438b1c57c879a3, 0x1, This is a very long panic messa, ge that does not fit in a felt, 0x1e\".";
+
+        let panic_data = try_extract_panic_data(str).unwrap();
+
+        assert_eq!(
+            panic_data.message(),
+            Some("This is a very long panic message that does not fit in a felt")
+        );
+        assert_eq!(
+            panic_data.felts(),
+            &[
+                Felt252::from_str_radix(BYTE_ARRAY_MAGIC, 16).unwrap(),
+                Felt252::from(1),
+                felt_from_short_string("This is a very long panic messa"),
+                felt_from_short_string("ge that does not fit in a felt"),
+                Felt252::from(30),
+            ]
+        );
+    }
+
+    #[test]
+    fn byte_array_magic_with_invalid_length_is_raw_felts() {
+        let felts = vec![
+            Felt252::from_str_radix(BYTE_ARRAY_MAGIC, 16).unwrap(),
+            Felt252::from(u64::MAX),
+            Felt252::from(0),
+            Felt252::from(0),
+        ];
+
+        assert_eq!(
+            PanicData::from_felts(felts.clone()),
+            PanicData::Felts(felts)
+        );
+    }
+
+    #[test]
+    fn extracting_raw_felts_panic_data() {
+        let str = "Got an exception while executing a hint: Custom Hint Error: Execution failed. Failure reason: \"0x1234, PANIK\".";
+
+        assert_eq!(
+            try_extract_panic_data(str),
+            Some(PanicData::Felts(vec![
+                Felt252::from(0x1234),
+                felt_from_short_string("PANIK")
+            ]))
+        );
+    }
 }
@@ -0,0 +1,74 @@
+use crate::panic_data::CallFrame;
+
+/// A call which was on the call stack more than once at the same time.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ReenteredFrame {
+    pub frame: CallFrame,
+    /// The highest number of times the call was on the call stack at once.
+    pub max_occurrences: usize,
+}
+
+/// How deep the last call went and which of its calls were re-entered.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ReentrancyReport {
+    /// Depth of the deepest call, the top level call has depth 1.
+    pub max_depth: usize,
+    /// Re-entered calls, in the order they were first re-entered.
+    pub reentered_frames: Vec<ReenteredFrame>,
+}
+
+impl ReentrancyReport {
+    /// Whether `frame` was on the call stack more than once at the same time.
+    #[must_use]
+    pub fn was_reentered(&self, frame: &CallFrame) -> bool {
+        self.reentered_frames
+            .iter()
+            .any(|reentered| reentered.frame == *frame)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ReentrancyTracker {
+    call_stack: Vec<CallFrame>,
+    pub(crate) report: ReentrancyReport,
+}
+
+impl ReentrancyTracker {
+    pub(crate) fn reset(&mut self) {
+        self.call_stack.clear();
+        self.report = ReentrancyReport::default();
+    }
+
+    pub(crate) fn enter(&mut self, frame: CallFrame) {
+        let occurrences = self
+            .call_stack
+            .iter()
+            .filter(|active_frame| **active_frame == frame)
+            .count()
+            + 1;
+        self.call_stack.push(frame.clone());
+        self.report.max_depth = self.report.max_depth.max(self.call_stack.len());
+
+        if occurrences < 2 {
+            return;
+        }
+        match self
+            .report
+            .reentered_frames
+            .iter_mut()
+            .find(|reentered| reentered.frame == frame)
+        {
+            Some(reentered) => {
+                reentered.max_occurrences = reentered.max_occurrences.max(occurrences);
+            }
+            None => self.report.reentered_frames.push(ReenteredFrame {
+                frame,
+                max_occurrences: occurrences,
+            }),
+        }
+    }
+
+    pub(crate) fn exit(&mut self) {
+        self.call_stack.pop();
+    }
+}
@@ -16,7 +16,9 @@ use std::collections::HashSet;
 use std::{any::Any, collections::HashMap, sync::Arc};
 
 use crate::{
-    constants::{build_block_context, build_transaction_context, TEST_ACCOUNT_CONTRACT_ADDRESS},
+    cheatcodes::{mock_call::MockedResponse, Event, InnerCall, L2ToL1Message},
+    constants::{build_block_context, build_transaction_context, BlockContextConfig},
+    conversions::contract_address_to_felt,
     CheatnetState,
 };
 use blockifier::execution::entry_point::CallExecution;
@@ -52,31 +54,32 @@ use blockifier::{
             EmptyRequest, GetExecutionInfoResponse,
         },
     },
-    state::state_api::State,
+    state::{errors::StateError, state_api::State},
 };
 use cairo_felt::Felt252;
 use cairo_lang_casm::{
-    hints::{Hint, StarknetHint},
+    hints::{CoreHint, CoreHintBase, Hint, StarknetHint},
     operand::{BinOpOperand, DerefOrImmediate, Operation, Register, ResOperand},
 };
+use cairo_lang_runner::short_string::as_cairo_short_string;
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources as VmExecutionResources;
 use starknet_api::{
-    core::{ClassHash, ContractAddress, EntryPointSelector, PatriciaKey},
+    block::BlockHash,
+    core::{ClassHash, ContractAddress, EntryPointSelector},
     deprecated_contract_class::EntryPointType,
     hash::{StarkFelt, StarkHash},
-    patricia_key,
     transaction::{Calldata, TransactionVersion},
 };
 
 use blockifier::execution::syscalls::{
-    LibraryCallRequest, SyscallRequest, SyscallRequestWrapper, SyscallResponse,
-    SyscallResponseWrapper, SyscallResult,
+    get_block_hash, GetBlockHashRequest, GetBlockHashResponse, LibraryCallRequest, SyscallRequest,
+    SyscallRequestWrapper, SyscallResponse, SyscallResponseWrapper, SyscallResult,
 };
 use cairo_vm::hint_processor::hint_processor_definition::HintProcessorLogic;
 use cairo_vm::vm::runners::cairo_runner::ResourceTracker;
 
-use crate::panic_data::try_extract_panic_data;
-use crate::state::CheatcodeState;
+use crate::panic_data::{try_extract_panic_data, CallFrame, PanicData, PanicTrace};
+use crate::state::{CheatStatus, CheatcodeState, UnknownSelectorBehavior};
 
 type SyscallSelector = DeprecatedSyscallSelector;
 
@@ -87,85 +90,281 @@ pub enum CallContractOutput {
 
 // This does contract call without the transaction layer. This way `call_contract` can return data and modify state.
 // `call` and `invoke` on the transactional layer use such method under the hood.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "call",
+        level = "debug",
+        skip(entry_point_selector, calldata, cheatnet_state),
+        fields(selector = %entry_point_selector)
+    )
+)]
 pub fn call_contract(
     contract_address: &ContractAddress,
     entry_point_selector: &Felt252,
     calldata: &[Felt252],
     cheatnet_state: &mut CheatnetState,
 ) -> Result<CallContractOutput> {
-    let blockifier_state = &mut cheatnet_state.blockifier_state;
-    let cheatcode_state = &mut cheatnet_state.cheatcode_state;
+    let entry_point = external_call_entry_point(
+        contract_address,
+        entry_point_selector,
+        calldata,
+        cheatnet_state.test_account_address(),
+        u64::MAX,
+    )?;
+
+    let exec_result = call_entry_point(entry_point, cheatnet_state);
+
+    let output = match exec_result {
+        Ok(call_info) => {
+            let raw_return_data = &call_info.execution.retdata.0;
+
+            let return_data = raw_return_data
+                .iter()
+                .map(|data| Felt252::from_bytes_be(data.bytes()))
+                .collect();
 
+            CallContractOutput::Success {
+                ret_data: return_data,
+            }
+        }
+        Err(error) => CallContractOutput::Panic {
+            panic_data: panic_data_from_execution_error(&error),
+        },
+    };
+
+    #[cfg(feature = "tracing")]
+    match &output {
+        CallContractOutput::Success { ret_data } => tracing::debug!(?ret_data, "call succeeded"),
+        CallContractOutput::Panic { panic_data } => tracing::debug!(?panic_data, "call reverted"),
+    }
+
+    Ok(output)
+}
+
+// Entry point of a call into an external function of `contract_address`, made by `caller_address`
+pub(crate) fn external_call_entry_point(
+    contract_address: &ContractAddress,
+    entry_point_selector: &Felt252,
+    calldata: &[Felt252],
+    caller_address: ContractAddress,
+    initial_gas: u64,
+) -> Result<CallEntryPoint> {
     let entry_point_selector =
         EntryPointSelector(StarkHash::new(entry_point_selector.to_be_bytes())?);
-
-    let account_address = ContractAddress(patricia_key!(TEST_ACCOUNT_CONTRACT_ADDRESS));
     let calldata = Calldata(Arc::new(
         calldata
             .iter()
             .map(|data| StarkFelt::new(data.to_be_bytes()))
             .collect::<Result<Vec<_>, _>>()?,
     ));
-    let mut entry_point = CallEntryPoint {
+
+    Ok(CallEntryPoint {
         class_hash: None,
         code_address: Some(*contract_address),
         entry_point_type: EntryPointType::External,
         entry_point_selector,
         calldata,
         storage_address: *contract_address,
-        caller_address: account_address,
+        caller_address,
         call_type: CallType::Call,
-        initial_gas: u64::MAX,
-    };
+        initial_gas,
+    })
+}
+
+// Executes an arbitrary entry point (e.g. a constructor) in the same cheatable context `call_contract` uses.
+pub(crate) fn call_entry_point(
+    entry_point: CallEntryPoint,
+    cheatnet_state: &mut CheatnetState,
+) -> EntryPointExecutionResult<CallInfo> {
+    call_entry_point_in_state(
+        entry_point,
+        &mut cheatnet_state.blockifier_state,
+        &mut cheatnet_state.cheatcode_state,
+        &cheatnet_state.block_context_config,
+    )
+}
+
+// Works like `call_entry_point`, but executes the call against `blockifier_state`,
+// e.g. a transactional state which can be discarded if the call fails
+pub(crate) fn call_entry_point_in_state(
+    entry_point: CallEntryPoint,
+    blockifier_state: &mut dyn State,
+    cheatcode_state: &mut CheatcodeState,
+    block_context_config: &BlockContextConfig,
+) -> EntryPointExecutionResult<CallInfo> {
+    call_entry_point_with_n_steps(
+        entry_point,
+        blockifier_state,
+        cheatcode_state,
+        block_context_config,
+    )
+    .0
+}
+
+// Works like `call_entry_point_in_state`, but also returns the number of VM steps executed,
+// which is known even if the call fails
+pub(crate) fn call_entry_point_with_n_steps(
+    mut entry_point: CallEntryPoint,
+    blockifier_state: &mut dyn State,
+    cheatcode_state: &mut CheatcodeState,
+    block_context_config: &BlockContextConfig,
+) -> (EntryPointExecutionResult<CallInfo>, usize) {
+    cheatcode_state.last_panic_trace = None;
+    cheatcode_state.last_revert_raw = None;
+    if let Some(reentrancy_tracker) = &mut cheatcode_state.reentrancy_tracker {
+        reentrancy_tracker.reset();
+    }
 
     let mut resources = ExecutionResources::default();
     let account_context = build_transaction_context();
-    let block_context = build_block_context();
+    let block_context = build_block_context(block_context_config);
 
-    let mut context = EntryPointExecutionContext::new(
-        block_context.clone(),
-        account_context,
-        block_context.invoke_tx_max_n_steps.try_into().unwrap(),
-    );
+    let max_n_steps: usize = block_context.invoke_tx_max_n_steps.try_into().unwrap();
+    let mut context =
+        EntryPointExecutionContext::new(block_context.clone(), account_context, max_n_steps);
 
-    let exec_result = execute_call_entry_point(
+    let result = execute_call_entry_point(
         &mut entry_point,
         blockifier_state,
         cheatcode_state,
         &mut resources,
         &mut context,
     );
+    let n_steps = max_n_steps
+        - context
+            .vm_run_resources
+            .get_n_steps()
+            .unwrap_or(max_n_steps);
+    let call_info = match result {
+        Ok(call_info) => call_info,
+        Err(error) => {
+            cheatcode_state.last_revert_raw = Some(error.to_string());
+            return (Err(error), n_steps);
+        }
+    };
 
-    if let Ok(call_info) = exec_result {
-        let raw_return_data = &call_info.execution.retdata.0;
+    if !cheatcode_state.discarding_changes {
+        let events = collect_events(&call_info);
+        for spy in &cheatcode_state.event_spies {
+            spy.record(&events);
+        }
+        let messages = collect_l2_to_l1_messages(&call_info);
+        for spy in &cheatcode_state.message_spies {
+            spy.record(&messages);
+        }
+    }
 
-        let return_data = raw_return_data
-            .iter()
-            .map(|data| Felt252::from_bytes_be(data.bytes()))
-            .collect();
+    (Ok(call_info), n_steps)
+}
 
-        Ok(CallContractOutput::Success {
-            ret_data: return_data,
-        })
-    } else if let Err(EntryPointExecutionError::ExecutionFailed { error_data }) = exec_result {
-        let err_data = error_data
+pub(crate) fn panic_data_from_execution_error(error: &EntryPointExecutionError) -> Vec<Felt252> {
+    match error {
+        EntryPointExecutionError::ExecutionFailed { error_data } => error_data
             .iter()
             .map(|data| Felt252::from_bytes_be(data.bytes()))
-            .collect();
-
-        Ok(CallContractOutput::Panic {
-            panic_data: err_data,
-        })
-    } else if let Err(EntryPointExecutionError::VirtualMachineExecutionErrorWithTrace {
-        trace,
-        ..
-    }) = exec_result
-    {
-        let panic_data =
-            try_extract_panic_data(&trace).unwrap_or_else(|| panic!("Unparseable result: {trace}"));
-        Ok(CallContractOutput::Panic { panic_data })
-    } else {
-        panic!("Unparseable result: {exec_result:?}");
+            .collect(),
+        EntryPointExecutionError::VirtualMachineExecutionErrorWithTrace { trace, .. } => {
+            try_extract_panic_data(trace)
+                .unwrap_or_else(|| PanicData::from_message(trace))
+                .into_felts()
+        }
+        // The raw error is passed on, so it can be reported instead of aborting the test run
+        error => PanicData::from_message(&error.to_string()).into_felts(),
+    }
+}
+
+// Collects events emitted in the whole call tree, in the order they were emitted
+pub(crate) fn collect_events(call_info: &CallInfo) -> Vec<Event> {
+    let mut ordered_events = vec![];
+    collect_ordered_events(call_info, &mut ordered_events);
+    ordered_events.sort_by_key(|(order, _)| *order);
+
+    ordered_events.into_iter().map(|(_, event)| event).collect()
+}
+
+fn collect_ordered_events(call_info: &CallInfo, ordered_events: &mut Vec<(usize, Event)>) {
+    for ordered_event in &call_info.execution.events {
+        ordered_events.push((
+            ordered_event.order,
+            Event {
+                from: call_info.call.storage_address,
+                keys: ordered_event
+                    .event
+                    .keys
+                    .iter()
+                    .map(|key| stark_felt_to_felt(key.0))
+                    .collect(),
+                data: ordered_event
+                    .event
+                    .data
+                    .0
+                    .iter()
+                    .map(|data| stark_felt_to_felt(*data))
+                    .collect(),
+            },
+        ));
+    }
+    for inner_call in &call_info.inner_calls {
+        collect_ordered_events(inner_call, ordered_events);
+    }
+}
+
+// Collects the calls nested in the call tree, in the order they were made,
+// each call followed by the calls it made itself
+pub(crate) fn collect_inner_calls(call_info: &CallInfo) -> Vec<InnerCall> {
+    let mut inner_calls = vec![];
+    for inner_call_info in &call_info.inner_calls {
+        inner_calls.push(InnerCall {
+            contract_address: inner_call_info.call.storage_address,
+            selector: stark_felt_to_felt(inner_call_info.call.entry_point_selector.0),
+            ret_data: inner_call_info
+                .execution
+                .retdata
+                .0
+                .iter()
+                .map(|data| stark_felt_to_felt(*data))
+                .collect(),
+        });
+        inner_calls.extend(collect_inner_calls(inner_call_info));
+    }
+    inner_calls
+}
+
+// Collects messages sent to L1 in the whole call tree, in the order they were sent
+pub(crate) fn collect_l2_to_l1_messages(call_info: &CallInfo) -> Vec<L2ToL1Message> {
+    let mut ordered_messages = vec![];
+    collect_ordered_l2_to_l1_messages(call_info, &mut ordered_messages);
+    ordered_messages.sort_by_key(|(order, _)| *order);
+
+    ordered_messages
+        .into_iter()
+        .map(|(_, message)| message)
+        .collect()
+}
+
+fn collect_ordered_l2_to_l1_messages(
+    call_info: &CallInfo,
+    ordered_messages: &mut Vec<(usize, L2ToL1Message)>,
+) {
+    for ordered_message in &call_info.execution.l2_to_l1_messages {
+        ordered_messages.push((
+            ordered_message.order,
+            L2ToL1Message {
+                from: call_info.call.storage_address,
+                to_address: Felt252::from_bytes_be(ordered_message.message.to_address.0.as_bytes()),
+                payload: ordered_message
+                    .message
+                    .payload
+                    .0
+                    .iter()
+                    .map(|data| stark_felt_to_felt(*data))
+                    .collect(),
+            },
+        ));
+    }
+    for inner_call in &call_info.inner_calls {
+        collect_ordered_l2_to_l1_messages(inner_call, ordered_messages);
     }
 }
 
@@ -173,12 +372,17 @@ pub fn call_contract(
 fn execute_call_entry_point(
     entry_point: &mut CallEntryPoint,
     state: &mut dyn State,
-    cheatcode_state: &CheatcodeState,
+    cheatcode_state: &mut CheatcodeState,
     resources: &mut ExecutionResources,
     context: &mut EntryPointExecutionContext,
 ) -> EntryPointExecutionResult<CallInfo> {
     // We skip recursion depth validation here.
 
+    // Mocked calls take precedence over the contract code, also for addresses without a deployed contract.
+    if let Some(ret_data) = get_ret_data_by_call_entry_point(entry_point, cheatcode_state) {
+        return Ok(call_info_without_execution(entry_point, ret_data));
+    }
+
     // Validate contract is deployed.
     let storage_address = entry_point.storage_address;
     let storage_class_hash = state.get_class_hash_at(entry_point.storage_address)?;
@@ -203,19 +407,57 @@ fn execute_call_entry_point(
     }
     // Add class hash to the call, that will appear in the output (call info).
     entry_point.class_hash = Some(class_hash);
-    let contract_class = state.get_compiled_contract_class(&class_hash)?;
-
-    let result = match contract_class {
-        ContractClass::V0(_) => panic!("Cairo 0 classes are not supported"),
-        ContractClass::V1(contract_class) => execute_entry_point_call_cairo1(
-            entry_point.clone(),
-            &contract_class,
-            state,
-            cheatcode_state,
-            resources,
-            context,
-        ),
+    let contract_class = match state.get_compiled_contract_class(&class_hash)? {
+        ContractClass::V1(contract_class) => contract_class,
+        // Fails only the call, not the whole test run
+        ContractClass::V0(_) => {
+            return Err(EntryPointExecutionError::StateError(
+                StateError::StateReadError(format!(
+                    "Class {} is a Cairo 0 class, Cairo 0 classes are not supported",
+                    class_hash.0
+                )),
+            ));
+        }
+    };
+
+    if cheatcode_state.unknown_selector_behavior == UnknownSelectorBehavior::EmptyReturn {
+        if let Err(PreExecutionError::EntryPointNotFound(_)) =
+            contract_class.get_entry_point(entry_point)
+        {
+            return Ok(call_info_without_execution(entry_point, vec![]));
+        }
+    }
+
+    // Calls a contract makes into itself are a part of a single call into the contract
+    let is_top_level_call = !cheatcode_state.call_stack.contains(&storage_address);
+    cheatcode_state.call_stack.push(storage_address);
+    let frame = CallFrame {
+        contract_address: storage_address,
+        selector: entry_point.entry_point_selector,
     };
+    if let Some(reentrancy_tracker) = &mut cheatcode_state.reentrancy_tracker {
+        reentrancy_tracker.enter(frame.clone());
+    }
+
+    let result = execute_entry_point_call_cairo1(
+        entry_point.clone(),
+        &contract_class,
+        state,
+        cheatcode_state,
+        resources,
+        context,
+    );
+
+    cheatcode_state.call_stack.pop();
+    if let Some(reentrancy_tracker) = &mut cheatcode_state.reentrancy_tracker {
+        reentrancy_tracker.exit();
+    }
+    if is_top_level_call && !cheatcode_state.discarding_changes {
+        cheatcode_state.decrement_cheat_spans(storage_address);
+    }
+    if let Err(error) = &result {
+        record_panic_frame(cheatcode_state, frame, error);
+    }
 
     result.map_err(|error| {
         match error {
@@ -239,9 +481,44 @@ fn execute_call_entry_point(
     })
 }
 
+// Result of a call which returns `ret_data` without executing any code, e.g. a mocked one
+fn call_info_without_execution(entry_point: &CallEntryPoint, ret_data: Vec<StarkFelt>) -> CallInfo {
+    CallInfo {
+        call: entry_point.clone(),
+        execution: CallExecution {
+            retdata: Retdata(ret_data),
+            events: vec![],
+            l2_to_l1_messages: vec![],
+            failed: false,
+            gas_consumed: 0,
+        },
+        vm_resources: VmExecutionResources::default(),
+        inner_calls: vec![],
+        storage_read_values: vec![],
+        accessed_storage_keys: HashSet::new(),
+    }
+}
+
+// Failed calls are unwound from the innermost one, which is the one that panicked
+fn record_panic_frame(
+    cheatcode_state: &mut CheatcodeState,
+    frame: CallFrame,
+    error: &EntryPointExecutionError,
+) {
+    match &mut cheatcode_state.last_panic_trace {
+        Some(panic_trace) => panic_trace.frames.insert(0, frame),
+        None => {
+            cheatcode_state.last_panic_trace = Some(PanicTrace {
+                panic_data: panic_data_from_execution_error(error),
+                frames: vec![frame],
+            });
+        }
+    }
+}
+
 pub struct CheatableSyscallHandler<'a> {
     pub syscall_handler: SyscallHintProcessor<'a>,
-    pub cheatcode_state: &'a CheatcodeState,
+    pub cheatcode_state: &'a mut CheatcodeState,
 }
 
 impl ResourceTracker for CheatableSyscallHandler<'_> {
@@ -280,6 +557,13 @@ impl HintProcessorLogic for CheatableSyscallHandler<'_> {
                 return self.execute_next_syscall_cheated(vm, hint);
             }
         }
+        if let Some(Hint::Core(CoreHintBase::Core(CoreHint::DebugPrint { start, end }))) =
+            maybe_extended_hint
+        {
+            if self.cheatcode_state.prints.is_some() {
+                return self.capture_debug_print(vm, start, end);
+            }
+        }
         self.syscall_handler
             .execute_hint(vm, exec_scopes, hint_data, constants)
     }
@@ -297,6 +581,34 @@ impl HintProcessorLogic for CheatableSyscallHandler<'_> {
     }
 }
 
+impl CheatableSyscallHandler<'_> {
+    // Felts printed with `print` are kept for `take_prints` instead of being written to stdout
+    // when capturing prints, printable short strings as they are and other felts as hex numbers
+    fn capture_debug_print(
+        &mut self,
+        vm: &mut VirtualMachine,
+        start: &ResOperand,
+        end: &ResOperand,
+    ) -> HintExecutionResult {
+        let mut current = get_ptr_from_res_operand_unchecked(vm, start);
+        let end = get_ptr_from_res_operand_unchecked(vm, end);
+        while current != end {
+            let felt = vm.get_integer(current)?;
+            let line = as_cairo_short_string(&felt)
+                .filter(|string| {
+                    !string.is_empty() && string.chars().all(|c| c.is_ascii_graphic() || c == ' ')
+                })
+                .unwrap_or_else(|| format!("0x{}", felt.to_str_radix(16)));
+            self.cheatcode_state
+                .prints
+                .get_or_insert_with(Vec::new)
+                .push(line);
+            current = (current + 1_usize)?;
+        }
+        Ok(())
+    }
+}
+
 /// Retrieves a [Relocatable] from the VM given a [`ResOperand`].
 /// A [`ResOperand`] represents a CASM result expression, and is deserialized with the hint.
 fn get_ptr_from_res_operand_unchecked(vm: &mut VirtualMachine, res: &ResOperand) -> Relocatable {
@@ -344,20 +656,82 @@ impl CheatableSyscallHandler<'_> {
 
         let mut new_block_info = original_block_info.to_owned();
 
-        if let Some(rolled_number) = self.cheatcode_state.rolled_contracts.get(contract_address) {
+        if let Some(rolled_number) = self.cheatcode_state.rolled_block_number(contract_address) {
             new_block_info[0] = MaybeRelocatable::Int(rolled_number.clone());
         };
 
-        if let Some(warped_timestamp) = self.cheatcode_state.warped_contracts.get(contract_address)
-        {
+        if let Some(warped_timestamp) = self.cheatcode_state.warped_timestamp(contract_address) {
             new_block_info[1] = MaybeRelocatable::Int(warped_timestamp.clone());
         };
 
+        if let Some(CheatStatus {
+            value: sequencer_address,
+            ..
+        }) = self.cheatcode_state.elected_contracts.get(contract_address)
+        {
+            new_block_info[2] = MaybeRelocatable::Int(contract_address_to_felt(*sequencer_address));
+        };
+
         vm.load_data(ptr_cheated_block_info, &new_block_info)
             .unwrap();
         ptr_cheated_block_info
     }
 
+    fn get_cheated_tx_info_ptr(
+        &self,
+        vm: &mut VirtualMachine,
+        original_tx_info: &[MaybeRelocatable],
+        contract_address: &ContractAddress,
+    ) -> Relocatable {
+        // create a new segment with replaced tx info
+        let ptr_cheated_tx_info = vm.add_memory_segment();
+
+        let mut new_tx_info = original_tx_info.to_owned();
+
+        let tx_info_mock = self
+            .cheatcode_state
+            .spoofed_contracts
+            .get(contract_address)
+            .map(|cheat_status| &cheat_status.value)
+            .expect("No tx info mock found for the spoofed contract address");
+
+        // TxInfo from corelib/src/starknet/info.cairo
+        // version, account_contract_address, max_fee, signature (start and end pointer),
+        // transaction_hash, chain_id, nonce
+        if let Some(version) = &tx_info_mock.version {
+            new_tx_info[0] = MaybeRelocatable::Int(version.clone());
+        };
+        if let Some(account_contract_address) = &tx_info_mock.account_contract_address {
+            new_tx_info[1] = MaybeRelocatable::Int(account_contract_address.clone());
+        };
+        if let Some(max_fee) = &tx_info_mock.max_fee {
+            new_tx_info[2] = MaybeRelocatable::Int(max_fee.clone());
+        };
+        if let Some(signature) = &tx_info_mock.signature {
+            let signature: Vec<MaybeRelocatable> = signature
+                .iter()
+                .map(|felt| MaybeRelocatable::Int(felt.clone()))
+                .collect();
+            let signature_start_ptr = vm.add_memory_segment();
+            let signature_end_ptr = vm.load_data(signature_start_ptr, &signature).unwrap();
+
+            new_tx_info[3] = MaybeRelocatable::RelocatableValue(signature_start_ptr);
+            new_tx_info[4] = MaybeRelocatable::RelocatableValue(signature_end_ptr);
+        };
+        if let Some(transaction_hash) = &tx_info_mock.transaction_hash {
+            new_tx_info[5] = MaybeRelocatable::Int(transaction_hash.clone());
+        };
+        if let Some(chain_id) = &tx_info_mock.chain_id {
+            new_tx_info[6] = MaybeRelocatable::Int(chain_id.clone());
+        };
+        if let Some(nonce) = &tx_info_mock.nonce {
+            new_tx_info[7] = MaybeRelocatable::Int(nonce.clone());
+        };
+
+        vm.load_data(ptr_cheated_tx_info, &new_tx_info).unwrap();
+        ptr_cheated_tx_info
+    }
+
     fn address_is_pranked(
         &mut self,
         _vm: &mut VirtualMachine,
@@ -374,8 +748,8 @@ impl CheatableSyscallHandler<'_> {
         contract_address: &ContractAddress,
     ) -> bool {
         self.cheatcode_state
-            .warped_contracts
-            .contains_key(contract_address)
+            .warped_timestamp(contract_address)
+            .is_some()
     }
 
     fn address_is_rolled(
@@ -384,7 +758,27 @@ impl CheatableSyscallHandler<'_> {
         contract_address: &ContractAddress,
     ) -> bool {
         self.cheatcode_state
-            .rolled_contracts
+            .rolled_block_number(contract_address)
+            .is_some()
+    }
+
+    fn address_is_elected(
+        &mut self,
+        _vm: &mut VirtualMachine,
+        contract_address: &ContractAddress,
+    ) -> bool {
+        self.cheatcode_state
+            .elected_contracts
+            .contains_key(contract_address)
+    }
+
+    fn address_is_spoofed(
+        &mut self,
+        _vm: &mut VirtualMachine,
+        contract_address: &ContractAddress,
+    ) -> bool {
+        self.cheatcode_state
+            .spoofed_contracts
             .contains_key(contract_address)
     }
 
@@ -399,6 +793,8 @@ impl CheatableSyscallHandler<'_> {
                 self.address_is_rolled(vm, contract_address)
                     || self.address_is_pranked(vm, contract_address)
                     || self.address_is_warped(vm, contract_address)
+                    || self.address_is_elected(vm, contract_address)
+                    || self.address_is_spoofed(vm, contract_address)
             }
             _ => false,
         }
@@ -456,6 +852,7 @@ impl CheatableSyscallHandler<'_> {
 
             if self.address_is_rolled(vm, &contract_address)
                 || self.address_is_warped(vm, &contract_address)
+                || self.address_is_elected(vm, &contract_address)
             {
                 let data = vm.get_range(execution_info_ptr, 1)[0].clone();
                 if let MaybeRelocatable::RelocatableValue(block_info_ptr) =
@@ -473,6 +870,17 @@ impl CheatableSyscallHandler<'_> {
                 }
             }
 
+            if self.address_is_spoofed(vm, &contract_address) {
+                if let MaybeRelocatable::RelocatableValue(tx_info_ptr) = new_exec_info[1].clone() {
+                    let original_tx_info = vm.get_continuous_range(tx_info_ptr, 8).unwrap();
+
+                    let ptr_cheated_tx_info =
+                        self.get_cheated_tx_info_ptr(vm, &original_tx_info, &contract_address);
+
+                    new_exec_info[1] = MaybeRelocatable::RelocatableValue(ptr_cheated_tx_info);
+                }
+            }
+
             if self.address_is_pranked(vm, &contract_address) {
                 new_exec_info[2] = MaybeRelocatable::Int(stark_felt_to_felt(
                     *self
@@ -480,6 +888,7 @@ impl CheatableSyscallHandler<'_> {
                         .pranked_contracts
                         .get(&contract_address)
                         .expect("No caller address value found for the pranked contract address")
+                        .value
                         .0
                         .key(),
                 ));
@@ -505,6 +914,14 @@ impl CheatableSyscallHandler<'_> {
                 call_contract_syscall,
                 constants::CALL_CONTRACT_GAS_COST,
             );
+        } else if SyscallSelector::GetBlockHash == selector {
+            // Increment, since the selector was peeked into before
+            self.syscall_handler.syscall_ptr += 1;
+            return self.execute_syscall(
+                vm,
+                get_block_hash_syscall,
+                constants::GET_BLOCK_HASH_GAS_COST,
+            );
         } else if SyscallSelector::LibraryCall == selector {
             // Increment, since the selector was peeked into before
             self.syscall_handler.syscall_ptr += 1;
@@ -630,8 +1047,37 @@ pub fn call_contract_syscall(
     })
 }
 
-// Inspired by blockifier::execution::syscalls::library_call
-// Calls a library using our implementation with modified logic
+// Returns the cheated block hash if there is one,
+// otherwise falls back to blockifier::execution::syscalls::get_block_hash
+pub fn get_block_hash_syscall(
+    request: GetBlockHashRequest,
+    vm: &mut VirtualMachine,
+    syscall_handler: &mut CheatableSyscallHandler<'_>,
+    remaining_gas: &mut u64,
+) -> SyscallResult<GetBlockHashResponse> {
+    let block_number = request.block_number.0;
+    if let Some(block_hash) = syscall_handler
+        .cheatcode_state
+        .cheated_block_hashes
+        .get(&block_number)
+    {
+        return Ok(GetBlockHashResponse {
+            block_hash: BlockHash(felt_to_stark_felt(block_hash)),
+        });
+    }
+
+    get_block_hash(
+        request,
+        vm,
+        &mut syscall_handler.syscall_handler,
+        remaining_gas,
+    )
+}
+
+/// Inspired by blockifier::execution::syscalls::library_call
+/// Calls a library using our implementation with modified logic.
+/// The library code runs in the context of the calling contract,
+/// so its storage and caller addresses stay those of the caller.
 pub fn library_call_syscall(
     request: LibraryCallRequest,
     vm: &mut VirtualMachine,
@@ -669,7 +1115,7 @@ pub fn execute_inner_call(
     let call_info = execute_call_entry_point(
         call,
         syscall_handler.syscall_handler.state,
-        syscall_handler.cheatcode_state,
+        &mut *syscall_handler.cheatcode_state,
         syscall_handler.syscall_handler.resources,
         syscall_handler.syscall_handler.context,
     )?;
@@ -694,16 +1140,27 @@ pub fn execute_inner_call(
     Ok(retdata_segment)
 }
 
-fn get_ret_data_by_call_entry_point<'a>(
+fn get_ret_data_by_call_entry_point(
     call: &CallEntryPoint,
-    cheatcode_state: &'a CheatcodeState,
-) -> Option<&'a Vec<StarkFelt>> {
+    cheatcode_state: &CheatcodeState,
+) -> Option<Vec<StarkFelt>> {
     if let Some(contract_address) = call.code_address {
         if let Some(contract_functions) = cheatcode_state.mocked_functions.get(&contract_address) {
             let entrypoint_selector = call.entry_point_selector;
 
-            let ret_data = contract_functions.get(&entrypoint_selector);
-            return ret_data;
+            return match contract_functions.get(&entrypoint_selector)? {
+                MockedResponse::RetData(ret_data) => Some(ret_data.clone()),
+                MockedResponse::Matcher(matcher) => {
+                    let calldata: Vec<Felt252> = call
+                        .calldata
+                        .0
+                        .iter()
+                        .map(|data| stark_felt_to_felt(*data))
+                        .collect();
+                    let ret_data = matcher(&calldata)?;
+                    Some(ret_data.iter().map(felt_to_stark_felt).collect())
+                }
+            };
         }
     }
     None
@@ -714,27 +1171,10 @@ fn execute_entry_point_call_cairo1(
     call: CallEntryPoint,
     contract_class: &ContractClassV1,
     state: &mut dyn State,
-    cheatcode_state: &CheatcodeState,
+    cheatcode_state: &mut CheatcodeState,
     resources: &mut ExecutionResources,
     context: &mut EntryPointExecutionContext,
 ) -> EntryPointExecutionResult<CallInfo> {
-    if let Some(ret_data) = get_ret_data_by_call_entry_point(&call, cheatcode_state) {
-        return Ok(CallInfo {
-            call,
-            execution: CallExecution {
-                retdata: Retdata(ret_data.clone()),
-                events: vec![],
-                l2_to_l1_messages: vec![],
-                failed: false,
-                gas_consumed: 0,
-            },
-            vm_resources: VmExecutionResources::default(),
-            inner_calls: vec![],
-            storage_read_values: vec![],
-            accessed_storage_keys: HashSet::new(),
-        });
-    }
-
     let VmExecutionContext {
         mut runner,
         mut vm,
@@ -811,3 +1251,20 @@ fn cheatable_run_entry_point(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn panic_data_from_unparseable_error() {
+        let error = EntryPointExecutionError::StateError(StateError::UndeclaredClassHash(
+            ClassHash(StarkFelt::from(123_u32)),
+        ));
+        let message = error.to_string();
+
+        let panic_data = PanicData::from_felts(panic_data_from_execution_error(&error));
+
+        assert_eq!(panic_data.message(), Some(message.as_str()));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::cheatcodes::ContractArtifacts;
+use anyhow::{anyhow, Context, Result};
+use cairo_lang_starknet::casm_contract_class::CasmContractClass;
+use cairo_lang_starknet::contract_class::ContractClass;
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::Deserialize;
+use std::fs;
+
+const ARTIFACTS_DIR: &str = "target/dev";
+const STARKNET_ARTIFACTS_SUFFIX: &str = ".starknet_artifacts.json";
+
+#[derive(Deserialize, Debug, Clone)]
+struct StarknetContract {
+    contract_name: String,
+    artifacts: StarknetContractArtifactPaths,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct StarknetContractArtifactPaths {
+    sierra: Utf8PathBuf,
+    casm: Utf8PathBuf,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct StarknetArtifacts {
+    contracts: Vec<StarknetContract>,
+}
+
+/// Loads the artifacts of the contract with the given name from a package built with `scarb build`.
+/// The returned artifacts are checked to parse into Sierra and CASM contract classes,
+/// so they can be passed to `declare` directly.
+pub fn load_contract(package_dir: &Utf8Path, contract_name: &str) -> Result<ContractArtifacts> {
+    let artifacts_dir = package_dir.join(ARTIFACTS_DIR);
+    let starknet_artifacts_paths = starknet_artifacts_paths(&artifacts_dir)?;
+
+    let contract = starknet_artifacts_paths
+        .iter()
+        .map(|path| read_starknet_artifacts(path))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .flat_map(|artifacts| artifacts.contracts)
+        .find(|contract| contract.contract_name == contract_name)
+        .ok_or_else(|| {
+            anyhow!(
+                "Failed to find contract {contract_name} in starknet artifacts in {artifacts_dir}"
+            )
+        })?;
+
+    let sierra = read_artifact(&artifacts_dir.join(&contract.artifacts.sierra))?;
+    let casm = read_artifact(&artifacts_dir.join(&contract.artifacts.casm))?;
+
+    serde_json::from_str::<ContractClass>(&sierra).with_context(|| {
+        format!("Failed to parse Sierra contract class of contract {contract_name}")
+    })?;
+    serde_json::from_str::<CasmContractClass>(&casm).with_context(|| {
+        format!("Failed to parse CASM contract class of contract {contract_name}")
+    })?;
+
+    Ok(ContractArtifacts { sierra, casm })
+}
+
+fn starknet_artifacts_paths(artifacts_dir: &Utf8Path) -> Result<Vec<Utf8PathBuf>> {
+    let entries = artifacts_dir.read_dir_utf8().with_context(|| {
+        format!("Failed to read {artifacts_dir}. Make sure the package is built with `scarb build`")
+    })?;
+
+    let mut paths = vec![];
+    for entry in entries {
+        let entry = entry.with_context(|| format!("Failed to read {artifacts_dir}"))?;
+        if entry.file_name().ends_with(STARKNET_ARTIFACTS_SUFFIX) {
+            paths.push(entry.into_path());
+        }
+    }
+    paths.sort();
+
+    if paths.is_empty() {
+        return Err(anyhow!(
+            "Failed to find {artifacts_dir}/<target name>{STARKNET_ARTIFACTS_SUFFIX}. Make sure starknet target is correctly defined in Scarb.toml file"
+        ));
+    }
+    Ok(paths)
+}
+
+fn read_starknet_artifacts(path: &Utf8Path) -> Result<StarknetArtifacts> {
+    let starknet_artifacts =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {path} contents"))?;
+    serde_json::from_str(&starknet_artifacts)
+        .with_context(|| format!("Failed to parse {path} contents. Make sure you have enabled sierra and casm code generation in Scarb.toml"))
+}
+
+fn read_artifact(path: &Utf8Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("Failed to read contract artifact at {path}"))
+}
@@ -0,0 +1,142 @@
+use crate::abi::ContractAbi;
+use crate::cheatcodes::spy_events::EventSpy;
+use crate::cheatcodes::spy_messages_to_l1::MessageSpy;
+use crate::cheatcodes::CheatcodeError;
+use crate::state::{CheatcodeState, DictStateReader};
+use crate::CheatnetState;
+use blockifier::state::cached_state::{CachedState, GlobalContractCache};
+use cairo_felt::Felt252;
+use starknet_api::core::ClassHash;
+use std::collections::HashMap;
+
+/// Deep copy of the whole `CheatnetState`, later mutations of the state are not visible in it.
+#[derive(Clone)]
+pub struct StateSnapshot {
+    state_reader: DictStateReader,
+    cheatcode_state: CheatcodeState,
+    // Spies share what they captured with their clones held by the test,
+    // so only the number of captured events and messages is kept
+    event_spy_counts: Vec<usize>,
+    message_spy_counts: Vec<usize>,
+    class_abis: HashMap<ClassHash, ContractAbi>,
+    function_names: HashMap<ClassHash, HashMap<Felt252, String>>,
+    deploy_salt_base: u32,
+}
+
+impl CheatnetState {
+    /// Captures the blockifier state, the active cheatcodes, the events and messages captured
+    /// by the existing spies, the ABIs of the declared classes and the deploy salt counter.
+    #[must_use]
+    pub fn snapshot(&self) -> StateSnapshot {
+        StateSnapshot {
+            state_reader: self.flattened_state_reader(),
+            cheatcode_state: self.cheatcode_state.clone(),
+            event_spy_counts: self
+                .cheatcode_state
+                .event_spies
+                .iter()
+                .map(EventSpy::count)
+                .collect(),
+            message_spy_counts: self
+                .cheatcode_state
+                .message_spies
+                .iter()
+                .map(MessageSpy::count)
+                .collect(),
+            class_abis: self.class_abis.clone(),
+            function_names: self.function_names.clone(),
+            deploy_salt_base: self.deploy_salt_base,
+        }
+    }
+
+    /// Rolls the state back to the moment `snapshot` was taken.
+    /// Events and messages captured since then are removed from the spies,
+    /// spies created since then stop capturing.
+    /// The same snapshot can be restored multiple times.
+    pub fn restore(&mut self, snapshot: &StateSnapshot) {
+        self.blockifier_state = CachedState::new(
+            snapshot.state_reader.clone(),
+            GlobalContractCache::default(),
+        );
+        self.cheatcode_state = snapshot.cheatcode_state.clone();
+        for (spy, count) in self
+            .cheatcode_state
+            .event_spies
+            .iter()
+            .zip(&snapshot.event_spy_counts)
+        {
+            spy.truncate(*count);
+        }
+        for (spy, count) in self
+            .cheatcode_state
+            .message_spies
+            .iter()
+            .zip(&snapshot.message_spy_counts)
+        {
+            spy.truncate(*count);
+        }
+        self.class_abis = snapshot.class_abis.clone();
+        self.function_names = snapshot.function_names.clone();
+        self.deploy_salt_base = snapshot.deploy_salt_base;
+    }
+
+    /// Runs a single fuzzing case of a test: `body` is called with `inputs`
+    /// and every change it made is rolled back afterwards, even if it failed,
+    /// so consecutive cases start from the same state. Events and messages it emitted
+    /// are removed from the spies created before the case, see `restore`.
+    pub fn run_fuzz_case<T, F>(&mut self, inputs: &[Felt252], body: F) -> Result<T, CheatcodeError>
+    where
+        F: FnOnce(&mut CheatnetState, &[Felt252]) -> Result<T, CheatcodeError>,
+    {
+        let snapshot = self.snapshot();
+        let result = body(self, inputs);
+        self.restore(&snapshot);
+        result
+    }
+
+    /// Brings the state back to the one it was created with:
+    /// only the predeployed contracts are left, all cheatcodes are stopped
+    /// and the deploy salt counter starts from 0 again. The block context and the seed are kept.
+    pub fn reset(&mut self) {
+        self.blockifier_state = CachedState::new(
+            self.initial_state_reader.clone(),
+            GlobalContractCache::default(),
+        );
+        self.cheatcode_state = CheatcodeState::new();
+        self.class_abis.clear();
+        self.function_names.clear();
+        self.deploy_salt_base = 0;
+    }
+
+    // Changes which are not committed yet live only in the cache,
+    // so they are applied on top of a copy of the underlying state reader
+    pub(crate) fn flattened_state_reader(&self) -> DictStateReader {
+        let mut state_reader = self.blockifier_state.state.clone();
+        let state_diff = self.blockifier_state.to_state_diff();
+
+        for (contract_address, class_hash) in state_diff.address_to_class_hash {
+            state_reader
+                .address_to_class_hash
+                .insert(contract_address, class_hash);
+        }
+        for (contract_address, nonce) in state_diff.address_to_nonce {
+            state_reader
+                .address_to_nonce
+                .insert(contract_address, nonce);
+        }
+        for (contract_address, storage_updates) in state_diff.storage_updates {
+            for (key, value) in storage_updates {
+                state_reader
+                    .storage_view
+                    .insert((contract_address, key), value);
+            }
+        }
+        for (class_hash, compiled_class_hash) in state_diff.class_hash_to_compiled_class_hash {
+            state_reader
+                .class_hash_to_compiled_class_hash
+                .insert(class_hash, compiled_class_hash);
+        }
+
+        state_reader
+    }
+}
@@ -1,3 +1,10 @@
+use crate::cheatcodes::mock_call::MockedResponse;
+use crate::cheatcodes::spoof::TxInfoMock;
+use crate::cheatcodes::spy_events::EventSpy;
+use crate::cheatcodes::spy_messages_to_l1::MessageSpy;
+use crate::forking::state::ForkStateReader;
+use crate::panic_data::PanicTrace;
+use crate::reentrancy::ReentrancyTracker;
 use blockifier::{
     execution::contract_class::ContractClass,
     state::{
@@ -16,13 +23,15 @@ use starknet_api::{
 use std::collections::HashMap;
 
 /// A simple implementation of `StateReader` using `HashMap`s as storage.
-#[derive(Debug, Default)]
+/// If a fork is configured, values missing from the maps are read from the forked network.
+#[derive(Debug, Default, Clone)]
 pub struct DictStateReader {
     pub storage_view: HashMap<ContractStorageKey, StarkFelt>,
     pub address_to_nonce: HashMap<ContractAddress, Nonce>,
     pub address_to_class_hash: HashMap<ContractAddress, ClassHash>,
     pub class_hash_to_class: HashMap<ClassHash, ContractClass>,
     pub class_hash_to_compiled_class_hash: HashMap<ClassHash, CompiledClassHash>,
+    pub fork_state_reader: Option<ForkStateReader>,
 }
 
 impl StateReader for DictStateReader {
@@ -32,41 +41,56 @@ impl StateReader for DictStateReader {
         key: StorageKey,
     ) -> StateResult<StarkFelt> {
         let contract_storage_key = (contract_address, key);
-        let value = self
-            .storage_view
-            .get(&contract_storage_key)
-            .copied()
-            .unwrap_or_default();
-        Ok(value)
+        match (
+            self.storage_view.get(&contract_storage_key),
+            &mut self.fork_state_reader,
+        ) {
+            (Some(value), _) => Ok(*value),
+            (None, Some(fork_state_reader)) => {
+                fork_state_reader.get_storage_at(contract_address, key)
+            }
+            (None, None) => Ok(StarkFelt::default()),
+        }
     }
 
     fn get_nonce_at(&mut self, contract_address: ContractAddress) -> StateResult<Nonce> {
-        let nonce = self
-            .address_to_nonce
-            .get(&contract_address)
-            .copied()
-            .unwrap_or_default();
-        Ok(nonce)
+        match (
+            self.address_to_nonce.get(&contract_address),
+            &mut self.fork_state_reader,
+        ) {
+            (Some(nonce), _) => Ok(*nonce),
+            (None, Some(fork_state_reader)) => fork_state_reader.get_nonce_at(contract_address),
+            (None, None) => Ok(Nonce::default()),
+        }
     }
 
     fn get_compiled_contract_class(
         &mut self,
         class_hash: &ClassHash,
     ) -> StateResult<ContractClass> {
-        let contract_class = self.class_hash_to_class.get(class_hash).cloned();
-        match contract_class {
-            Some(contract_class) => Ok(contract_class),
-            _ => Err(StateError::UndeclaredClassHash(*class_hash)),
+        match (
+            self.class_hash_to_class.get(class_hash),
+            &mut self.fork_state_reader,
+        ) {
+            (Some(contract_class), _) => Ok(contract_class.clone()),
+            (None, Some(fork_state_reader)) => {
+                fork_state_reader.get_compiled_contract_class(class_hash)
+            }
+            (None, None) => Err(StateError::UndeclaredClassHash(*class_hash)),
         }
     }
 
     fn get_class_hash_at(&mut self, contract_address: ContractAddress) -> StateResult<ClassHash> {
-        let class_hash = self
-            .address_to_class_hash
-            .get(&contract_address)
-            .copied()
-            .unwrap_or_default();
-        Ok(class_hash)
+        match (
+            self.address_to_class_hash.get(&contract_address),
+            &mut self.fork_state_reader,
+        ) {
+            (Some(class_hash), _) => Ok(*class_hash),
+            (None, Some(fork_state_reader)) => {
+                fork_state_reader.get_class_hash_at(contract_address)
+            }
+            (None, None) => Ok(ClassHash::default()),
+        }
     }
 
     fn get_compiled_class_hash(
@@ -82,12 +106,72 @@ impl StateReader for DictStateReader {
     }
 }
 
+/// What happens when a deployed contract is called with a selector it doesn't have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnknownSelectorBehavior {
+    /// The call fails, like it would on the network.
+    #[default]
+    Panic,
+    /// The call succeeds and returns no data.
+    EmptyReturn,
+}
+
+/// What happens when cheatnet itself panics, e.g. on an artifact it fails to parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PanicPolicy {
+    /// The panic unwinds further, aborting the test runner unless it catches panics itself.
+    #[default]
+    Abort,
+    /// The panic is caught and returned as `CheatcodeFailure::InternalPanic`,
+    /// so the runner can go on and report the other tests. The state may be left
+    /// partially modified by the failed cheatcode.
+    Recover,
+}
+
+/// How long a cheat applies to its target contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheatSpan {
+    /// Until the cheat is stopped.
+    Indefinite,
+    /// For the given number of calls into the target, `TargetCalls(0)` doesn't apply the cheat at all.
+    /// Calls the target makes into itself, e.g. library calls, are counted as a part of the outer call.
+    TargetCalls(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct CheatStatus<T> {
+    pub value: T,
+    pub span: CheatSpan,
+}
+
 #[allow(clippy::module_name_repetitions)]
+#[derive(Clone)]
 pub struct CheatcodeState {
-    pub rolled_contracts: HashMap<ContractAddress, Felt252>,
-    pub pranked_contracts: HashMap<ContractAddress, ContractAddress>,
-    pub warped_contracts: HashMap<ContractAddress, Felt252>,
-    pub mocked_functions: HashMap<ContractAddress, HashMap<EntryPointSelector, Vec<StarkFelt>>>,
+    pub rolled_contracts: HashMap<ContractAddress, CheatStatus<Felt252>>,
+    pub pranked_contracts: HashMap<ContractAddress, CheatStatus<ContractAddress>>,
+    pub warped_contracts: HashMap<ContractAddress, CheatStatus<Felt252>>,
+    pub elected_contracts: HashMap<ContractAddress, CheatStatus<ContractAddress>>,
+    // Applied to all contracts which aren't rolled or warped themselves
+    pub global_roll: Option<Felt252>,
+    pub global_warp: Option<Felt252>,
+    pub spoofed_contracts: HashMap<ContractAddress, CheatStatus<TxInfoMock>>,
+    pub mocked_functions: HashMap<ContractAddress, HashMap<EntryPointSelector, MockedResponse>>,
+    pub cheated_block_hashes: HashMap<u64, Felt252>,
+    pub event_spies: Vec<EventSpy>,
+    pub message_spies: Vec<MessageSpy>,
+    pub unknown_selector_behavior: UnknownSelectorBehavior,
+    // Addresses of the contracts which are currently being executed, the innermost last
+    pub(crate) call_stack: Vec<ContractAddress>,
+    pub(crate) last_panic_trace: Option<PanicTrace>,
+    pub(crate) last_revert_raw: Option<String>,
+    // Lines printed by the contracts with `print`, until taken with `take_prints`.
+    // Set only if capturing prints was started, otherwise they are written to stdout
+    pub(crate) prints: Option<Vec<String>>,
+    // Set only if reentrancy tracking was started
+    pub(crate) reentrancy_tracker: Option<ReentrancyTracker>,
+    // Set while executing a call whose changes are discarded, e.g. `call`.
+    // Such calls aren't seen by spies and don't consume cheat spans
+    pub(crate) discarding_changes: bool,
 }
 
 impl CheatcodeState {
@@ -97,8 +181,85 @@ impl CheatcodeState {
             rolled_contracts: HashMap::new(),
             pranked_contracts: HashMap::new(),
             warped_contracts: HashMap::new(),
+            elected_contracts: HashMap::new(),
+            global_roll: None,
+            global_warp: None,
+            spoofed_contracts: HashMap::new(),
             mocked_functions: HashMap::new(),
+            cheated_block_hashes: HashMap::new(),
+            event_spies: vec![],
+            message_spies: vec![],
+            unknown_selector_behavior: UnknownSelectorBehavior::default(),
+            call_stack: vec![],
+            last_panic_trace: None,
+            last_revert_raw: None,
+            prints: None,
+            reentrancy_tracker: None,
+            discarding_changes: false,
+        }
+    }
+
+    /// Block number seen by calls into `contract_address`.
+    /// A roll of the contract takes precedence over the global one.
+    #[must_use]
+    pub fn rolled_block_number(&self, contract_address: &ContractAddress) -> Option<&Felt252> {
+        self.rolled_contracts
+            .get(contract_address)
+            .map(|rolled| &rolled.value)
+            .or(self.global_roll.as_ref())
+    }
+
+    /// Block timestamp seen by calls into `contract_address`.
+    /// A warp of the contract takes precedence over the global one.
+    #[must_use]
+    pub fn warped_timestamp(&self, contract_address: &ContractAddress) -> Option<&Felt252> {
+        self.warped_contracts
+            .get(contract_address)
+            .map(|warped| &warped.value)
+            .or(self.global_warp.as_ref())
+    }
+
+    // Has to be called after every call into `contract_address`
+    // which is not made from inside `contract_address` itself
+    pub(crate) fn decrement_cheat_spans(&mut self, contract_address: ContractAddress) {
+        decrement_cheat_span(&mut self.rolled_contracts, contract_address);
+        decrement_cheat_span(&mut self.pranked_contracts, contract_address);
+        decrement_cheat_span(&mut self.warped_contracts, contract_address);
+        decrement_cheat_span(&mut self.elected_contracts, contract_address);
+        decrement_cheat_span(&mut self.spoofed_contracts, contract_address);
+    }
+}
+
+/// Starts the cheat on `contract_address`, replacing the previous one.
+pub(crate) fn start_cheat<T>(
+    cheated_contracts: &mut HashMap<ContractAddress, CheatStatus<T>>,
+    contract_address: ContractAddress,
+    value: T,
+    span: CheatSpan,
+) {
+    if span == CheatSpan::TargetCalls(0) {
+        cheated_contracts.remove(&contract_address);
+    } else {
+        cheated_contracts.insert(contract_address, CheatStatus { value, span });
+    }
+}
+
+fn decrement_cheat_span<T>(
+    cheated_contracts: &mut HashMap<ContractAddress, CheatStatus<T>>,
+    contract_address: ContractAddress,
+) {
+    let expired = match cheated_contracts.get_mut(&contract_address) {
+        Some(CheatStatus {
+            span: CheatSpan::TargetCalls(calls),
+            ..
+        }) => {
+            *calls -= 1;
+            *calls == 0
         }
+        _ => false,
+    };
+    if expired {
+        cheated_contracts.remove(&contract_address);
     }
 }
 
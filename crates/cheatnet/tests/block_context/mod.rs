@@ -0,0 +1,196 @@
+use crate::{
+    assert_panic, assert_success,
+    common::{
+        deploy_contract,
+        state::{create_cheatnet_state, create_cheatnet_state_with_block_context_config},
+    },
+};
+use cairo_felt::Felt252;
+use cheatnet::{
+    cheatcodes::CheatcodeError,
+    constants::BlockContextConfig,
+    conversions::{felt_from_short_string, felt_selector_from_name},
+    rpc::call_contract,
+};
+use starknet_api::block::{BlockNumber, BlockTimestamp};
+use starknet_api::core::{ChainId, ContractAddress};
+
+#[test]
+fn default_chain_id() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "SpoofChecker", &[]);
+
+    let selector = felt_selector_from_name("get_chain_id");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+
+    assert_success!(output, vec![felt_from_short_string("SN_GOERLI")]);
+}
+
+#[test]
+fn custom_chain_id() {
+    let mut state = create_cheatnet_state_with_block_context_config(BlockContextConfig {
+        chain_id: ChainId("SN_MAIN".to_string()),
+        ..Default::default()
+    });
+    let contract_address = deploy_contract(&mut state, "SpoofChecker", &[]);
+
+    let selector = felt_selector_from_name("get_chain_id");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+
+    assert_success!(output, vec![felt_from_short_string("SN_MAIN")]);
+}
+
+#[test]
+fn set_chain_id() {
+    let mut state = create_cheatnet_state();
+    state
+        .set_chain_id(felt_from_short_string("SN_MAIN"))
+        .unwrap();
+
+    let contract_address = deploy_contract(&mut state, "ConstructorChainIdChecker", &[]);
+
+    let selector = felt_selector_from_name("get_stored_chain_id");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![felt_from_short_string("SN_MAIN")]);
+
+    let selector = felt_selector_from_name("get_chain_id");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![felt_from_short_string("SN_MAIN")]);
+}
+
+#[test]
+fn set_invalid_chain_id() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "SpoofChecker", &[]);
+
+    let result = state.set_chain_id(Felt252::from(0xff));
+    assert!(matches!(result, Err(CheatcodeError::Unrecoverable(_))));
+
+    let selector = felt_selector_from_name("get_chain_id");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![felt_from_short_string("SN_GOERLI")]);
+}
+
+#[test]
+fn advance_blocks_past_unlock() {
+    let mut state = create_cheatnet_state();
+    let default_config = BlockContextConfig::default();
+    let unlock_timestamp = default_config.block_timestamp.0 + 100;
+    let contract_address =
+        deploy_contract(&mut state, "TimeLock", &[Felt252::from(unlock_timestamp)]);
+    let unlock = felt_selector_from_name("unlock");
+
+    let output = call_contract(&contract_address, &unlock, &[], &mut state).unwrap();
+    assert_panic!(output, vec![felt_from_short_string("Still locked")]);
+
+    state.advance_blocks(10, 12).unwrap();
+    assert_eq!(
+        state.block_context_config().block_number,
+        BlockNumber(default_config.block_number.0 + 10)
+    );
+
+    let output = call_contract(&contract_address, &unlock, &[], &mut state).unwrap();
+    assert_success!(output, vec![]);
+    let selector = felt_selector_from_name("is_unlocked");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(1)]);
+}
+
+#[test]
+fn advance_blocks_keeps_warps() {
+    let mut state = create_cheatnet_state();
+    let warped_address = deploy_contract(&mut state, "WarpChecker", &[]);
+    let other_address = deploy_contract(&mut state, "WarpChecker", &[]);
+    let selector = felt_selector_from_name("get_block_timestamp");
+
+    state.start_warp(warped_address, Felt252::from(5));
+    state.advance_blocks(2, 30).unwrap();
+
+    let output = call_contract(&warped_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(5)]);
+    let output = call_contract(&other_address, &selector, &[], &mut state).unwrap();
+    assert_success!(
+        output,
+        vec![Felt252::from(
+            BlockContextConfig::default().block_timestamp.0 + 60
+        )]
+    );
+}
+
+#[test]
+fn advance_blocks_overflow() {
+    let mut state = create_cheatnet_state();
+    let default_config = BlockContextConfig::default();
+
+    for (count, seconds_per_block) in [(u64::MAX, 0), (2, u64::MAX)] {
+        let result = state.advance_blocks(count, seconds_per_block);
+        assert!(matches!(result, Err(CheatcodeError::Unrecoverable(_))));
+        assert_eq!(
+            state.block_context_config().block_number,
+            default_config.block_number
+        );
+        assert_eq!(
+            state.block_context_config().block_timestamp,
+            default_config.block_timestamp
+        );
+    }
+}
+
+#[test]
+fn custom_block_number_and_timestamp() {
+    let mut state = create_cheatnet_state_with_block_context_config(BlockContextConfig {
+        block_number: BlockNumber(123),
+        block_timestamp: BlockTimestamp(456),
+        ..Default::default()
+    });
+
+    let roll_checker = deploy_contract(&mut state, "RollChecker", &[]);
+    let selector = felt_selector_from_name("get_block_number");
+    let output = call_contract(&roll_checker, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(123)]);
+
+    let warp_checker = deploy_contract(&mut state, "WarpChecker", &[]);
+    let selector = felt_selector_from_name("get_block_timestamp");
+    let output = call_contract(&warp_checker, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(456)]);
+}
+
+#[test]
+fn current_block_context_with_warp() {
+    let mut state = create_cheatnet_state();
+    let warped_address = ContractAddress::from(123_u128);
+    let other_address = ContractAddress::from(234_u128);
+
+    state.start_warp(warped_address, Felt252::from(1234));
+    state.start_roll(warped_address, Felt252::from(5678));
+
+    let block_context = state.current_block_context(Some(warped_address)).unwrap();
+    assert_eq!(block_context.block_timestamp, BlockTimestamp(1234));
+    assert_eq!(block_context.block_number, BlockNumber(5678));
+
+    let default_config = BlockContextConfig::default();
+    for target in [Some(other_address), None] {
+        let block_context = state.current_block_context(target).unwrap();
+        assert_eq!(
+            block_context.block_timestamp,
+            default_config.block_timestamp
+        );
+        assert_eq!(block_context.block_number, default_config.block_number);
+        assert_eq!(
+            block_context.sequencer_address,
+            default_config.sequencer_address
+        );
+    }
+}
+
+#[test]
+fn current_block_context_with_roll_exceeding_u64() {
+    let mut state = create_cheatnet_state();
+    let rolled_address = ContractAddress::from(123_u128);
+
+    state.start_roll(rolled_address, Felt252::from(u128::from(u64::MAX) + 1));
+
+    let result = state.current_block_context(Some(rolled_address));
+    assert!(matches!(result, Err(CheatcodeError::Unrecoverable(_))));
+    assert!(state.current_block_context(None).is_ok());
+}
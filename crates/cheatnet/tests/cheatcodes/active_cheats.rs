@@ -0,0 +1,108 @@
+use crate::common::{deploy_contract, state::create_cheatnet_state};
+use blockifier::abi::abi_utils::selector_from_name;
+use cairo_felt::Felt252;
+use cheatnet::{
+    cheatcodes::{
+        active_cheats::{CheatDescription, CheatValue},
+        spoof::TxInfoMock,
+    },
+    conversions::felt_selector_from_name,
+    state::CheatSpan,
+};
+use starknet_api::core::ContractAddress;
+use starknet_api::hash::StarkFelt;
+
+#[test]
+fn active_cheats_lists_cheats_in_order() {
+    let mut state = create_cheatnet_state();
+    let first = ContractAddress::from(1_u8);
+    let second = ContractAddress::from(2_u8);
+
+    state.start_mock_call(
+        second,
+        selector_from_name("get_thing"),
+        vec![StarkFelt::from(123_u32)],
+    );
+    state.prank(second, first, CheatSpan::TargetCalls(2));
+    state.start_warp(first, Felt252::from(100));
+    state.start_roll(first, Felt252::from(10));
+    state.start_warp_global(Felt252::from(50));
+
+    assert_eq!(
+        state.active_cheats(),
+        vec![
+            CheatDescription {
+                target: None,
+                value: CheatValue::Warp(Felt252::from(50)),
+                span: CheatSpan::Indefinite,
+            },
+            CheatDescription {
+                target: Some(first),
+                value: CheatValue::Roll(Felt252::from(10)),
+                span: CheatSpan::Indefinite,
+            },
+            CheatDescription {
+                target: Some(first),
+                value: CheatValue::Warp(Felt252::from(100)),
+                span: CheatSpan::Indefinite,
+            },
+            CheatDescription {
+                target: Some(second),
+                value: CheatValue::Prank(first),
+                span: CheatSpan::TargetCalls(2),
+            },
+            CheatDescription {
+                target: Some(second),
+                value: CheatValue::MockCall {
+                    selector: selector_from_name("get_thing"),
+                    ret_data: Some(vec![Felt252::from(123)]),
+                },
+                span: CheatSpan::Indefinite,
+            },
+        ]
+    );
+
+    state.stop_warp(first);
+    state.stop_warp_global();
+    state.stop_roll(first);
+    state.stop_prank(second);
+    state.stop_mock_call(second, selector_from_name("get_thing"));
+    assert_eq!(state.active_cheats(), vec![]);
+}
+
+#[test]
+fn clear_cheats_stops_all_cheats() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    let other_address = ContractAddress::from(1_u8);
+
+    state.start_mock_call(
+        contract_address,
+        selector_from_name("get_balance"),
+        vec![StarkFelt::from(123_u32)],
+    );
+    state.prank(contract_address, other_address, CheatSpan::TargetCalls(2));
+    state.start_warp(contract_address, Felt252::from(100));
+    state.start_roll_global(Felt252::from(10));
+    state.start_elect(other_address, contract_address);
+    state.start_spoof(
+        other_address,
+        TxInfoMock {
+            nonce: Some(Felt252::from(3)),
+            ..Default::default()
+        },
+    );
+    assert_eq!(state.active_cheats().len(), 6);
+
+    state.clear_cheats();
+    assert_eq!(state.active_cheats(), vec![]);
+
+    let ret_data = state
+        .call(
+            contract_address,
+            &felt_selector_from_name("get_balance"),
+            &[],
+        )
+        .unwrap();
+    assert_eq!(ret_data, vec![Felt252::from(0)]);
+}
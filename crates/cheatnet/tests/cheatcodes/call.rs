@@ -0,0 +1,205 @@
+use crate::{
+    assert_success,
+    common::{deploy_contract, state::create_cheatnet_state},
+};
+use cairo_felt::Felt252;
+use cheatnet::{
+    cheatcodes::{CheatcodeError, CheatcodeFailure},
+    conversions::{felt_from_short_string, felt_selector_from_name},
+    rpc::call_contract,
+    state::CheatSpan,
+};
+use starknet_api::core::ContractAddress;
+
+#[test]
+fn call_view() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+
+    call_contract(
+        &contract_address,
+        &felt_selector_from_name("increase_balance"),
+        &[Felt252::from(42)],
+        &mut state,
+    )
+    .unwrap();
+
+    let ret_data = state
+        .call(
+            contract_address,
+            &felt_selector_from_name("get_balance"),
+            &[],
+        )
+        .unwrap();
+
+    assert_eq!(ret_data, vec![Felt252::from(42)]);
+}
+
+#[test]
+fn call_is_not_seen_by_spies() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "SpyEventsChecker", &[]);
+
+    let spy = state.spy_events();
+    state
+        .call(
+            contract_address,
+            &felt_selector_from_name("emit_one_event"),
+            &[Felt252::from(1)],
+        )
+        .unwrap();
+
+    assert_eq!(spy.count(), 0);
+}
+
+#[test]
+fn call_does_not_consume_cheat_spans() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "PrankChecker", &[]);
+    let selector = felt_selector_from_name("get_caller_address");
+
+    state.prank(
+        contract_address,
+        ContractAddress::from(123_u128),
+        CheatSpan::TargetCalls(1),
+    );
+
+    let ret_data = state.call(contract_address, &selector, &[]).unwrap();
+    assert_eq!(ret_data, vec![Felt252::from(123)]);
+
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(123)]);
+}
+
+#[test]
+fn call_does_not_change_state() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+
+    let ret_data = state
+        .call(
+            contract_address,
+            &felt_selector_from_name("increase_balance"),
+            &[Felt252::from(42)],
+        )
+        .unwrap();
+    assert!(ret_data.is_empty());
+
+    let output = call_contract(
+        &contract_address,
+        &felt_selector_from_name("get_balance"),
+        &[],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![Felt252::from(0)]);
+}
+
+#[test]
+fn call_panicking() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+
+    let result = state.call(
+        contract_address,
+        &felt_selector_from_name("increase_balance"),
+        &[Felt252::from(0)],
+    );
+
+    assert!(match result {
+        Err(CheatcodeError::Recoverable(failure)) =>
+            failure.panic_data() == vec![felt_from_short_string("Amount cannot be 0")],
+        _ => false,
+    });
+}
+
+#[test]
+fn call_with_gas_within_limit() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "GasChecker", &[]);
+
+    let ret_data = state
+        .call_with_gas(
+            contract_address,
+            &felt_selector_from_name("sum_up_to"),
+            &[Felt252::from(10)],
+            10_000_000,
+        )
+        .unwrap();
+
+    assert_eq!(ret_data, vec![Felt252::from(55)]);
+}
+
+#[test]
+fn call_with_gas_out_of_gas() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "GasChecker", &[]);
+
+    let result = state.call_with_gas(
+        contract_address,
+        &felt_selector_from_name("sum_up_to"),
+        &[Felt252::from(10_000)],
+        100_000,
+    );
+
+    match result {
+        Err(CheatcodeError::Recoverable(CheatcodeFailure::OutOfGas { gas_limit, n_steps })) => {
+            assert_eq!(gas_limit, 100_000);
+            assert!(n_steps > 0);
+        }
+        _ => panic!("Expected the call to run out of gas"),
+    }
+}
+
+#[test]
+fn assert_reverts_with_matching_panic() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+
+    state
+        .assert_reverts_with(
+            contract_address,
+            &felt_selector_from_name("increase_balance"),
+            &[Felt252::from(0)],
+            &[felt_from_short_string("Amount cannot be 0")],
+        )
+        .unwrap();
+}
+
+#[test]
+fn assert_reverts_with_successful_call() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+
+    let result = state.assert_reverts_with(
+        contract_address,
+        &felt_selector_from_name("get_balance"),
+        &[],
+        &[felt_from_short_string("Amount cannot be 0")],
+    );
+
+    assert!(matches!(
+        result,
+        Err(CheatcodeError::Unrecoverable(error))
+            if error.to_string() == "Expected the call to revert with ['Amount cannot be 0'], but it succeeded and returned [0x0]"
+    ));
+}
+
+#[test]
+fn assert_reverts_with_different_panic() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+
+    let result = state.assert_reverts_with(
+        contract_address,
+        &felt_selector_from_name("increase_balance"),
+        &[Felt252::from(0)],
+        &[felt_from_short_string("Other error"), Felt252::from(1)],
+    );
+
+    assert!(matches!(
+        result,
+        Err(CheatcodeError::Unrecoverable(error))
+            if error.to_string() == "Expected the call to revert with ['Other error', 0x1], but it reverted with ['Amount cannot be 0']"
+    ));
+}
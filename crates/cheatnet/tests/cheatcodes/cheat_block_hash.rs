@@ -0,0 +1,110 @@
+use crate::{
+    assert_success,
+    common::{deploy_contract, state::create_cheatnet_state},
+};
+use cairo_felt::Felt252;
+use cheatnet::{
+    cheatcodes::CheatcodeError, conversions::felt_selector_from_name, rpc::call_contract,
+};
+
+#[test]
+fn cheat_block_hash_simple() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "BlockHashChecker", &[]);
+
+    state.start_cheat_block_hash(100, Felt252::from(123));
+
+    let selector = felt_selector_from_name("get_block_hash");
+    let output = call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(100)],
+        &mut state,
+    )
+    .unwrap();
+
+    assert_success!(output, vec![Felt252::from(123)]);
+}
+
+#[test]
+fn cheat_block_hash_other_block_number() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "BlockHashChecker", &[]);
+
+    state.start_cheat_block_hash(100, Felt252::from(123));
+
+    let selector = felt_selector_from_name("get_block_hash");
+    let output = call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(101)],
+        &mut state,
+    )
+    .unwrap();
+
+    assert_success!(output, vec![Felt252::from(0)]);
+}
+
+#[test]
+fn cheat_block_hash_stop() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "BlockHashChecker", &[]);
+
+    state.start_cheat_block_hash(100, Felt252::from(123));
+    state.stop_cheat_block_hash(100);
+
+    let selector = felt_selector_from_name("get_block_hash");
+    let output = call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(100)],
+        &mut state,
+    )
+    .unwrap();
+
+    assert_success!(output, vec![Felt252::from(0)]);
+}
+
+#[test]
+fn cheat_block_hashes_consecutive() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "BlockHashChecker", &[]);
+
+    state
+        .cheat_block_hashes(100, &[Felt252::from(1), Felt252::from(2), Felt252::from(3)])
+        .unwrap();
+
+    let selector = felt_selector_from_name("get_block_hash");
+    for (block_number, expected_hash) in [(100, 1), (101, 2), (102, 3), (103, 0), (99, 0)] {
+        let output = call_contract(
+            &contract_address,
+            &selector,
+            &[Felt252::from(block_number)],
+            &mut state,
+        )
+        .unwrap();
+        assert_success!(output, vec![Felt252::from(expected_hash)]);
+    }
+}
+
+#[test]
+fn cheat_block_hashes_overflow() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "BlockHashChecker", &[]);
+
+    let result = state.cheat_block_hashes(u64::MAX, &[Felt252::from(1), Felt252::from(2)]);
+    assert!(matches!(result, Err(CheatcodeError::Unrecoverable(_))));
+
+    state
+        .cheat_block_hashes(u64::MAX, &[Felt252::from(1)])
+        .unwrap();
+    let selector = felt_selector_from_name("get_block_hash");
+    let output = call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(u64::MAX)],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![Felt252::from(1)]);
+}
@@ -0,0 +1,88 @@
+use crate::{
+    assert_success,
+    common::{deploy_contract, state::create_cheatnet_state},
+};
+use cairo_felt::Felt252;
+use cheatnet::{
+    cheatcodes::{
+        cheat_execution_info::{BlockInfoMock, ExecutionInfoMock},
+        spoof::TxInfoMock,
+    },
+    conversions::felt_selector_from_name,
+    rpc::call_contract,
+    CheatnetState,
+};
+use starknet_api::core::ContractAddress;
+
+fn call_getter(
+    state: &mut CheatnetState,
+    contract_address: ContractAddress,
+    getter: &str,
+) -> cheatnet::rpc::CallContractOutput {
+    call_contract(
+        &contract_address,
+        &felt_selector_from_name(getter),
+        &[],
+        state,
+    )
+    .unwrap()
+}
+
+#[test]
+fn cheat_execution_info_block_number_and_caller() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "ExecutionInfoChecker", &[]);
+
+    state.cheat_execution_info(
+        contract_address,
+        ExecutionInfoMock {
+            block_info: BlockInfoMock {
+                block_number: Some(Felt252::from(123)),
+                ..Default::default()
+            },
+            caller_address: Some(ContractAddress::from(456_u16)),
+            ..Default::default()
+        },
+    );
+
+    let output = call_getter(&mut state, contract_address, "get_block_number");
+    assert_success!(output, vec![Felt252::from(123)]);
+    let output = call_getter(&mut state, contract_address, "get_caller_address");
+    assert_success!(output, vec![Felt252::from(456)]);
+
+    // The last override of a field wins
+    state.start_roll(contract_address, Felt252::from(789));
+    let output = call_getter(&mut state, contract_address, "get_block_number");
+    assert_success!(output, vec![Felt252::from(789)]);
+    let output = call_getter(&mut state, contract_address, "get_caller_address");
+    assert_success!(output, vec![Felt252::from(456)]);
+}
+
+#[test]
+fn cheat_execution_info_keeps_spoofed_fields() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "ExecutionInfoChecker", &[]);
+
+    state.start_spoof(
+        contract_address,
+        TxInfoMock {
+            transaction_hash: Some(Felt252::from(123)),
+            ..Default::default()
+        },
+    );
+    state.cheat_execution_info(
+        contract_address,
+        ExecutionInfoMock {
+            tx_info: TxInfoMock {
+                nonce: Some(Felt252::from(3)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let output = call_getter(&mut state, contract_address, "get_transaction_hash");
+    assert_success!(output, vec![Felt252::from(123)]);
+    let output = call_getter(&mut state, contract_address, "get_nonce");
+    assert_success!(output, vec![Felt252::from(3)]);
+}
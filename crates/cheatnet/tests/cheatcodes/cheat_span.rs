@@ -0,0 +1,155 @@
+use crate::{
+    assert_success,
+    common::{deploy_contract, get_contracts, recover_data, state::create_cheatnet_state},
+};
+use cairo_felt::Felt252;
+use cheatnet::{
+    conversions::{
+        class_hash_to_felt, contract_address_to_felt, felt_from_short_string,
+        felt_selector_from_name,
+    },
+    rpc::call_contract,
+    state::CheatSpan,
+};
+use starknet_api::core::ContractAddress;
+
+#[test]
+fn prank_single_call() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "PrankChecker", &[]);
+    let selector = felt_selector_from_name("get_caller_address");
+
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    let original_caller_address = recover_data(output);
+
+    state.prank(
+        contract_address,
+        ContractAddress::from(123_u128),
+        CheatSpan::TargetCalls(1),
+    );
+
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(123)]);
+
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_eq!(recover_data(output), original_caller_address);
+}
+
+#[test]
+fn roll_multiple_calls() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "RollChecker", &[]);
+    let selector = felt_selector_from_name("get_block_number");
+
+    state.roll(
+        contract_address,
+        Felt252::from(123),
+        CheatSpan::TargetCalls(2),
+    );
+
+    for _ in 0..2 {
+        let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+        assert_success!(output, vec![Felt252::from(123)]);
+    }
+
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_ne!(recover_data(output), vec![Felt252::from(123)]);
+}
+
+#[test]
+fn warp_indefinite() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "WarpChecker", &[]);
+    let selector = felt_selector_from_name("get_block_timestamp");
+
+    state.warp(contract_address, Felt252::from(123), CheatSpan::Indefinite);
+
+    for _ in 0..3 {
+        let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+        assert_success!(output, vec![Felt252::from(123)]);
+    }
+}
+
+#[test]
+fn calls_from_other_contracts_are_counted() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "PrankChecker", &[]);
+    let proxy_address = deploy_contract(&mut state, "PrankCheckerProxy", &[]);
+
+    state.prank(
+        contract_address,
+        ContractAddress::from(123_u128),
+        CheatSpan::TargetCalls(1),
+    );
+
+    let proxy_selector = felt_selector_from_name("get_prank_checkers_caller_address");
+    let output = call_contract(
+        &proxy_address,
+        &proxy_selector,
+        &[contract_address_to_felt(contract_address)],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![Felt252::from(123)]);
+
+    let selector = felt_selector_from_name("get_caller_address");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_ne!(recover_data(output), vec![Felt252::from(123)]);
+}
+
+#[test]
+fn library_calls_are_counted_as_part_of_the_call() {
+    let mut state = create_cheatnet_state();
+
+    let contracts = get_contracts();
+    let contract_name = felt_from_short_string("PrankChecker");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+
+    let lib_call_address = deploy_contract(&mut state, "PrankCheckerLibCall", &[]);
+
+    state.prank(
+        lib_call_address,
+        ContractAddress::from(123_u128),
+        CheatSpan::TargetCalls(1),
+    );
+
+    let selector = felt_selector_from_name("get_caller_address_with_lib_call");
+    let output = call_contract(
+        &lib_call_address,
+        &selector,
+        &[class_hash_to_felt(class_hash)],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![Felt252::from(123)]);
+
+    let output = call_contract(
+        &lib_call_address,
+        &selector,
+        &[class_hash_to_felt(class_hash)],
+        &mut state,
+    )
+    .unwrap();
+    assert_ne!(recover_data(output), vec![Felt252::from(123)]);
+}
+
+#[test]
+fn zero_target_calls_does_not_apply_cheat() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "RollChecker", &[]);
+    let selector = felt_selector_from_name("get_block_number");
+
+    state.roll(
+        contract_address,
+        Felt252::from(123),
+        CheatSpan::TargetCalls(0),
+    );
+
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_ne!(recover_data(output), vec![Felt252::from(123)]);
+}
@@ -0,0 +1,138 @@
+use crate::{
+    assert_success,
+    common::{get_contracts, state::create_cheatnet_state},
+};
+use cairo_felt::Felt252;
+use cheatnet::{
+    cheatcodes::{CheatcodeError, CheatcodeFailure, ContractArtifacts},
+    conversions::{felt_from_short_string, felt_selector_from_name},
+    rpc::call_contract,
+    state::PanicPolicy,
+};
+use std::collections::HashMap;
+
+#[test]
+fn declare_and_deploy_multiple_times() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("HelloStarknet");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+
+    let first_address = state.deploy(&class_hash, &[]).unwrap();
+    let second_address = state.deploy(&class_hash, &[]).unwrap();
+
+    assert_ne!(first_address, second_address);
+}
+
+#[test]
+fn redeclare_returns_same_class_hash() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("HelloStarknet");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    let redeclared_class_hash = state.declare(&contract_name, &contracts).unwrap();
+
+    assert_eq!(class_hash, redeclared_class_hash);
+    state.deploy(&redeclared_class_hash, &[]).unwrap();
+}
+
+#[test]
+fn declare_casm_and_deploy() {
+    let mut state = create_cheatnet_state();
+    let casm = &get_contracts().get("HelloStarknet").unwrap().casm;
+
+    let class_hash = state.declare_casm(casm).unwrap();
+    let contract_address = state.deploy(&class_hash, &[]).unwrap();
+
+    call_contract(
+        &contract_address,
+        &felt_selector_from_name("increase_balance"),
+        &[Felt252::from(5)],
+        &mut state,
+    )
+    .unwrap();
+    let output = call_contract(
+        &contract_address,
+        &felt_selector_from_name("get_balance"),
+        &[],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![Felt252::from(5)]);
+}
+
+#[test]
+fn declare_casm_with_unsupported_compiler_version() {
+    let mut state = create_cheatnet_state();
+    let casm = &get_contracts().get("HelloStarknet").unwrap().casm;
+    let mut casm: serde_json::Value = serde_json::from_str(casm).unwrap();
+    casm["compiler_version"] = "1.1.0".into();
+
+    let result = state.declare_casm(&casm.to_string());
+
+    assert!(matches!(
+        result,
+        Err(CheatcodeError::Unrecoverable(error))
+            if error.to_string().contains("Unsupported CASM compiler version 1.1.0")
+    ));
+}
+
+fn contracts_with_invalid_artifact() -> HashMap<String, ContractArtifacts> {
+    HashMap::from([(
+        "Invalid".to_string(),
+        ContractArtifacts {
+            sierra: "not a sierra class".to_string(),
+            casm: String::new(),
+        },
+    )])
+}
+
+#[test]
+#[should_panic(expected = "Failed to parse json from artifact")]
+fn declare_invalid_artifact_aborts_by_default() {
+    let mut state = create_cheatnet_state();
+
+    let _ = state.declare(
+        &felt_from_short_string("Invalid"),
+        &contracts_with_invalid_artifact(),
+    );
+}
+
+#[test]
+fn declare_invalid_artifact_with_recover_panic_policy() {
+    let mut state = create_cheatnet_state();
+    state.set_panic_policy(PanicPolicy::Recover);
+
+    let result = state.declare(
+        &felt_from_short_string("Invalid"),
+        &contracts_with_invalid_artifact(),
+    );
+    assert!(matches!(
+        result,
+        Err(CheatcodeError::Recoverable(CheatcodeFailure::InternalPanic(message)))
+            if message.starts_with("Failed to parse json from artifact")
+    ));
+
+    // The state is still usable afterwards
+    let class_hash = state
+        .declare(&felt_from_short_string("HelloStarknet"), &get_contracts())
+        .unwrap();
+    state.deploy(&class_hash, &[]).unwrap();
+}
+
+#[test]
+fn declare_reuses_compiled_artifact() {
+    let contracts = get_contracts();
+    let contract_name = felt_from_short_string("ERC20");
+
+    let mut state = create_cheatnet_state();
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+
+    // Declaring in a new state has to register the class again, but doesn't compile it
+    let mut state = create_cheatnet_state();
+    let cached_class_hash = state.declare(&contract_name, &contracts).unwrap();
+
+    assert_eq!(class_hash, cached_class_hash);
+}
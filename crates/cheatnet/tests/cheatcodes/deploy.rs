@@ -0,0 +1,679 @@
+use crate::{
+    assert_success,
+    common::{
+        deploy_contract, get_contracts, recover_data,
+        state::{create_cheatnet_state, create_cheatnet_state_with_seed},
+    },
+};
+use cairo_felt::Felt252;
+use cairo_lang_starknet::contract_class::ContractClass;
+use cheatnet::{
+    address::calculate_contract_address,
+    cheatcodes::{deploy::DeployOptions, CheatcodeError, CheatcodeFailure, Event, InnerCall},
+    conversions::{
+        class_hash_to_felt, contract_address_from_felt, contract_address_to_felt,
+        felt_from_short_string, felt_selector_from_name, map_entry_address,
+    },
+    panic_data::PanicData,
+    rpc::call_contract,
+};
+use starknet_api::core::{ClassHash, ContractAddress};
+use starknet_api::hash::StarkFelt;
+
+#[test]
+fn deploy_at_predefined_address() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("PrankChecker");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    let contract_address = state
+        .deploy_at(&class_hash, &[], ContractAddress::from(1_u8))
+        .unwrap();
+
+    assert_eq!(contract_address, ContractAddress::from(1_u8));
+
+    let proxy_name = felt_from_short_string("PrankCheckerProxy");
+    let proxy_class_hash = state.declare(&proxy_name, &contracts).unwrap();
+    let proxy_address = state
+        .deploy_at(&proxy_class_hash, &[], ContractAddress::from(2_u8))
+        .unwrap();
+
+    let selector = felt_selector_from_name("get_prank_checkers_caller_address");
+    let output = call_contract(
+        &proxy_address,
+        &selector,
+        &[contract_address_to_felt(contract_address)],
+        &mut state,
+    )
+    .unwrap();
+
+    assert_success!(output, vec![Felt252::from(2)]);
+}
+
+#[test]
+fn deploy_with_seed_is_reproducible() {
+    let first_deployed_address = |seed| {
+        let mut state = create_cheatnet_state_with_seed(seed);
+        deploy_contract(&mut state, "HelloStarknet", &[])
+    };
+
+    assert_eq!(first_deployed_address(42), first_deployed_address(42));
+    assert_ne!(first_deployed_address(42), first_deployed_address(43));
+
+    let mut state = create_cheatnet_state();
+    let default_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    assert_eq!(first_deployed_address(0), default_address);
+}
+
+#[test]
+fn deploy_at_runs_constructor() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("MockChecker");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    let contract_address = state
+        .deploy_at(
+            &class_hash,
+            &[Felt252::from(420)],
+            ContractAddress::from(1_u8),
+        )
+        .unwrap();
+
+    let selector = felt_selector_from_name("get_thing");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+
+    assert_success!(output, vec![Felt252::from(420)]);
+}
+
+#[test]
+fn deploy_at_taken_address() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("HelloStarknet");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    state
+        .deploy_at(&class_hash, &[], ContractAddress::from(1_u8))
+        .unwrap();
+
+    let result = state.deploy_at(&class_hash, &[], ContractAddress::from(1_u8));
+
+    assert!(matches!(
+        result,
+        Err(CheatcodeError::Recoverable(
+            CheatcodeFailure::AddressOccupied
+        ))
+    ));
+}
+
+#[test]
+fn deploy_calldata_no_constructor() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("HelloStarknet");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+
+    let result = state.deploy(&class_hash, &[Felt252::from(123)]);
+
+    match result {
+        Err(CheatcodeError::Recoverable(failure)) => {
+            assert_eq!(failure, CheatcodeFailure::MissingConstructor);
+            assert_eq!(
+                failure.panic_data(),
+                vec![felt_from_short_string("No constructor in contract")]
+            );
+        }
+        _ => panic!("Deploy should fail recoverably"),
+    }
+}
+
+#[test]
+fn deploy_with_salt_is_deterministic() {
+    let contracts = get_contracts();
+    let contract_name = felt_from_short_string("HelloStarknet");
+
+    let mut state = create_cheatnet_state();
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    let contract_address = state
+        .deploy_with_salt(&class_hash, &[], Felt252::from(123))
+        .unwrap();
+
+    let mut other_state = create_cheatnet_state();
+    let other_class_hash = other_state.declare(&contract_name, &contracts).unwrap();
+    let other_contract_address = other_state
+        .deploy_with_salt(&other_class_hash, &[], Felt252::from(123))
+        .unwrap();
+
+    assert_eq!(contract_address, other_contract_address);
+}
+
+#[test]
+fn deploy_opts_from_zero() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+    let contract_name = felt_from_short_string("HelloStarknet");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    let salt = Felt252::from(123);
+
+    let from_zero_address = state
+        .deploy_opts(
+            &class_hash,
+            &[],
+            DeployOptions {
+                salt: Some(salt.clone()),
+                deploy_from_zero: true,
+            },
+        )
+        .unwrap();
+    let from_account_address = state
+        .deploy_opts(
+            &class_hash,
+            &[],
+            DeployOptions {
+                salt: Some(salt.clone()),
+                deploy_from_zero: false,
+            },
+        )
+        .unwrap();
+
+    assert_ne!(from_zero_address, from_account_address);
+    assert_eq!(
+        from_zero_address,
+        calculate_contract_address(&salt, &class_hash, &[], ContractAddress::default())
+    );
+    assert_eq!(
+        from_account_address,
+        calculate_contract_address(&salt, &class_hash, &[], state.test_account_address())
+    );
+}
+
+#[test]
+fn deploy_same_class_twice() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("HelloStarknet");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+
+    let contract_address = state.deploy(&class_hash, &[]).unwrap();
+    let other_contract_address = state.deploy(&class_hash, &[]).unwrap();
+
+    assert_ne!(contract_address, other_contract_address);
+}
+
+#[test]
+fn deploy_addresses_do_not_depend_on_other_states() {
+    let contracts = get_contracts();
+    let contract_name = felt_from_short_string("HelloStarknet");
+
+    let mut state = create_cheatnet_state();
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    let contract_address = state.deploy(&class_hash, &[]).unwrap();
+
+    let mut other_state = create_cheatnet_state();
+    let other_class_hash = other_state.declare(&contract_name, &contracts).unwrap();
+    let other_contract_address = other_state.deploy(&other_class_hash, &[]).unwrap();
+
+    assert_eq!(contract_address, other_contract_address);
+}
+
+#[test]
+fn deploy_with_salt_keeps_salt_base() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("HelloStarknet");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+
+    let precalculated_address = state.precalculate_address(&class_hash, &[]);
+    let contract_address = state
+        .deploy_with_salt(&class_hash, &[], Felt252::from(123))
+        .unwrap();
+
+    assert_eq!(state.deploy_salt_base, 0);
+    assert_ne!(contract_address, precalculated_address);
+
+    let contract_address = state.deploy(&class_hash, &[]).unwrap();
+
+    assert_eq!(contract_address, precalculated_address);
+}
+
+#[test]
+fn deploy_detailed_returns_constructor_events() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("ConstructorEventEmitter");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    let deploy_result = state
+        .deploy_detailed(&class_hash, &[Felt252::from(123)])
+        .unwrap();
+
+    assert_eq!(
+        deploy_result.events,
+        vec![Event {
+            from: deploy_result.contract_address,
+            keys: vec![felt_selector_from_name("Initialized")],
+            data: vec![Felt252::from(123)],
+        }]
+    );
+    assert!(deploy_result.l2_to_l1_messages.is_empty());
+}
+
+#[test]
+fn deploy_detailed_returns_class_hash() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("HelloStarknet");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    let deploy_result = state.deploy_detailed(&class_hash, &[]).unwrap();
+
+    assert_eq!(deploy_result.class_hash, class_hash);
+    assert_eq!(
+        state
+            .get_class_hash(deploy_result.contract_address)
+            .unwrap(),
+        class_hash
+    );
+}
+
+#[test]
+fn deploy_with_too_few_constructor_args() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("ERC20");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+
+    // name, symbol, decimals, initial_supply: u256 - the recipient is missing
+    let calldata = [1, 2, 18, 1000, 0].map(Felt252::from);
+    let result = state.deploy(&class_hash, &calldata);
+
+    assert!(match result {
+        Err(CheatcodeError::Recoverable(failure)) =>
+            failure.panic_data()
+                == vec![
+                    felt_from_short_string("Invalid constructor calldata"),
+                    felt_from_short_string("expected 6, got 5"),
+                ],
+        _ => false,
+    });
+}
+
+#[test]
+fn deploy_with_too_many_constructor_args() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("ERC20");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+
+    let calldata = [1, 2, 18, 1000, 0, 123, 456].map(Felt252::from);
+    let result = state.deploy(&class_hash, &calldata);
+
+    assert!(match result {
+        Err(CheatcodeError::Recoverable(failure)) =>
+            failure.panic_data()
+                == vec![
+                    felt_from_short_string("Invalid constructor calldata"),
+                    felt_from_short_string("expected 6, got 7"),
+                ],
+        _ => false,
+    });
+}
+
+#[test]
+fn deploy_detailed_returns_nested_constructor_events_in_order() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let spy_events_checker = deploy_contract(&mut state, "SpyEventsChecker", &[]);
+    let contract_name = felt_from_short_string("ConstructorNestedEventEmitter");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    let deploy_result = state
+        .deploy_detailed(
+            &class_hash,
+            &[
+                Felt252::from(123),
+                contract_address_to_felt(spy_events_checker),
+            ],
+        )
+        .unwrap();
+
+    assert_eq!(
+        deploy_result.events,
+        vec![
+            Event {
+                from: deploy_result.contract_address,
+                keys: vec![felt_selector_from_name("Initialized")],
+                data: vec![Felt252::from(123)],
+            },
+            Event {
+                from: spy_events_checker,
+                keys: vec![felt_selector_from_name("FirstEvent")],
+                data: vec![Felt252::from(124)],
+            },
+            Event {
+                from: deploy_result.contract_address,
+                keys: vec![felt_selector_from_name("Finished")],
+                data: vec![Felt252::from(125)],
+            },
+        ]
+    );
+}
+
+#[test]
+fn deploy_class_in_single_call() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_class: ContractClass =
+        serde_json::from_str(&contracts.get("HelloStarknet").unwrap().sierra).unwrap();
+    let (class_hash, contract_address) = state.deploy_class(contract_class, &[]).unwrap();
+
+    assert_eq!(state.get_class_hash(contract_address).unwrap(), class_hash);
+
+    let selector = felt_selector_from_name("get_balance");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(0)]);
+}
+
+#[test]
+fn deploy_class_reuses_declared_class() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("HelloStarknet");
+    let declared_class_hash = state.declare(&contract_name, &contracts).unwrap();
+
+    let contract_class: ContractClass =
+        serde_json::from_str(&contracts.get("HelloStarknet").unwrap().sierra).unwrap();
+    let (class_hash, _) = state.deploy_class(contract_class, &[]).unwrap();
+
+    assert_eq!(class_hash, declared_class_hash);
+}
+
+#[test]
+fn deploy_caller_is_test_account() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "ConstructorPrankChecker", &[]);
+
+    let selector = felt_selector_from_name("get_stored_caller_address");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(
+        output,
+        vec![contract_address_to_felt(state.test_account_address())]
+    );
+}
+
+#[test]
+fn deploy_caller_is_custom_test_account() {
+    let mut state =
+        create_cheatnet_state().with_test_account_address(ContractAddress::from(123_u128));
+    assert_eq!(
+        state.test_account_address(),
+        ContractAddress::from(123_u128)
+    );
+
+    let contracts = get_contracts();
+    let contract_name = felt_from_short_string("ConstructorPrankChecker");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    let precalculated_address = state.precalculate_address(&class_hash, &[]);
+    let contract_address = state.deploy(&class_hash, &[]).unwrap();
+    assert_eq!(contract_address, precalculated_address);
+
+    let selector = felt_selector_from_name("get_stored_caller_address");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(123)]);
+}
+
+#[test]
+fn failed_deploy_leaves_no_state_changes() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("ERC20");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+
+    // The constructor writes the name before panicking on the zero recipient
+    let calldata = [1, 2, 18, 1000, 0, 0].map(Felt252::from);
+    let precalculated_address = state.precalculate_address(&class_hash, &calldata);
+    let account_address = state.test_account_address();
+    let nonce = state.get_nonce(account_address).unwrap();
+    let result = state.deploy(&class_hash, &calldata);
+
+    assert!(matches!(
+        result,
+        Err(CheatcodeError::Recoverable(
+            CheatcodeFailure::ContractPanic(_)
+        ))
+    ));
+    assert!(matches!(
+        state.get_class_hash(precalculated_address),
+        Err(CheatcodeError::Recoverable(CheatcodeFailure::ContractNotDeployed(address)))
+            if address == precalculated_address
+    ));
+    assert_eq!(
+        state
+            .load(precalculated_address, &map_entry_address("name", &[]))
+            .unwrap(),
+        Felt252::from(0)
+    );
+    assert_eq!(state.get_nonce(account_address).unwrap(), nonce);
+
+    let calldata = [1, 2, 18, 1000, 0, 123].map(Felt252::from);
+    let contract_address = state
+        .deploy_at(&class_hash, &calldata, precalculated_address)
+        .unwrap();
+
+    assert_eq!(state.get_class_hash(contract_address).unwrap(), class_hash);
+}
+
+#[test]
+fn deploy_undeclared_class() {
+    let mut state = create_cheatnet_state();
+    let class_hash = ClassHash(StarkFelt::from(0x1234_u32));
+
+    let result = state.deploy(&class_hash, &[]);
+
+    assert!(matches!(
+        result,
+        Err(CheatcodeError::Recoverable(failure @ CheatcodeFailure::ClassNotDeclared(_)))
+            if PanicData::from_felts(failure.panic_data()).message()
+                == Some("Class hash 0x1234 not declared")
+    ));
+}
+
+#[test]
+fn deploy_many_in_order() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let hello_starknet = state
+        .declare(&felt_from_short_string("HelloStarknet"), &contracts)
+        .unwrap();
+    let prank_checker = state
+        .declare(&felt_from_short_string("PrankChecker"), &contracts)
+        .unwrap();
+
+    let addresses = state
+        .deploy_many(&[
+            (hello_starknet, vec![]),
+            (prank_checker, vec![]),
+            (hello_starknet, vec![]),
+        ])
+        .unwrap();
+
+    assert_eq!(addresses.len(), 3);
+    assert_ne!(addresses[0], addresses[1]);
+    assert_ne!(addresses[1], addresses[2]);
+    assert_ne!(addresses[0], addresses[2]);
+    assert_eq!(state.get_class_hash(addresses[1]).unwrap(), prank_checker);
+}
+
+#[test]
+fn deploy_many_reports_failing_index() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let hello_starknet = state
+        .declare(&felt_from_short_string("HelloStarknet"), &contracts)
+        .unwrap();
+    let erc20 = state
+        .declare(&felt_from_short_string("ERC20"), &contracts)
+        .unwrap();
+
+    let result = state.deploy_many(&[
+        (hello_starknet, vec![]),
+        (erc20, vec![Felt252::from(1)]),
+        (hello_starknet, vec![]),
+    ]);
+
+    let Err(CheatcodeError::Recoverable(failure)) = result else {
+        panic!("Expected deploy_many to fail recoverably");
+    };
+    assert!(matches!(
+        &failure,
+        CheatcodeFailure::DeployFailed { index: 1, failure }
+            if **failure == CheatcodeFailure::InvalidConstructorCalldata { expected: 6, actual: 1 }
+    ));
+    assert_eq!(
+        failure.panic_data(),
+        vec![
+            felt_from_short_string("Deployment 1 failed"),
+            felt_from_short_string("Invalid constructor calldata"),
+            felt_from_short_string("expected 6, got 1"),
+        ]
+    );
+}
+
+#[test]
+fn deploy_detailed_returns_inner_calls_of_factory() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let child_class_hash = state
+        .declare(
+            &felt_from_short_string("ConstructorEventEmitter"),
+            &contracts,
+        )
+        .unwrap();
+    let factory_class_hash = state
+        .declare(&felt_from_short_string("Factory"), &contracts)
+        .unwrap();
+    let deploy_result = state
+        .deploy_detailed(&factory_class_hash, &[class_hash_to_felt(child_class_hash)])
+        .unwrap();
+
+    let mut children = vec![];
+    for getter in ["get_first_child", "get_second_child"] {
+        let output = call_contract(
+            &deploy_result.contract_address,
+            &felt_selector_from_name(getter),
+            &[],
+            &mut state,
+        )
+        .unwrap();
+        children.push(contract_address_from_felt(&recover_data(output)[0]).unwrap());
+    }
+
+    assert_eq!(
+        deploy_result.inner_calls,
+        children
+            .iter()
+            .map(|child| InnerCall {
+                contract_address: *child,
+                selector: felt_selector_from_name("constructor"),
+                ret_data: vec![],
+            })
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn deployed_contracts_lists_children_of_factory() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let child_class_hash = state
+        .declare(
+            &felt_from_short_string("ConstructorEventEmitter"),
+            &contracts,
+        )
+        .unwrap();
+    let factory_class_hash = state
+        .declare(&felt_from_short_string("Factory"), &contracts)
+        .unwrap();
+    let deploy_result = state
+        .deploy_detailed(&factory_class_hash, &[class_hash_to_felt(child_class_hash)])
+        .unwrap();
+    let hello_starknet_class_hash = state
+        .declare(&felt_from_short_string("HelloStarknet"), &contracts)
+        .unwrap();
+    let hello_starknet_address = state.deploy(&hello_starknet_class_hash, &[]).unwrap();
+
+    let deployed_contracts = state.deployed_contracts();
+
+    let mut expected = vec![
+        (deploy_result.contract_address, factory_class_hash),
+        (hello_starknet_address, hello_starknet_class_hash),
+    ];
+    expected.extend(
+        deploy_result
+            .inner_calls
+            .iter()
+            .map(|inner_call| (inner_call.contract_address, child_class_hash)),
+    );
+    for contract in expected {
+        assert!(
+            deployed_contracts.contains(&contract),
+            "{contract:?} is missing in {deployed_contracts:?}"
+        );
+    }
+    assert!(deployed_contracts
+        .windows(2)
+        .all(|pair| pair[0].0 < pair[1].0));
+}
+
+#[test]
+fn deploy_skip_constructor_has_no_constructor_side_effects() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let spy = state.spy_events();
+    let emitter_class_hash = state
+        .declare(
+            &felt_from_short_string("ConstructorEventEmitter"),
+            &contracts,
+        )
+        .unwrap();
+    state
+        .deploy_skip_constructor(&emitter_class_hash, ContractAddress::from(1_u8))
+        .unwrap();
+    assert_eq!(spy.count(), 0);
+
+    let checker_class_hash = state
+        .declare(&felt_from_short_string("MockChecker"), &contracts)
+        .unwrap();
+    let contract_address = state
+        .deploy_skip_constructor(&checker_class_hash, ContractAddress::from(2_u8))
+        .unwrap();
+
+    // Storage written by the constructor stays empty, while the contract can be called
+    let selector = felt_selector_from_name("get_thing");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(0)]);
+
+    let result = state.deploy_skip_constructor(&checker_class_hash, contract_address);
+    assert!(matches!(
+        result,
+        Err(CheatcodeError::Recoverable(
+            CheatcodeFailure::AddressOccupied
+        ))
+    ));
+}
@@ -0,0 +1,97 @@
+use crate::{
+    assert_success,
+    common::{deploy_contract, get_contracts, recover_data, state::create_cheatnet_state},
+};
+use cairo_felt::Felt252;
+use cheatnet::{
+    conversions::{felt_from_short_string, felt_selector_from_name},
+    rpc::call_contract,
+};
+use starknet_api::core::ContractAddress;
+
+#[test]
+fn elect_simple() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "ElectChecker", vec![].as_slice());
+
+    state.start_elect(contract_address, ContractAddress::from(123_u128));
+
+    let selector = felt_selector_from_name("get_sequencer_address");
+
+    let output =
+        call_contract(&contract_address, &selector, vec![].as_slice(), &mut state).unwrap();
+
+    assert_success!(output, vec![Felt252::from(123)]);
+}
+
+#[test]
+fn elect_in_constructor() {
+    let mut state = create_cheatnet_state();
+
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("ConstructorElectChecker");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    let precalculated_address = state.precalculate_address(&class_hash, vec![].as_slice());
+
+    state.start_elect(precalculated_address, ContractAddress::from(123_u128));
+
+    let contract_address = state.deploy(&class_hash, vec![].as_slice()).unwrap();
+
+    assert_eq!(precalculated_address, contract_address);
+
+    let selector = felt_selector_from_name("get_stored_sequencer_address");
+
+    let output =
+        call_contract(&contract_address, &selector, vec![].as_slice(), &mut state).unwrap();
+
+    assert_success!(output, vec![Felt252::from(123)]);
+}
+
+#[test]
+fn elect_stop() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "ElectChecker", vec![].as_slice());
+
+    let selector = felt_selector_from_name("get_sequencer_address");
+
+    let output =
+        call_contract(&contract_address, &selector, vec![].as_slice(), &mut state).unwrap();
+
+    let old_sequencer_address = recover_data(output);
+
+    state.start_elect(contract_address, ContractAddress::from(123_u128));
+
+    let output =
+        call_contract(&contract_address, &selector, vec![].as_slice(), &mut state).unwrap();
+
+    let new_sequencer_address = recover_data(output);
+    assert_eq!(new_sequencer_address, vec![Felt252::from(123)]);
+    assert_ne!(old_sequencer_address, new_sequencer_address);
+
+    state.stop_elect(contract_address);
+
+    let output =
+        call_contract(&contract_address, &selector, vec![].as_slice(), &mut state).unwrap();
+    let changed_back_sequencer_address = recover_data(output);
+
+    assert_eq!(old_sequencer_address, changed_back_sequencer_address);
+}
+
+#[test]
+fn elect_does_not_affect_other_contracts() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "ElectChecker", vec![].as_slice());
+    let other_address = deploy_contract(&mut state, "ElectChecker", vec![].as_slice());
+
+    state.start_elect(contract_address, ContractAddress::from(123_u128));
+
+    let selector = felt_selector_from_name("get_sequencer_address");
+
+    let output = call_contract(&other_address, &selector, vec![].as_slice(), &mut state).unwrap();
+
+    assert_ne!(recover_data(output), vec![Felt252::from(123)]);
+}
@@ -0,0 +1,55 @@
+use crate::common::{deploy_contract, state::create_cheatnet_state};
+use cairo_felt::Felt252;
+use cheatnet::{
+    cheatcodes::{erc20_transfer::TransferBalances, CheatcodeError, CheatcodeFailure},
+    conversions::contract_address_to_felt,
+};
+use starknet_api::core::ContractAddress;
+
+#[test]
+fn erc20_transfer_moves_tokens() {
+    let mut state = create_cheatnet_state();
+    let from = ContractAddress::from(123_u8);
+    let to = ContractAddress::from(456_u8);
+    let mut calldata = [1, 2, 18, 1000, 0].map(Felt252::from).to_vec();
+    calldata.push(contract_address_to_felt(from));
+    let token = deploy_contract(&mut state, "ERC20", &calldata);
+
+    let balances = state.erc20_transfer(token, from, to, 300, 0).unwrap();
+
+    assert_eq!(
+        balances,
+        TransferBalances {
+            from: (700, 0),
+            to: (300, 0),
+        }
+    );
+}
+
+#[test]
+fn erc20_transfer_insufficient_balance() {
+    let mut state = create_cheatnet_state();
+    let from = ContractAddress::from(123_u8);
+    let to = ContractAddress::from(456_u8);
+    let mut calldata = [1, 2, 18, 1000, 0].map(Felt252::from).to_vec();
+    calldata.push(contract_address_to_felt(from));
+    let token = deploy_contract(&mut state, "ERC20", &calldata);
+
+    let result = state.erc20_transfer(token, from, to, 1001, 0);
+    assert!(matches!(
+        result,
+        Err(CheatcodeError::Recoverable(
+            CheatcodeFailure::ContractPanic(_)
+        ))
+    ));
+
+    // Balances are left untouched by the failed transfer
+    let balances = state.erc20_transfer(token, from, to, 0, 0).unwrap();
+    assert_eq!(
+        balances,
+        TransferBalances {
+            from: (1000, 0),
+            to: (0, 0),
+        }
+    );
+}
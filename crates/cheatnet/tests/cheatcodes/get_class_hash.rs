@@ -0,0 +1,29 @@
+use crate::common::{get_contracts, state::create_cheatnet_state};
+use cheatnet::{cheatcodes::CheatcodeError, conversions::felt_from_short_string};
+use starknet_api::core::ContractAddress;
+
+#[test]
+fn get_class_hash_simple() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let class_hash = state
+        .declare(&felt_from_short_string("HelloStarknet"), &contracts)
+        .unwrap();
+    let contract_address = state.deploy(&class_hash, &[]).unwrap();
+
+    assert_eq!(state.get_class_hash(contract_address).unwrap(), class_hash);
+}
+
+#[test]
+fn get_class_hash_undeployed() {
+    let mut state = create_cheatnet_state();
+
+    let result = state.get_class_hash(ContractAddress::from(123_u128));
+
+    assert!(match result {
+        Err(CheatcodeError::Recoverable(failure)) =>
+            failure.panic_data() == vec![felt_from_short_string("Contract not deployed")],
+        _ => false,
+    });
+}
@@ -0,0 +1,502 @@
+use crate::{
+    assert_success,
+    common::{deploy_contract, get_contracts, state::create_cheatnet_state},
+};
+use cairo_felt::Felt252;
+use cairo_lang_starknet::contract_class::ContractClass;
+use camino::Utf8PathBuf;
+use cheatnet::{
+    cheatcodes::{invoke::Call, spoof::TxInfoMock, CheatcodeError, CheatcodeFailure},
+    constants::{INITIAL_BALANCE, MAX_FEE},
+    conversions::{
+        contract_address_to_felt, felt_from_short_string, felt_selector_from_name,
+        map_entry_address, u256_to_felts, CalldataBuilder,
+    },
+    rpc::call_contract,
+    CheatnetState,
+};
+use starknet_api::core::ContractAddress;
+
+#[test]
+fn invoke_simple() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+
+    let selector = felt_selector_from_name("increase_balance");
+    let invoke_result = state
+        .invoke(&contract_address, &selector, &[Felt252::from(5)])
+        .unwrap();
+    assert_eq!(invoke_result.ret_data, vec![]);
+
+    let selector = felt_selector_from_name("get_balance");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(5)]);
+}
+
+#[test]
+fn invoke_returns_data() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+
+    let selector = felt_selector_from_name("increase_balance");
+    state
+        .invoke(&contract_address, &selector, &[Felt252::from(5)])
+        .unwrap();
+
+    let selector = felt_selector_from_name("get_balance");
+    let invoke_result = state.invoke(&contract_address, &selector, &[]).unwrap();
+    assert_eq!(invoke_result.ret_data, vec![Felt252::from(5)]);
+}
+
+#[test]
+fn invoke_with_u256_calldata() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "U256Adder", &[]);
+    let selector = felt_selector_from_name("add");
+
+    let cases = [
+        ((1, 2), (3, 4), (4, 6)),
+        // The low parts overflow, so 1 is carried to the high part
+        ((u128::MAX, 0), (1, 0), (0, 1)),
+        ((5, 7), (u128::MAX, 1), (4, 9)),
+    ];
+    for ((a_low, a_high), (b_low, b_high), (sum_low, sum_high)) in cases {
+        let calldata = [u256_to_felts(a_low, a_high), u256_to_felts(b_low, b_high)].concat();
+        let invoke_result = state
+            .invoke(&contract_address, &selector, &calldata)
+            .unwrap();
+        assert_eq!(
+            invoke_result.ret_data,
+            u256_to_felts(sum_low, sum_high).to_vec()
+        );
+    }
+}
+
+#[test]
+fn invoke_with_enum_calldata() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "ShapeMatcher", &[]);
+    let selector = felt_selector_from_name("describe");
+
+    let cases: [(usize, &[Felt252], u8); 3] = [
+        (0, &[], 0),
+        (1, &[Felt252::from(7)], 7),
+        (2, &[Felt252::from(3), Felt252::from(4)], 7),
+    ];
+    for (variant_index, payload, payload_sum) in cases {
+        let calldata = CalldataBuilder::new()
+            .add_enum(variant_index, payload)
+            .build();
+        let invoke_result = state
+            .invoke(&contract_address, &selector, &calldata)
+            .unwrap();
+        assert_eq!(
+            invoke_result.ret_data,
+            vec![Felt252::from(variant_index), Felt252::from(payload_sum)]
+        );
+    }
+}
+
+#[test]
+fn invoke_used_resources_are_stable() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    let selector = felt_selector_from_name("increase_balance");
+
+    let first_resources = state
+        .invoke(&contract_address, &selector, &[Felt252::from(5)])
+        .unwrap()
+        .used_resources;
+    let second_resources = state
+        .invoke(&contract_address, &selector, &[Felt252::from(5)])
+        .unwrap()
+        .used_resources;
+
+    assert!(first_resources.n_steps > 0);
+    assert!(first_resources.l1_gas > 0);
+    assert!(first_resources
+        .builtin_instance_counter
+        .contains_key("range_check"));
+    assert_eq!(first_resources.n_steps, second_resources.n_steps);
+    assert_eq!(
+        first_resources.builtin_instance_counter,
+        second_resources.builtin_instance_counter
+    );
+}
+
+#[test]
+fn invoke_panicking() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+
+    let selector = felt_selector_from_name("increase_balance");
+    let result = state.invoke(&contract_address, &selector, &[Felt252::from(0)]);
+
+    assert!(matches!(
+        result,
+        Err(CheatcodeError::Recoverable(failure))
+            if failure.panic_data() == vec![felt_from_short_string("Amount cannot be 0")]
+    ));
+}
+
+#[test]
+fn invoke_fee_scales_with_gas_price() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    let selector = felt_selector_from_name("increase_balance");
+
+    state.set_charge_fee(true);
+    state.set_gas_price(100);
+    let first_result = state
+        .invoke(&contract_address, &selector, &[Felt252::from(5)])
+        .unwrap();
+
+    state.set_gas_price(200);
+    let second_result = state
+        .invoke(&contract_address, &selector, &[Felt252::from(5)])
+        .unwrap();
+
+    assert_eq!(first_result.used_resources, second_result.used_resources);
+    assert!(first_result.actual_fee > 0);
+    assert_eq!(second_result.actual_fee, 2 * first_result.actual_fee);
+}
+
+#[test]
+fn invoke_with_max_fee() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    let call = Call {
+        to: contract_address,
+        selector: felt_selector_from_name("increase_balance"),
+        calldata: vec![Felt252::from(5)],
+    };
+
+    state.set_charge_fee(true);
+    let actual_fee = state
+        .invoke_with_max_fee(&call, MAX_FEE)
+        .unwrap()
+        .actual_fee;
+
+    let result = state.invoke_with_max_fee(&call, 1);
+    assert!(matches!(
+        result,
+        Err(CheatcodeError::Recoverable(
+            CheatcodeFailure::TransactionRejected(_)
+        ))
+    ));
+
+    // The fee of the same call doesn't change, so exactly the fee paid before is sufficient
+    let invoke_result = state.invoke_with_max_fee(&call, actual_fee).unwrap();
+    assert_eq!(invoke_result.actual_fee, actual_fee);
+}
+
+#[test]
+fn invoke_with_zero_gas_price() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    let selector = felt_selector_from_name("increase_balance");
+
+    state.set_gas_price(0);
+    let invoke_result = state
+        .invoke(&contract_address, &selector, &[Felt252::from(5)])
+        .unwrap();
+
+    assert_eq!(invoke_result.actual_fee, 0);
+}
+
+fn fee_token_address() -> ContractAddress {
+    ContractAddress::from(0x1001_u128)
+}
+
+// Deploys an account used as the test account, funded so it can pay for its transactions
+fn create_cheatnet_state_with_account(account_name: &str, calldata: &[Felt252]) -> CheatnetState {
+    let mut state = create_cheatnet_state();
+    let account_class = state
+        .declare(&felt_from_short_string(account_name), &get_contracts())
+        .unwrap();
+    let account_address = state
+        .deploy_and_fund_account(
+            &account_class,
+            Felt252::from(0),
+            calldata,
+            fee_token_address(),
+            INITIAL_BALANCE,
+            0,
+        )
+        .unwrap();
+
+    state.with_test_account_address(account_address)
+}
+
+#[test]
+fn invoke_multi_applies_all_calls() {
+    let mut state = create_cheatnet_state_with_account("MulticallAccount", &[]);
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+
+    let selector = felt_selector_from_name("increase_balance");
+    let ret_data = state
+        .invoke_multi(&[
+            Call {
+                to: contract_address,
+                selector: selector.clone(),
+                calldata: vec![Felt252::from(10)],
+            },
+            Call {
+                to: contract_address,
+                selector,
+                calldata: vec![Felt252::from(20)],
+            },
+        ])
+        .unwrap();
+    assert_eq!(ret_data, vec![vec![], vec![]]);
+
+    let selector = felt_selector_from_name("get_balance");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(30)]);
+}
+
+#[test]
+fn invoke_multi_is_atomic() {
+    let mut state = create_cheatnet_state_with_account("MulticallAccount", &[]);
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+
+    let selector = felt_selector_from_name("increase_balance");
+    let result = state.invoke_multi(&[
+        Call {
+            to: contract_address,
+            selector: selector.clone(),
+            calldata: vec![Felt252::from(10)],
+        },
+        Call {
+            to: contract_address,
+            selector,
+            calldata: vec![Felt252::from(0)],
+        },
+    ]);
+    assert!(matches!(result, Err(CheatcodeError::Recoverable(_))));
+
+    let selector = felt_selector_from_name("get_balance");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(0)]);
+}
+
+#[test]
+fn invoke_signed_passes_signature_to_validate() {
+    let signer = Felt252::from(1234);
+    let mut state =
+        create_cheatnet_state_with_account("SignatureCheckingAccount", &[signer.clone()]);
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+
+    let call = Call {
+        to: contract_address,
+        selector: felt_selector_from_name("increase_balance"),
+        calldata: vec![Felt252::from(5)],
+    };
+
+    let result = state.invoke_signed(&call, vec![]);
+    assert!(matches!(
+        result,
+        Err(CheatcodeError::Recoverable(CheatcodeFailure::ContractPanic(panic_data)))
+            if panic_data == vec![felt_from_short_string("Invalid signature length")]
+    ));
+
+    let result = state.invoke_signed(&call, vec![Felt252::from(1)]);
+    assert!(matches!(
+        result,
+        Err(CheatcodeError::Recoverable(CheatcodeFailure::ContractPanic(panic_data)))
+            if panic_data == vec![felt_from_short_string("Invalid signature")]
+    ));
+
+    let invoke_result = state.invoke_signed(&call, vec![signer]).unwrap();
+    assert_eq!(invoke_result.ret_data, vec![]);
+
+    let selector = felt_selector_from_name("get_balance");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(5)]);
+}
+
+#[test]
+fn invoke_through_predeployed_account() {
+    let contracts = get_contracts();
+    let account_class: ContractClass =
+        serde_json::from_str(&contracts.get("ValidatingAccount").unwrap().sierra).unwrap();
+    let account_address = ContractAddress::from(0x1234_u128);
+
+    let mut state = CheatnetState::new_with_predeployed_account(
+        &Utf8PathBuf::from("predeployed-contracts"),
+        None,
+        account_class,
+        account_address,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(state.test_account_address(), account_address);
+
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    let selector = felt_selector_from_name("increase_balance");
+    state
+        .invoke(&contract_address, &selector, &[Felt252::from(5)])
+        .unwrap();
+
+    let selector = felt_selector_from_name("get_balance");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(5)]);
+
+    let selector = felt_selector_from_name("get_validate_count");
+    let output = call_contract(&account_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(1)]);
+}
+
+#[test]
+fn invoke_through_funded_account_pays_fee() {
+    let mut state = create_cheatnet_state();
+    let account_class = state
+        .declare(
+            &felt_from_short_string("ValidatingAccount"),
+            &get_contracts(),
+        )
+        .unwrap();
+    let account_address = state
+        .deploy_and_fund_account(
+            &account_class,
+            Felt252::from(123),
+            &[],
+            fee_token_address(),
+            INITIAL_BALANCE,
+            0,
+        )
+        .unwrap();
+    let balance_address = map_entry_address(
+        "ERC20_balances",
+        &[contract_address_to_felt(account_address)],
+    );
+    assert_eq!(
+        state.load(fee_token_address(), &balance_address).unwrap(),
+        Felt252::from(INITIAL_BALANCE)
+    );
+
+    let mut state = state.with_test_account_address(account_address);
+    state.set_charge_fee(true);
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    state
+        .invoke(
+            &contract_address,
+            &felt_selector_from_name("increase_balance"),
+            &[Felt252::from(5)],
+        )
+        .unwrap();
+
+    let selector = felt_selector_from_name("get_validate_count");
+    let output = call_contract(&account_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(1)]);
+
+    // The fee of the transaction was charged from the account
+    let balance = state.load(fee_token_address(), &balance_address).unwrap();
+    assert!(balance < Felt252::from(INITIAL_BALANCE));
+}
+
+#[test]
+fn invoke_from_unfunded_account_with_charge_fee() {
+    let mut state = create_cheatnet_state();
+    let account_class = state
+        .declare(
+            &felt_from_short_string("ValidatingAccount"),
+            &get_contracts(),
+        )
+        .unwrap();
+    let account_address = state.deploy(&account_class, &[]).unwrap();
+    let mut state = state.with_test_account_address(account_address);
+
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    let selector = felt_selector_from_name("increase_balance");
+
+    // Fees are not charged by default, so the account doesn't need any fee tokens
+    state
+        .invoke(&contract_address, &selector, &[Felt252::from(5)])
+        .unwrap();
+
+    state.set_charge_fee(true);
+    let result = state.invoke(&contract_address, &selector, &[Felt252::from(5)]);
+    assert!(matches!(
+        result,
+        Err(CheatcodeError::Recoverable(
+            CheatcodeFailure::TransactionRejected(_)
+        ))
+    ));
+
+    let selector = felt_selector_from_name("get_balance");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(5)]);
+}
+
+#[test]
+fn validate_only_reports_rejected_signature() {
+    let signer = Felt252::from(1234);
+    let mut state =
+        create_cheatnet_state_with_account("SignatureCheckingAccount", &[signer.clone()]);
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+
+    let call = Call {
+        to: contract_address,
+        selector: felt_selector_from_name("increase_balance"),
+        calldata: vec![Felt252::from(5)],
+    };
+
+    let result = state.validate_only(&call, vec![Felt252::from(1)]);
+    assert!(matches!(
+        result,
+        Err(CheatcodeError::Recoverable(failure))
+            if failure.panic_data() == vec![felt_from_short_string("Invalid signature")]
+    ));
+
+    let ret_data = state.validate_only(&call, vec![signer]).unwrap();
+    assert_eq!(ret_data, vec![felt_from_short_string("VALID")]);
+
+    // Nothing was executed
+    let selector = felt_selector_from_name("get_balance");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(0)]);
+}
+
+#[test]
+fn validate_only_keeps_spoofed_tx_info() {
+    let mut state = create_cheatnet_state_with_account("TxInfoValidatingAccount", &[]);
+    let account_address = state.test_account_address();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+
+    let call = Call {
+        to: contract_address,
+        selector: felt_selector_from_name("increase_balance"),
+        calldata: vec![Felt252::from(5)],
+    };
+    state.start_spoof(
+        account_address,
+        TxInfoMock {
+            transaction_hash: Some(Felt252::from(123)),
+            signature: Some(vec![]),
+            ..Default::default()
+        },
+    );
+
+    let ret_data = state.validate_only(&call, vec![Felt252::from(1)]).unwrap();
+    assert_eq!(ret_data, vec![Felt252::from(123)]);
+}
+
+#[test]
+fn execute_only_skips_validation() {
+    let mut state =
+        create_cheatnet_state_with_account("SignatureCheckingAccount", &[Felt252::from(1234)]);
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+
+    let call = Call {
+        to: contract_address,
+        selector: felt_selector_from_name("increase_balance"),
+        calldata: vec![Felt252::from(5)],
+    };
+    let invoke_result = state.execute_only(&call).unwrap();
+    assert_eq!(invoke_result.ret_data, vec![]);
+
+    let selector = felt_selector_from_name("get_balance");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(5)]);
+}
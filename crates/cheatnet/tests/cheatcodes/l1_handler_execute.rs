@@ -0,0 +1,52 @@
+use crate::{
+    assert_success,
+    common::{deploy_contract, state::create_cheatnet_state},
+};
+use cairo_felt::Felt252;
+use cheatnet::{
+    cheatcodes::CheatcodeError,
+    conversions::{felt_from_short_string, felt_selector_from_name},
+    rpc::call_contract,
+};
+
+#[test]
+fn l1_handler_execute_simple() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "L1HandlerExecutor", &[]);
+
+    state
+        .l1_handler_execute(
+            contract_address,
+            &felt_selector_from_name("process_l1_message"),
+            &Felt252::from(123),
+            &[Felt252::from(456)],
+        )
+        .unwrap();
+
+    let selector = felt_selector_from_name("get_l1_caller_address");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(123)]);
+
+    let selector = felt_selector_from_name("get_value");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(456)]);
+}
+
+#[test]
+fn l1_handler_execute_panicking() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "L1HandlerExecutor", &[]);
+
+    let result = state.l1_handler_execute(
+        contract_address,
+        &felt_selector_from_name("process_l1_message"),
+        &Felt252::from(123),
+        &[Felt252::from(0)],
+    );
+
+    assert!(match result {
+        Err(CheatcodeError::Recoverable(failure)) =>
+            failure.panic_data() == vec![felt_from_short_string("Value cannot be 0")],
+        _ => false,
+    });
+}
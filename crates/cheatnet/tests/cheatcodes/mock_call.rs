@@ -0,0 +1,166 @@
+use crate::{
+    assert_success,
+    common::{deploy_contract, state::create_cheatnet_state},
+};
+use blockifier::abi::abi_utils::selector_from_name;
+use cairo_felt::Felt252;
+use cheatnet::{
+    conversions::{contract_address_to_felt, felt_selector_from_name},
+    rpc::call_contract,
+};
+use starknet_api::core::ContractAddress;
+use starknet_api::hash::StarkFelt;
+
+#[test]
+fn mock_call_simple() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(
+        &mut state,
+        "MockChecker",
+        vec![Felt252::from(420)].as_slice(),
+    );
+
+    state.start_mock_call(
+        contract_address,
+        selector_from_name("get_thing"),
+        vec![StarkFelt::from(123_u32)],
+    );
+
+    let selector = felt_selector_from_name("get_thing");
+    let output =
+        call_contract(&contract_address, &selector, vec![].as_slice(), &mut state).unwrap();
+
+    assert_success!(output, vec![Felt252::from(123)]);
+}
+
+#[test]
+fn mock_call_stop() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(
+        &mut state,
+        "MockChecker",
+        vec![Felt252::from(420)].as_slice(),
+    );
+
+    state.start_mock_call(
+        contract_address,
+        selector_from_name("get_thing"),
+        vec![StarkFelt::from(123_u32)],
+    );
+    state.stop_mock_call(contract_address, selector_from_name("get_thing"));
+
+    let selector = felt_selector_from_name("get_thing");
+    let output =
+        call_contract(&contract_address, &selector, vec![].as_slice(), &mut state).unwrap();
+
+    assert_success!(output, vec![Felt252::from(420)]);
+}
+
+#[test]
+fn mock_call_proxy() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(
+        &mut state,
+        "MockChecker",
+        vec![Felt252::from(420)].as_slice(),
+    );
+    let proxy_address = deploy_contract(&mut state, "MockCheckerProxy", vec![].as_slice());
+
+    state.start_mock_call(
+        contract_address,
+        selector_from_name("get_thing"),
+        vec![StarkFelt::from(123_u32)],
+    );
+
+    let proxy_selector = felt_selector_from_name("get_thing_from_contract");
+    let output = call_contract(
+        &proxy_address,
+        &proxy_selector,
+        vec![contract_address_to_felt(contract_address)].as_slice(),
+        &mut state,
+    )
+    .unwrap();
+
+    assert_success!(output, vec![Felt252::from(123)]);
+}
+
+#[test]
+fn mock_call_not_deployed_contract() {
+    let mut state = create_cheatnet_state();
+
+    let proxy_address = deploy_contract(&mut state, "MockCheckerProxy", vec![].as_slice());
+    let not_deployed_address = ContractAddress::from(1234_u128);
+
+    state.start_mock_call(
+        not_deployed_address,
+        selector_from_name("get_thing"),
+        vec![StarkFelt::from(123_u32)],
+    );
+
+    let proxy_selector = felt_selector_from_name("get_thing_from_contract");
+    let output = call_contract(
+        &proxy_address,
+        &proxy_selector,
+        vec![contract_address_to_felt(not_deployed_address)].as_slice(),
+        &mut state,
+    )
+    .unwrap();
+
+    assert_success!(output, vec![Felt252::from(123)]);
+}
+
+#[test]
+fn mock_call_with_matcher() {
+    let mut state = create_cheatnet_state();
+
+    // name, symbol, decimals, initial_supply: u256, recipient
+    let calldata = [1, 2, 18, 1000, 0, 123].map(Felt252::from);
+    let contract_address = deploy_contract(&mut state, "ERC20", &calldata);
+
+    state.start_mock_call_with_matcher(
+        contract_address,
+        selector_from_name("balance_of"),
+        |calldata| match calldata {
+            [account] if *account == Felt252::from(1) => {
+                Some(vec![Felt252::from(100), Felt252::from(0)])
+            }
+            [account] if *account == Felt252::from(2) => {
+                Some(vec![Felt252::from(200), Felt252::from(0)])
+            }
+            _ => None,
+        },
+    );
+
+    let selector = felt_selector_from_name("balance_of");
+
+    let output = call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(1)],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![Felt252::from(100), Felt252::from(0)]);
+
+    let output = call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(2)],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![Felt252::from(200), Felt252::from(0)]);
+
+    // Not matched, executed by the contract
+    let output = call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(123)],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![Felt252::from(1000), Felt252::from(0)]);
+}
@@ -1,3 +1,28 @@
+mod active_cheats;
+mod call;
+mod cheat_block_hash;
+mod cheat_execution_info;
+mod cheat_span;
+mod declare;
+mod deploy;
+mod elect;
+mod erc20_transfer;
+mod get_class_hash;
+mod invoke;
+mod l1_handler_execute;
+mod mock_call;
+mod nonce;
+mod panic_trace;
 mod prank;
+mod precalculate_address;
+mod reentrancy;
+mod replace_class;
+mod reset;
 mod roll;
+mod snapshot;
+mod spoof;
+mod spy_events;
+mod spy_messages_to_l1;
+mod storage;
+mod unknown_selector;
 mod warp;
@@ -0,0 +1,86 @@
+use crate::common::{get_contracts, state::create_cheatnet_state};
+use cairo_felt::Felt252;
+use cheatnet::constants::TEST_ACCOUNT_CONTRACT_ADDRESS;
+use cheatnet::conversions::felt_from_short_string;
+use starknet_api::core::{ContractAddress, PatriciaKey};
+use starknet_api::hash::StarkHash;
+use starknet_api::patricia_key;
+
+#[test]
+fn get_nonce_default() {
+    let mut state = create_cheatnet_state();
+
+    let nonce = state.get_nonce(ContractAddress::from(123_u128)).unwrap();
+
+    assert_eq!(nonce, Felt252::from(0));
+}
+
+#[test]
+fn set_and_get_nonce() {
+    let mut state = create_cheatnet_state();
+    let contract_address = ContractAddress::from(123_u128);
+
+    state.set_nonce(contract_address, &Felt252::from(10));
+    assert_eq!(
+        state.get_nonce(contract_address).unwrap(),
+        Felt252::from(10)
+    );
+
+    state.set_nonce(contract_address, &Felt252::from(3));
+    assert_eq!(state.get_nonce(contract_address).unwrap(), Felt252::from(3));
+}
+
+#[test]
+fn get_nonce_after_deploy() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+    let account_address = ContractAddress(patricia_key!(TEST_ACCOUNT_CONTRACT_ADDRESS));
+
+    let class_hash = state
+        .declare(&felt_from_short_string("HelloStarknet"), &contracts)
+        .unwrap();
+    let nonce_before = state.get_nonce(account_address).unwrap();
+
+    state.deploy(&class_hash, &[]).unwrap();
+
+    assert_eq!(
+        state.get_nonce(account_address).unwrap(),
+        nonce_before + Felt252::from(1)
+    );
+}
+
+#[test]
+fn get_nonce_after_deploy_at() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+    let account_address = ContractAddress(patricia_key!(TEST_ACCOUNT_CONTRACT_ADDRESS));
+
+    let class_hash = state
+        .declare(&felt_from_short_string("HelloStarknet"), &contracts)
+        .unwrap();
+    let nonce_before = state.get_nonce(account_address).unwrap();
+
+    state
+        .deploy_at(&class_hash, &[], ContractAddress::from(1_u8))
+        .unwrap();
+
+    assert_eq!(
+        state.get_nonce(account_address).unwrap(),
+        nonce_before + Felt252::from(1)
+    );
+}
+
+#[test]
+fn set_nonce_keeps_state() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let class_hash = state
+        .declare(&felt_from_short_string("HelloStarknet"), &contracts)
+        .unwrap();
+    let contract_address = state.deploy(&class_hash, &[]).unwrap();
+
+    state.set_nonce(contract_address, &Felt252::from(5));
+
+    assert_eq!(state.get_class_hash(contract_address).unwrap(), class_hash);
+}
@@ -0,0 +1,151 @@
+use crate::{
+    assert_panic, assert_success,
+    common::{deploy_contract, state::create_cheatnet_state},
+};
+use blockifier::execution::execution_utils::stark_felt_to_felt;
+use cairo_felt::Felt252;
+use cheatnet::{
+    conversions::{contract_address_to_felt, felt_from_short_string, felt_selector_from_name},
+    panic_data::CallFrame,
+    rpc::call_contract,
+};
+use starknet_api::core::EntryPointSelector;
+use starknet_api::hash::StarkFelt;
+
+fn entry_point_selector(name: &str) -> EntryPointSelector {
+    EntryPointSelector(StarkFelt::new(felt_selector_from_name(name).to_be_bytes()).unwrap())
+}
+
+#[test]
+fn panic_trace_of_nested_call() {
+    let mut state = create_cheatnet_state();
+    let hello_starknet_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    let proxy_address = deploy_contract(&mut state, "HelloStarknetProxy", &[]);
+
+    let output = call_contract(
+        &proxy_address,
+        &felt_selector_from_name("increase_balance"),
+        &[
+            contract_address_to_felt(hello_starknet_address),
+            Felt252::from(0),
+        ],
+        &mut state,
+    )
+    .unwrap();
+    assert_panic!(output, vec![felt_from_short_string("Amount cannot be 0")]);
+
+    let panic_trace = state.last_panic_trace().unwrap();
+    assert_eq!(
+        panic_trace.panic_data,
+        vec![felt_from_short_string("Amount cannot be 0")]
+    );
+    assert_eq!(panic_trace.contract_address(), Some(hello_starknet_address));
+    assert_eq!(
+        panic_trace.frames,
+        vec![
+            CallFrame {
+                contract_address: proxy_address,
+                selector: entry_point_selector("increase_balance"),
+            },
+            CallFrame {
+                contract_address: hello_starknet_address,
+                selector: entry_point_selector("increase_balance"),
+            },
+        ]
+    );
+}
+
+#[test]
+fn panic_trace_cleared_by_successful_call() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    let selector = felt_selector_from_name("increase_balance");
+
+    call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(0)],
+        &mut state,
+    )
+    .unwrap();
+    assert!(state.last_panic_trace().is_some());
+
+    let output = call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(1)],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![]);
+    assert!(state.last_panic_trace().is_none());
+}
+
+#[test]
+fn last_revert_raw_of_nested_call() {
+    let mut state = create_cheatnet_state();
+    let hello_starknet_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    let proxy_address = deploy_contract(&mut state, "HelloStarknetProxy", &[]);
+    let selector = felt_selector_from_name("increase_balance");
+    assert!(state.last_revert_raw().is_none());
+
+    call_contract(
+        &proxy_address,
+        &selector,
+        &[
+            contract_address_to_felt(hello_starknet_address),
+            Felt252::from(0),
+        ],
+        &mut state,
+    )
+    .unwrap();
+    assert!(state
+        .last_revert_raw()
+        .unwrap()
+        .contains("Amount cannot be 0"));
+
+    call_contract(
+        &proxy_address,
+        &selector,
+        &[
+            contract_address_to_felt(hello_starknet_address),
+            Felt252::from(1),
+        ],
+        &mut state,
+    )
+    .unwrap();
+    assert!(state.last_revert_raw().is_none());
+}
+
+#[test]
+fn panic_trace_names_failing_function() {
+    let mut state = create_cheatnet_state();
+    let hello_starknet_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    let proxy_address = deploy_contract(&mut state, "HelloStarknetProxy", &[]);
+
+    call_contract(
+        &proxy_address,
+        &felt_selector_from_name("increase_balance"),
+        &[
+            contract_address_to_felt(hello_starknet_address),
+            Felt252::from(0),
+        ],
+        &mut state,
+    )
+    .unwrap();
+
+    let failing_frame = state.last_panic_trace().unwrap().frames.last().unwrap();
+    let failing_function = state.selector_to_name(&stark_felt_to_felt(failing_frame.selector.0));
+    assert_eq!(failing_function.as_deref(), Some("increase_balance"));
+
+    assert_eq!(
+        state
+            .selector_to_name(&felt_selector_from_name("get_balance"))
+            .as_deref(),
+        Some("get_balance")
+    );
+    assert_eq!(
+        state.selector_to_name(&felt_selector_from_name("missing")),
+        None
+    );
+}
@@ -9,6 +9,8 @@ use cheatnet::{
         felt_selector_from_name,
     },
     rpc::call_contract,
+    state::CheatSpan,
+    CheatnetState,
 };
 use starknet_api::core::ContractAddress;
 
@@ -45,7 +47,6 @@ fn prank_with_other_syscall() {
 }
 
 #[test]
-#[ignore = "TODO(#254)"]
 fn prank_in_constructor() {
     let mut state = create_cheatnet_state();
 
@@ -183,3 +184,128 @@ fn prank_library_call() {
 
     assert_success!(output, vec![Felt252::from(123)]);
 }
+
+#[test]
+fn prank_does_not_affect_called_contracts() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "PrankChecker", vec![].as_slice());
+    let proxy_address = deploy_contract(&mut state, "PrankCheckerProxy", vec![].as_slice());
+
+    state.start_prank(proxy_address, ContractAddress::from(123_u128));
+
+    let proxy_selector = felt_selector_from_name("get_prank_checkers_caller_address");
+    let output = call_contract(
+        &proxy_address,
+        &proxy_selector,
+        vec![contract_address_to_felt(contract_address)].as_slice(),
+        &mut state,
+    )
+    .unwrap();
+
+    assert_success!(output, vec![contract_address_to_felt(proxy_address)]);
+}
+
+// Calls the proxy (A) which calls the prank checker (B), returns the callers seen by A and B
+fn callers_seen_through_proxy(
+    state: &mut CheatnetState,
+    proxy_address: ContractAddress,
+    checker_address: ContractAddress,
+) -> Vec<Felt252> {
+    let output = call_contract(
+        &proxy_address,
+        &felt_selector_from_name("get_own_and_prank_checkers_caller_address"),
+        &[contract_address_to_felt(checker_address)],
+        state,
+    )
+    .unwrap();
+    recover_data(output)
+}
+
+#[test]
+fn prank_nested_calls_without_pranks() {
+    let mut state = create_cheatnet_state();
+    let checker_address = deploy_contract(&mut state, "PrankChecker", &[]);
+    let proxy_address = deploy_contract(&mut state, "PrankCheckerProxy", &[]);
+
+    // The proxy is called by the test account, the checker by the proxy
+    assert_eq!(
+        callers_seen_through_proxy(&mut state, proxy_address, checker_address),
+        vec![
+            contract_address_to_felt(state.test_account_address()),
+            contract_address_to_felt(proxy_address),
+        ]
+    );
+}
+
+#[test]
+fn prank_nested_calls_prank_on_caller() {
+    let mut state = create_cheatnet_state();
+    let checker_address = deploy_contract(&mut state, "PrankChecker", &[]);
+    let proxy_address = deploy_contract(&mut state, "PrankCheckerProxy", &[]);
+
+    state.start_prank(proxy_address, ContractAddress::from(123_u128));
+
+    // Only the proxy sees the pranked caller, the checker still sees the proxy
+    assert_eq!(
+        callers_seen_through_proxy(&mut state, proxy_address, checker_address),
+        vec![Felt252::from(123), contract_address_to_felt(proxy_address)]
+    );
+}
+
+#[test]
+fn prank_nested_calls_prank_on_callee() {
+    let mut state = create_cheatnet_state();
+    let checker_address = deploy_contract(&mut state, "PrankChecker", &[]);
+    let proxy_address = deploy_contract(&mut state, "PrankCheckerProxy", &[]);
+
+    state.start_prank(checker_address, ContractAddress::from(456_u128));
+
+    // The checker sees the pranked caller instead of the proxy calling it
+    assert_eq!(
+        callers_seen_through_proxy(&mut state, proxy_address, checker_address),
+        vec![
+            contract_address_to_felt(state.test_account_address()),
+            Felt252::from(456),
+        ]
+    );
+}
+
+#[test]
+fn prank_nested_calls_prank_on_both() {
+    let mut state = create_cheatnet_state();
+    let checker_address = deploy_contract(&mut state, "PrankChecker", &[]);
+    let proxy_address = deploy_contract(&mut state, "PrankCheckerProxy", &[]);
+
+    state.start_prank(proxy_address, ContractAddress::from(123_u128));
+    state.start_prank(checker_address, ContractAddress::from(456_u128));
+
+    // Each contract sees its own pranked caller
+    assert_eq!(
+        callers_seen_through_proxy(&mut state, proxy_address, checker_address),
+        vec![Felt252::from(123), Felt252::from(456)]
+    );
+}
+
+#[test]
+fn prank_nested_calls_prank_on_callee_with_span() {
+    let mut state = create_cheatnet_state();
+    let checker_address = deploy_contract(&mut state, "PrankChecker", &[]);
+    let proxy_address = deploy_contract(&mut state, "PrankCheckerProxy", &[]);
+
+    state.prank(
+        checker_address,
+        ContractAddress::from(456_u128),
+        CheatSpan::TargetCalls(1),
+    );
+
+    // Calls made by other contracts count towards the span of the prank
+    assert_eq!(
+        callers_seen_through_proxy(&mut state, proxy_address, checker_address)[1],
+        Felt252::from(456)
+    );
+    assert_eq!(
+        callers_seen_through_proxy(&mut state, proxy_address, checker_address)[1],
+        contract_address_to_felt(proxy_address)
+    );
+}
@@ -0,0 +1,76 @@
+use crate::common::{get_contracts, state::create_cheatnet_state};
+use cairo_felt::Felt252;
+use cheatnet::{address::calculate_contract_address, conversions::felt_from_short_string};
+
+#[test]
+fn precalculate_address_simple() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("HelloStarknet");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+
+    let precalculated_address = state.precalculate_address(&class_hash, &[]);
+    let contract_address = state.deploy(&class_hash, &[]).unwrap();
+
+    assert_eq!(precalculated_address, contract_address);
+}
+
+#[test]
+fn precalculate_address_with_calldata() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("MockChecker");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+
+    let precalculated_address = state.precalculate_address(&class_hash, &[Felt252::from(420)]);
+    let other_precalculated_address =
+        state.precalculate_address(&class_hash, &[Felt252::from(421)]);
+    let contract_address = state.deploy(&class_hash, &[Felt252::from(420)]).unwrap();
+
+    assert_eq!(precalculated_address, contract_address);
+    assert_ne!(precalculated_address, other_precalculated_address);
+}
+
+#[test]
+fn precalculate_address_does_not_change_salt() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("HelloStarknet");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+
+    let precalculated_address = state.precalculate_address(&class_hash, &[]);
+    let precalculated_address_again = state.precalculate_address(&class_hash, &[]);
+    assert_eq!(precalculated_address, precalculated_address_again);
+
+    state.deploy(&class_hash, &[]).unwrap();
+    let next_precalculated_address = state.precalculate_address(&class_hash, &[]);
+    let next_contract_address = state.deploy(&class_hash, &[]).unwrap();
+
+    assert_ne!(precalculated_address, next_precalculated_address);
+    assert_eq!(next_precalculated_address, next_contract_address);
+}
+
+#[test]
+fn deploy_address_matches_calculated_address() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("MockChecker");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+
+    let calldata = [Felt252::from(420)];
+    let contract_address = state.deploy(&class_hash, &calldata).unwrap();
+
+    assert_eq!(
+        contract_address,
+        calculate_contract_address(
+            &Felt252::from(0),
+            &class_hash,
+            &calldata,
+            state.test_account_address()
+        )
+    );
+}
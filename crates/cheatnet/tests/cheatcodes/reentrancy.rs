@@ -0,0 +1,89 @@
+use crate::{
+    assert_success,
+    common::{deploy_contract, state::create_cheatnet_state},
+};
+use cairo_felt::Felt252;
+use cheatnet::{
+    conversions::felt_selector_from_name,
+    panic_data::CallFrame,
+    reentrancy::{ReenteredFrame, ReentrancyReport},
+    rpc::call_contract,
+};
+use starknet_api::core::EntryPointSelector;
+use starknet_api::hash::StarkFelt;
+
+fn entry_point_selector(name: &str) -> EntryPointSelector {
+    EntryPointSelector(StarkFelt::new(felt_selector_from_name(name).to_be_bytes()).unwrap())
+}
+
+#[test]
+fn reentrancy_is_not_tracked_by_default() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "ReentrancyChecker", &[]);
+
+    let output = call_contract(
+        &contract_address,
+        &felt_selector_from_name("reenter"),
+        &[Felt252::from(1)],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![]);
+
+    assert_eq!(state.last_call_reentrancy_report(), None);
+}
+
+#[test]
+fn reentered_call_is_reported() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "ReentrancyChecker", &[]);
+    state.track_reentrancy();
+
+    let output = call_contract(
+        &contract_address,
+        &felt_selector_from_name("reenter"),
+        &[Felt252::from(2)],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![]);
+
+    // reenter(2) -> reenter(1) -> reenter(0) -> get_value
+    assert_eq!(
+        state.last_call_reentrancy_report(),
+        Some(&ReentrancyReport {
+            max_depth: 4,
+            reentered_frames: vec![ReenteredFrame {
+                frame: CallFrame {
+                    contract_address,
+                    selector: entry_point_selector("reenter"),
+                },
+                max_occurrences: 3,
+            }],
+        })
+    );
+}
+
+#[test]
+fn call_without_reentry_is_not_reported() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "ReentrancyChecker", &[]);
+    state.track_reentrancy();
+
+    call_contract(
+        &contract_address,
+        &felt_selector_from_name("reenter"),
+        &[Felt252::from(0)],
+        &mut state,
+    )
+    .unwrap();
+
+    // The calls into `get_value` are on the stack one after another, never at the same time
+    let report = state.last_call_reentrancy_report().unwrap();
+    assert_eq!(report.max_depth, 2);
+    assert!(!report.was_reentered(&CallFrame {
+        contract_address,
+        selector: entry_point_selector("reenter"),
+    }));
+    assert!(report.reentered_frames.is_empty());
+}
@@ -0,0 +1,83 @@
+use crate::{
+    assert_success,
+    common::{deploy_contract, get_contracts, state::create_cheatnet_state},
+};
+use cairo_felt::Felt252;
+use cheatnet::{
+    cheatcodes::{CheatcodeError, CheatcodeFailure},
+    conversions::{felt_from_short_string, felt_selector_from_name},
+    panic_data::PanicData,
+    rpc::call_contract,
+};
+use starknet_api::core::{ClassHash, ContractAddress};
+use starknet_api::hash::StarkFelt;
+
+#[test]
+fn replace_class_simple() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+    let contract_address = deploy_contract(&mut state, "ReplaceClassA", &[]);
+
+    let selector = felt_selector_from_name("set_value");
+    call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(5)],
+        &mut state,
+    )
+    .unwrap();
+
+    let new_class_hash = state
+        .declare(&felt_from_short_string("ReplaceClassB"), &contracts)
+        .unwrap();
+    state
+        .replace_class(contract_address, new_class_hash)
+        .unwrap();
+
+    assert_eq!(
+        state.get_class_hash(contract_address).unwrap(),
+        new_class_hash
+    );
+    let selector = felt_selector_from_name("get_value");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(105)]);
+}
+
+#[test]
+fn replace_class_undeclared() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "ReplaceClassA", &[]);
+
+    let result = state.replace_class(contract_address, ClassHash(StarkFelt::from(123_u32)));
+
+    assert!(match result {
+        Err(CheatcodeError::Recoverable(failure)) =>
+            PanicData::from_felts(failure.panic_data()).message()
+                == Some("Class hash 0x7b not declared"),
+        _ => false,
+    });
+}
+
+#[test]
+fn replace_class_not_deployed() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+    let class_hash = state
+        .declare(&felt_from_short_string("ReplaceClassB"), &contracts)
+        .unwrap();
+    let target = ContractAddress::from(1234_u128);
+
+    let result = state.replace_class(target, class_hash);
+
+    assert!(matches!(
+        result,
+        Err(CheatcodeError::Recoverable(CheatcodeFailure::ContractNotDeployed(address)))
+            if address == target
+    ));
+    assert!(matches!(
+        state.get_class_hash(target),
+        Err(CheatcodeError::Recoverable(
+            CheatcodeFailure::ContractNotDeployed(_)
+        ))
+    ));
+}
@@ -0,0 +1,81 @@
+use crate::common::{get_contracts, recover_data, state::create_cheatnet_state};
+use cairo_felt::Felt252;
+use cheatnet::{
+    cheatcodes::CheatcodeError,
+    constants::TEST_ACCOUNT_CONTRACT_ADDRESS,
+    conversions::{felt_from_short_string, felt_selector_from_name},
+    rpc::call_contract,
+};
+use starknet_api::core::{ContractAddress, PatriciaKey};
+use starknet_api::hash::StarkHash;
+use starknet_api::patricia_key;
+
+#[test]
+fn reset_then_deploy_yields_first_address() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+    let contract_name = felt_from_short_string("HelloStarknet");
+
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    let first_address = state.deploy(&class_hash, &[]).unwrap();
+    state.deploy(&class_hash, &[]).unwrap();
+
+    state.reset();
+
+    assert!(matches!(
+        state.get_class_hash(first_address),
+        Err(CheatcodeError::Recoverable(_))
+    ));
+
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    let contract_address = state.deploy(&class_hash, &[]).unwrap();
+
+    assert_eq!(contract_address, first_address);
+}
+
+#[test]
+fn reset_stops_cheatcodes() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+    let contract_name = felt_from_short_string("RollChecker");
+
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    let precalculated_address = state.precalculate_address(&class_hash, &[]);
+    state.start_roll(precalculated_address, Felt252::from(123));
+
+    state.reset();
+
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    let contract_address = state.deploy(&class_hash, &[]).unwrap();
+    assert_eq!(contract_address, precalculated_address);
+
+    let selector = felt_selector_from_name("get_block_number");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_ne!(recover_data(output), vec![Felt252::from(123)]);
+}
+
+#[test]
+fn reset_is_idempotent() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+    let contract_name = felt_from_short_string("HelloStarknet");
+    let account_address = ContractAddress(patricia_key!(TEST_ACCOUNT_CONTRACT_ADDRESS));
+
+    let initial_nonce = state.get_nonce(account_address).unwrap();
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    state.deploy(&class_hash, &[]).unwrap();
+
+    state.reset();
+    let nonce_after_reset = state.get_nonce(account_address).unwrap();
+    let address_after_reset = state.precalculate_address(&class_hash, &[]);
+
+    state.reset();
+
+    assert_eq!(nonce_after_reset, initial_nonce);
+    assert_eq!(state.get_nonce(account_address).unwrap(), nonce_after_reset);
+    assert_eq!(
+        state.precalculate_address(&class_hash, &[]),
+        address_after_reset
+    );
+    assert_eq!(state.deploy_salt_base, 0);
+}
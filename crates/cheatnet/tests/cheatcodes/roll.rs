@@ -44,7 +44,6 @@ fn roll_with_other_syscall() {
 }
 
 #[test]
-#[ignore = "TODO(#254)"]
 fn roll_in_constructor() {
     let mut state = create_cheatnet_state();
 
@@ -182,3 +181,73 @@ fn roll_library_call() {
 
     assert_success!(output, vec![Felt252::from(123)]);
 }
+
+#[test]
+fn roll_stopped_before_deploy() {
+    let mut state = create_cheatnet_state();
+
+    let contracts = get_contracts();
+
+    let contract_name = felt_from_short_string("ConstructorRollChecker");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    let precalculated_address = state.precalculate_address(&class_hash, vec![].as_slice());
+
+    state.start_roll(precalculated_address, Felt252::from(123_u128));
+    state.stop_roll(precalculated_address);
+
+    let contract_address = state.deploy(&class_hash, vec![].as_slice()).unwrap();
+
+    let selector = felt_selector_from_name("get_stored_block_number");
+
+    let output =
+        call_contract(&contract_address, &selector, vec![].as_slice(), &mut state).unwrap();
+
+    assert_success!(output, vec![Felt252::from(2000)]);
+}
+
+#[test]
+fn roll_global_with_per_contract_override() {
+    let mut state = create_cheatnet_state();
+
+    let rolled_address = deploy_contract(&mut state, "RollChecker", &[]);
+    let other_address = deploy_contract(&mut state, "RollChecker", &[]);
+    let selector = felt_selector_from_name("get_block_number");
+
+    state.start_roll_global(Felt252::from(100));
+    state.start_roll(rolled_address, Felt252::from(200));
+
+    // The roll of the contract takes precedence over the global one
+    let output = call_contract(&rolled_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(200)]);
+    let output = call_contract(&other_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(100)]);
+
+    state.stop_roll(rolled_address);
+    let output = call_contract(&rolled_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(100)]);
+
+    state.stop_roll_global();
+    let output = call_contract(&other_address, &selector, &[], &mut state).unwrap();
+    assert_ne!(recover_data(output), vec![Felt252::from(100)]);
+}
+
+#[test]
+fn get_roll_read_modify_write() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "RollChecker", &[]);
+
+    assert_eq!(state.get_roll(contract_address), None);
+
+    state.start_roll(contract_address, Felt252::from(123));
+    let value = state.get_roll(contract_address).unwrap();
+    assert_eq!(value, 123);
+    state.start_roll(contract_address, Felt252::from(value + 100));
+
+    let selector = felt_selector_from_name("get_block_number");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(223)]);
+
+    state.stop_roll(contract_address);
+    state.start_roll_global(Felt252::from(456));
+    assert_eq!(state.get_roll(contract_address), Some(456));
+}
@@ -0,0 +1,247 @@
+use crate::{
+    assert_success,
+    common::{deploy_contract, get_contracts, state::create_cheatnet_state},
+};
+use cairo_felt::Felt252;
+use cheatnet::{
+    cheatcodes::{CheatcodeError, CheatcodeFailure},
+    conversions::{felt_from_short_string, felt_selector_from_name},
+    rpc::call_contract,
+    CheatnetState,
+};
+use starknet_api::core::ContractAddress;
+
+#[test]
+fn restore_reverts_storage() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    let increase_balance = felt_selector_from_name("increase_balance");
+    let get_balance = felt_selector_from_name("get_balance");
+
+    call_contract(
+        &contract_address,
+        &increase_balance,
+        &[Felt252::from(5)],
+        &mut state,
+    )
+    .unwrap();
+
+    let snapshot = state.snapshot();
+
+    call_contract(
+        &contract_address,
+        &increase_balance,
+        &[Felt252::from(10)],
+        &mut state,
+    )
+    .unwrap();
+    let output = call_contract(&contract_address, &get_balance, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(15)]);
+
+    state.restore(&snapshot);
+
+    let output = call_contract(&contract_address, &get_balance, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(5)]);
+}
+
+#[test]
+fn restore_multiple_times() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    let increase_balance = felt_selector_from_name("increase_balance");
+    let get_balance = felt_selector_from_name("get_balance");
+
+    let snapshot = state.snapshot();
+
+    for _ in 0..3 {
+        call_contract(
+            &contract_address,
+            &increase_balance,
+            &[Felt252::from(5)],
+            &mut state,
+        )
+        .unwrap();
+        let output = call_contract(&contract_address, &get_balance, &[], &mut state).unwrap();
+        assert_success!(output, vec![Felt252::from(5)]);
+
+        state.restore(&snapshot);
+    }
+}
+
+#[test]
+fn restore_keeps_declared_and_deployed_contracts() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+    let contract_name = felt_from_short_string("HelloStarknet");
+    let class_hash = state.declare(&contract_name, &contracts).unwrap();
+    let contract_address = state.deploy(&class_hash, &[]).unwrap();
+
+    let snapshot = state.snapshot();
+    state.restore(&snapshot);
+
+    let selector = felt_selector_from_name("get_balance");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(0)]);
+
+    // The salt counter is restored as well, so the next deployment doesn't collide
+    let other_address = state.deploy(&class_hash, &[]).unwrap();
+    assert_ne!(contract_address, other_address);
+}
+
+#[test]
+fn restore_reverts_salt_and_cheats() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "WarpChecker", &[]);
+    let selector = felt_selector_from_name("get_block_timestamp");
+
+    state.start_warp(contract_address, Felt252::from(123));
+    let deploy_salt_base = state.deploy_salt_base;
+    let snapshot = state.snapshot();
+
+    state.stop_warp(contract_address);
+    deploy_contract(&mut state, "HelloStarknet", &[]);
+
+    state.restore(&snapshot);
+
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(123)]);
+    assert_eq!(state.deploy_salt_base, deploy_salt_base);
+}
+
+#[test]
+fn restore_reverts_captured_events() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "SpyEventsChecker", &[]);
+    let selector = felt_selector_from_name("emit_one_event");
+
+    let spy = state.spy_events();
+    call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(1)],
+        &mut state,
+    )
+    .unwrap();
+    let snapshot = state.snapshot();
+
+    call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(2)],
+        &mut state,
+    )
+    .unwrap();
+    assert_eq!(spy.count(), 2);
+
+    state.restore(&snapshot);
+    assert_eq!(spy.count(), 1);
+
+    // The spy still captures events after a restore
+    call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(3)],
+        &mut state,
+    )
+    .unwrap();
+    let data: Vec<Vec<Felt252>> = spy
+        .get_events()
+        .into_iter()
+        .map(|event| event.data)
+        .collect();
+    assert_eq!(data, vec![vec![Felt252::from(1)], vec![Felt252::from(3)]]);
+}
+
+fn increase_balance_case(
+    state: &mut CheatnetState,
+    contract_address: ContractAddress,
+    inputs: &[Felt252],
+) -> Result<Vec<Felt252>, CheatcodeError> {
+    state.invoke(
+        &contract_address,
+        &felt_selector_from_name("increase_balance"),
+        inputs,
+    )?;
+    deploy_contract(state, "HelloStarknet", &[]);
+    state.call(
+        contract_address,
+        &felt_selector_from_name("get_balance"),
+        &[],
+    )
+}
+
+#[test]
+fn run_fuzz_case_restores_state() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    let deploy_salt_base = state.deploy_salt_base;
+
+    for _ in 0..2 {
+        let balance = state
+            .run_fuzz_case(&[Felt252::from(5)], |state, inputs| {
+                increase_balance_case(state, contract_address, inputs)
+            })
+            .unwrap();
+        assert_eq!(balance, vec![Felt252::from(5)]);
+
+        let output = call_contract(
+            &contract_address,
+            &felt_selector_from_name("get_balance"),
+            &[],
+            &mut state,
+        )
+        .unwrap();
+        assert_success!(output, vec![Felt252::from(0)]);
+        assert_eq!(state.deploy_salt_base, deploy_salt_base);
+    }
+}
+
+#[test]
+fn run_fuzz_case_returns_recoverable_errors() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+
+    let result = state.run_fuzz_case(&[Felt252::from(0)], |state, inputs| {
+        increase_balance_case(state, contract_address, inputs)
+    });
+    assert!(matches!(
+        result,
+        Err(CheatcodeError::Recoverable(CheatcodeFailure::ContractPanic(panic_data)))
+            if panic_data == vec![felt_from_short_string("Amount cannot be 0")]
+    ));
+
+    let output = call_contract(
+        &contract_address,
+        &felt_selector_from_name("get_balance"),
+        &[],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![Felt252::from(0)]);
+}
+
+#[test]
+fn run_fuzz_case_reverts_captured_events() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "SpyEventsChecker", &[]);
+    let spy = state.spy_events();
+
+    for input in [1, 2] {
+        let count = state
+            .run_fuzz_case(&[Felt252::from(input)], |state, inputs| {
+                call_contract(
+                    &contract_address,
+                    &felt_selector_from_name("emit_one_event"),
+                    inputs,
+                    state,
+                )
+                .unwrap();
+                Ok(spy.count())
+            })
+            .unwrap();
+
+        // Every case sees only its own event
+        assert_eq!(count, 1);
+        assert_eq!(spy.count(), 0);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{
+    assert_success,
+    common::{deploy_contract, recover_data, state::create_cheatnet_state},
+};
+use cairo_felt::Felt252;
+use cheatnet::{
+    cheatcodes::spoof::TxInfoMock, conversions::felt_selector_from_name, rpc::call_contract,
+};
+
+#[test]
+fn spoof_transaction_hash() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "SpoofChecker", &[]);
+
+    state.start_spoof(
+        contract_address,
+        TxInfoMock {
+            transaction_hash: Some(Felt252::from(123)),
+            ..Default::default()
+        },
+    );
+
+    let selector = felt_selector_from_name("get_transaction_hash");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(123)]);
+
+    // Fields which weren't provided keep their original values
+    let selector = felt_selector_from_name("get_signature");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(0)]);
+}
+
+#[test]
+fn spoof_signature() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "SpoofChecker", &[]);
+
+    let selector = felt_selector_from_name("get_transaction_hash");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    let original_transaction_hash = recover_data(output);
+
+    state.start_spoof(
+        contract_address,
+        TxInfoMock {
+            signature: Some(vec![Felt252::from(12), Felt252::from(34)]),
+            ..Default::default()
+        },
+    );
+
+    let selector = felt_selector_from_name("get_signature");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(
+        output,
+        vec![Felt252::from(2), Felt252::from(12), Felt252::from(34)]
+    );
+
+    let selector = felt_selector_from_name("get_transaction_hash");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_eq!(recover_data(output), original_transaction_hash);
+}
+
+#[test]
+fn spoof_multiple_fields() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "SpoofChecker", &[]);
+
+    state.start_spoof(
+        contract_address,
+        TxInfoMock {
+            transaction_hash: Some(Felt252::from(123)),
+            nonce: Some(Felt252::from(5)),
+            ..Default::default()
+        },
+    );
+
+    let selector = felt_selector_from_name("get_transaction_hash");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(123)]);
+
+    let selector = felt_selector_from_name("get_nonce");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(5)]);
+}
+
+#[test]
+fn spoof_stop() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "SpoofChecker", &[]);
+    let selector = felt_selector_from_name("get_transaction_hash");
+
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    let original_transaction_hash = recover_data(output);
+
+    state.start_spoof(
+        contract_address,
+        TxInfoMock {
+            transaction_hash: Some(Felt252::from(123)),
+            ..Default::default()
+        },
+    );
+    state.stop_spoof(contract_address);
+
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_eq!(recover_data(output), original_transaction_hash);
+}
@@ -0,0 +1,270 @@
+use crate::common::{deploy_contract, state::create_cheatnet_state};
+use cairo_felt::Felt252;
+use cheatnet::{
+    cheatcodes::{spy_events::ExpectedEvent, Event},
+    conversions::{contract_address_to_felt, felt_selector_from_name},
+    rpc::call_contract,
+    CheatnetState,
+};
+use starknet_api::core::ContractAddress;
+
+#[test]
+fn spy_events_simple() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "SpyEventsChecker", &[]);
+
+    let spy = state.spy_events();
+
+    let selector = felt_selector_from_name("emit_one_event");
+    call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(123)],
+        &mut state,
+    )
+    .unwrap();
+
+    let expected_event = Event {
+        from: contract_address,
+        keys: vec![felt_selector_from_name("FirstEvent")],
+        data: vec![Felt252::from(123)],
+    };
+    assert_eq!(spy.get_events(), vec![expected_event.clone()]);
+    spy.assert_emitted(&expected_event);
+}
+
+#[test]
+fn spy_events_nested_calls_in_emission_order() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "SpyEventsChecker", &[]);
+    let other_address = deploy_contract(&mut state, "SpyEventsChecker", &[]);
+
+    let spy = state.spy_events();
+
+    let selector = felt_selector_from_name("emit_and_call_other");
+    call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(123), contract_address_to_felt(other_address)],
+        &mut state,
+    )
+    .unwrap();
+
+    assert_eq!(
+        spy.get_events(),
+        vec![
+            Event {
+                from: contract_address,
+                keys: vec![felt_selector_from_name("FirstEvent")],
+                data: vec![Felt252::from(123)],
+            },
+            Event {
+                from: other_address,
+                keys: vec![felt_selector_from_name("FirstEvent")],
+                data: vec![Felt252::from(124)],
+            },
+        ]
+    );
+}
+
+#[test]
+fn spy_events_ignores_events_before_creation() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "SpyEventsChecker", &[]);
+    let selector = felt_selector_from_name("emit_one_event");
+
+    call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(123)],
+        &mut state,
+    )
+    .unwrap();
+
+    let spy = state.spy_events();
+
+    call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(456)],
+        &mut state,
+    )
+    .unwrap();
+
+    assert_eq!(
+        spy.get_events(),
+        vec![Event {
+            from: contract_address,
+            keys: vec![felt_selector_from_name("FirstEvent")],
+            data: vec![Felt252::from(456)],
+        }]
+    );
+}
+
+#[test]
+fn spy_events_in_constructor() {
+    let mut state = create_cheatnet_state();
+
+    let spy = state.spy_events();
+    let contract_address =
+        deploy_contract(&mut state, "ConstructorEventEmitter", &[Felt252::from(7)]);
+
+    spy.assert_emitted(&Event {
+        from: contract_address,
+        keys: vec![felt_selector_from_name("Initialized")],
+        data: vec![Felt252::from(7)],
+    });
+}
+
+#[test]
+#[should_panic(expected = "was not emitted")]
+fn assert_emitted_fails_for_missing_event() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "SpyEventsChecker", &[]);
+
+    let spy = state.spy_events();
+
+    spy.assert_emitted(&Event {
+        from: contract_address,
+        keys: vec![felt_selector_from_name("FirstEvent")],
+        data: vec![Felt252::from(123)],
+    });
+}
+
+#[test]
+fn spy_events_filters() {
+    let mut state = create_cheatnet_state();
+    let other_address = deploy_contract(&mut state, "SpyEventsChecker", &[]);
+
+    let spy = state.spy_events();
+    let emitter_address = deploy_contract(
+        &mut state,
+        "ConstructorNestedEventEmitter",
+        &[Felt252::from(7), contract_address_to_felt(other_address)],
+    );
+    call_contract(
+        &other_address,
+        &felt_selector_from_name("emit_one_event"),
+        &[Felt252::from(123)],
+        &mut state,
+    )
+    .unwrap();
+
+    assert_eq!(spy.count(), 4);
+
+    let emitter_events = spy.events_from(emitter_address);
+    assert_eq!(
+        emitter_events
+            .iter()
+            .map(|event| event.keys[0].clone())
+            .collect::<Vec<_>>(),
+        vec![
+            felt_selector_from_name("Initialized"),
+            felt_selector_from_name("Finished")
+        ]
+    );
+
+    let other_events = spy.events_from(other_address);
+    assert_eq!(other_events.len(), 2);
+    assert_eq!(
+        spy.events_with_selector(&felt_selector_from_name("FirstEvent")),
+        other_events
+    );
+
+    assert_eq!(
+        spy.events_with_selector(&felt_selector_from_name("Finished")),
+        vec![Event {
+            from: emitter_address,
+            keys: vec![felt_selector_from_name("Finished")],
+            data: vec![Felt252::from(9)],
+        }]
+    );
+    assert!(spy
+        .events_with_selector(&felt_selector_from_name("NotEmitted"))
+        .is_empty());
+}
+
+// Transfers tokens from `owner` to `recipient` and then approves `spender`, with random amounts
+fn transfer_then_approve(
+    state: &mut CheatnetState,
+    owner: ContractAddress,
+    recipient: ContractAddress,
+    spender: ContractAddress,
+) -> ContractAddress {
+    let mut calldata = [1, 2, 18, 1000, 0].map(Felt252::from).to_vec();
+    calldata.push(contract_address_to_felt(owner));
+    let token = deploy_contract(state, "ERC20", &calldata);
+
+    state
+        .erc20_transfer(token, owner, recipient, 123, 0)
+        .unwrap();
+    state.start_prank(token, owner);
+    call_contract(
+        &token,
+        &felt_selector_from_name("approve"),
+        &[
+            contract_address_to_felt(spender),
+            Felt252::from(456),
+            Felt252::from(0),
+        ],
+        state,
+    )
+    .unwrap();
+    state.stop_prank(token);
+
+    token
+}
+
+// Matches the event by its name and the two addresses it holds, ignoring the u256 amount
+fn expected_erc20_event(
+    token: ContractAddress,
+    name: &str,
+    first: ContractAddress,
+    second: ContractAddress,
+) -> ExpectedEvent {
+    ExpectedEvent {
+        from: Some(token),
+        keys: Some(vec![Some(felt_selector_from_name(name))]),
+        data: Some(vec![
+            Some(contract_address_to_felt(first)),
+            Some(contract_address_to_felt(second)),
+            None,
+            None,
+        ]),
+    }
+}
+
+#[test]
+fn expect_events_in_order_with_wildcards() {
+    let mut state = create_cheatnet_state();
+    let owner = ContractAddress::from(123_u8);
+    let recipient = ContractAddress::from(456_u8);
+    let spender = ContractAddress::from(789_u8);
+
+    let spy = state.spy_events();
+    let token = transfer_then_approve(&mut state, owner, recipient, spender);
+
+    // The Transfer event emitted by the constructor is skipped
+    spy.expect(&[
+        expected_erc20_event(token, "Transfer", owner, recipient),
+        expected_erc20_event(token, "Approval", owner, spender),
+    ]);
+    spy.expect(&[ExpectedEvent::default(), (&spy.get_events()[2]).into()]);
+}
+
+#[test]
+#[should_panic(expected = "Expected event 1")]
+fn expect_events_fails_for_wrong_order() {
+    let mut state = create_cheatnet_state();
+    let owner = ContractAddress::from(123_u8);
+    let recipient = ContractAddress::from(456_u8);
+    let spender = ContractAddress::from(789_u8);
+
+    let spy = state.spy_events();
+    let token = transfer_then_approve(&mut state, owner, recipient, spender);
+
+    spy.expect(&[
+        expected_erc20_event(token, "Approval", owner, spender),
+        expected_erc20_event(token, "Transfer", owner, recipient),
+    ]);
+}
@@ -0,0 +1,91 @@
+use crate::common::{deploy_contract, state::create_cheatnet_state};
+use cairo_felt::Felt252;
+use cheatnet::{
+    cheatcodes::L2ToL1Message,
+    conversions::{contract_address_to_felt, felt_selector_from_name},
+    rpc::call_contract,
+};
+
+#[test]
+fn spy_messages_to_l1_simple() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "MessageToL1Checker", &[]);
+
+    let spy = state.spy_messages_to_l1();
+
+    let selector = felt_selector_from_name("send_message");
+    call_contract(
+        &contract_address,
+        &selector,
+        &[
+            Felt252::from(123),
+            Felt252::from(2),
+            Felt252::from(456),
+            Felt252::from(789),
+        ],
+        &mut state,
+    )
+    .unwrap();
+
+    let expected_message = L2ToL1Message {
+        from: contract_address,
+        to_address: Felt252::from(123),
+        payload: vec![Felt252::from(456), Felt252::from(789)],
+    };
+    assert_eq!(spy.get_messages(), vec![expected_message.clone()]);
+    spy.assert_sent(&expected_message);
+}
+
+#[test]
+fn spy_messages_to_l1_nested_calls_in_order() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "MessageToL1Checker", &[]);
+    let other_address = deploy_contract(&mut state, "MessageToL1Checker", &[]);
+
+    let spy = state.spy_messages_to_l1();
+
+    let selector = felt_selector_from_name("send_and_call_other");
+    call_contract(
+        &contract_address,
+        &selector,
+        &[
+            Felt252::from(123),
+            Felt252::from(1),
+            Felt252::from(456),
+            contract_address_to_felt(other_address),
+        ],
+        &mut state,
+    )
+    .unwrap();
+
+    assert_eq!(
+        spy.get_messages(),
+        vec![
+            L2ToL1Message {
+                from: contract_address,
+                to_address: Felt252::from(123),
+                payload: vec![Felt252::from(456)],
+            },
+            L2ToL1Message {
+                from: other_address,
+                to_address: Felt252::from(124),
+                payload: vec![Felt252::from(456)],
+            },
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "was not sent")]
+fn assert_sent_fails_for_missing_message() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "MessageToL1Checker", &[]);
+
+    let spy = state.spy_messages_to_l1();
+
+    spy.assert_sent(&L2ToL1Message {
+        from: contract_address,
+        to_address: Felt252::from(123),
+        payload: vec![],
+    });
+}
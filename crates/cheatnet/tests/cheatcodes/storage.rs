@@ -0,0 +1,275 @@
+use crate::{
+    assert_success,
+    common::{deploy_contract, state::create_cheatnet_state},
+};
+use blockifier::abi::abi_utils::get_storage_var_address;
+use blockifier::execution::execution_utils::stark_felt_to_felt;
+use cairo_felt::Felt252;
+use cheatnet::{
+    conversions::{
+        felt_from_short_string, felt_selector_from_name, map_entry_address,
+        storage_address_from_base, storage_var_address,
+    },
+    rpc::call_contract,
+};
+use starknet_api::core::ContractAddress;
+
+#[test]
+fn store_and_load_simple() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", vec![].as_slice());
+
+    state
+        .store(contract_address, &Felt252::from(123), &Felt252::from(420))
+        .unwrap();
+
+    let value = state.load(contract_address, &Felt252::from(123)).unwrap();
+    assert_eq!(value, Felt252::from(420));
+
+    let value = state.load(contract_address, &Felt252::from(124)).unwrap();
+    assert_eq!(value, Felt252::from(0));
+}
+
+#[test]
+fn store_is_visible_to_contract() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", vec![].as_slice());
+    let balance_address =
+        stark_felt_to_felt(*get_storage_var_address("balance", &[]).unwrap().0.key());
+
+    state
+        .store(contract_address, &balance_address, &Felt252::from(420))
+        .unwrap();
+
+    let selector = felt_selector_from_name("get_balance");
+    let output =
+        call_contract(&contract_address, &selector, vec![].as_slice(), &mut state).unwrap();
+
+    assert_success!(output, vec![Felt252::from(420)]);
+}
+
+#[test]
+fn load_reads_contract_writes() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", vec![].as_slice());
+    let balance_address =
+        stark_felt_to_felt(*get_storage_var_address("balance", &[]).unwrap().0.key());
+
+    let selector = felt_selector_from_name("increase_balance");
+    call_contract(
+        &contract_address,
+        &selector,
+        vec![Felt252::from(42)].as_slice(),
+        &mut state,
+    )
+    .unwrap();
+
+    let value = state.load(contract_address, &balance_address).unwrap();
+    assert_eq!(value, Felt252::from(42));
+}
+
+#[test]
+fn store_not_deployed_contract() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = ContractAddress::from(1234_u128);
+
+    state
+        .store(contract_address, &Felt252::from(123), &Felt252::from(420))
+        .unwrap();
+
+    let value = state.load(contract_address, &Felt252::from(123)).unwrap();
+    assert_eq!(value, Felt252::from(420));
+}
+
+#[test]
+fn map_entry_address_matches_contract_storage() {
+    let mut state = create_cheatnet_state();
+
+    let recipient = Felt252::from(123);
+    let contract_address = deploy_contract(
+        &mut state,
+        "ERC20",
+        vec![
+            felt_from_short_string("Token"),
+            felt_from_short_string("TKN"),
+            Felt252::from(18),
+            Felt252::from(1000),
+            Felt252::from(0),
+            recipient.clone(),
+        ]
+        .as_slice(),
+    );
+
+    let name = state
+        .load(contract_address, &map_entry_address("name", &[]))
+        .unwrap();
+    assert_eq!(name, felt_from_short_string("Token"));
+
+    let balance_address = map_entry_address("balances", &[recipient]);
+    let balance_low = state.load(contract_address, &balance_address).unwrap();
+    let balance_high = state
+        .load(
+            contract_address,
+            &storage_address_from_base(&balance_address, 1),
+        )
+        .unwrap();
+
+    assert_eq!(balance_low, Felt252::from(1000));
+    assert_eq!(balance_high, Felt252::from(0));
+}
+
+#[test]
+fn store_struct_members() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "StructStorageChecker", &[]);
+    let key = Felt252::from(7);
+
+    state
+        .store(
+            contract_address,
+            &storage_var_address("positions", &[key.clone()], 0),
+            &Felt252::from(10),
+        )
+        .unwrap();
+    state
+        .store(
+            contract_address,
+            &storage_var_address("positions", &[key.clone()], 2),
+            &Felt252::from(30),
+        )
+        .unwrap();
+
+    let selector = felt_selector_from_name("get_position");
+    let output = call_contract(&contract_address, &selector, &[key], &mut state).unwrap();
+    assert_success!(
+        output,
+        vec![Felt252::from(10), Felt252::from(0), Felt252::from(30)]
+    );
+}
+
+#[test]
+fn dump_storage_lists_written_slots() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    assert_eq!(state.dump_storage(contract_address), vec![]);
+
+    state
+        .store(contract_address, &Felt252::from(124), &Felt252::from(2))
+        .unwrap();
+    state
+        .store(contract_address, &Felt252::from(123), &Felt252::from(1))
+        .unwrap();
+    state
+        .store(contract_address, &Felt252::from(125), &Felt252::from(0))
+        .unwrap();
+
+    let selector = felt_selector_from_name("increase_balance");
+    call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(100)],
+        &mut state,
+    )
+    .unwrap();
+    let balance_address =
+        stark_felt_to_felt(*get_storage_var_address("balance", &[]).unwrap().0.key());
+
+    let mut expected = vec![
+        (Felt252::from(123), Felt252::from(1)),
+        (Felt252::from(124), Felt252::from(2)),
+        (balance_address, Felt252::from(100)),
+    ];
+    expected.sort();
+    assert_eq!(state.dump_storage(contract_address), expected);
+
+    let other_contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    assert_eq!(state.dump_storage(other_contract_address), vec![]);
+}
+
+#[test]
+fn set_balance_is_read_by_balance_of() {
+    let mut state = create_cheatnet_state();
+
+    let token_address = deploy_contract(&mut state, "Erc20BalancesChecker", &[]);
+    let account = ContractAddress::from(123_u128);
+
+    state.set_balance(token_address, account, 1000, 2).unwrap();
+
+    let selector = felt_selector_from_name("balance_of");
+    let output =
+        call_contract(&token_address, &selector, &[Felt252::from(123)], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(1000), Felt252::from(2)]);
+
+    let output =
+        call_contract(&token_address, &selector, &[Felt252::from(234)], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(0), Felt252::from(0)]);
+}
+
+#[test]
+fn store_and_load_felt_array() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "ArrayStorageChecker", &[]);
+    let get_values = felt_selector_from_name("get_values");
+    let values = [1, 2, 3].map(Felt252::from);
+
+    state
+        .store_felt_array(contract_address, "values", &values)
+        .unwrap();
+
+    let output = call_contract(&contract_address, &get_values, &[], &mut state).unwrap();
+    assert_success!(output, [&[Felt252::from(3)], values.as_slice()].concat());
+    assert_eq!(
+        state.load_felt_array(contract_address, "values").unwrap(),
+        values
+    );
+
+    // Arrays written by the contract are read back the same way
+    call_contract(
+        &contract_address,
+        &felt_selector_from_name("set_values"),
+        &[Felt252::from(2), Felt252::from(7), Felt252::from(8)],
+        &mut state,
+    )
+    .unwrap();
+    assert_eq!(
+        state.load_felt_array(contract_address, "values").unwrap(),
+        vec![Felt252::from(7), Felt252::from(8)]
+    );
+}
+
+#[test]
+fn store_empty_felt_array() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "ArrayStorageChecker", &[]);
+    state
+        .store_felt_array(contract_address, "values", &[Felt252::from(5)])
+        .unwrap();
+
+    state
+        .store_felt_array(contract_address, "values", &[])
+        .unwrap();
+
+    assert_eq!(
+        state
+            .load(contract_address, &map_entry_address("values", &[]))
+            .unwrap(),
+        Felt252::from(0)
+    );
+    let output = call_contract(
+        &contract_address,
+        &felt_selector_from_name("get_values"),
+        &[],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![Felt252::from(0)]);
+    assert!(state
+        .load_felt_array(contract_address, "values")
+        .unwrap()
+        .is_empty());
+}
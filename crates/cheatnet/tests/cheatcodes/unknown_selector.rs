@@ -0,0 +1,69 @@
+use crate::{
+    assert_success,
+    common::{deploy_contract, state::create_cheatnet_state},
+};
+use cairo_felt::Felt252;
+use cheatnet::{
+    conversions::felt_selector_from_name,
+    rpc::{call_contract, CallContractOutput},
+    state::UnknownSelectorBehavior,
+};
+
+#[test]
+fn unknown_selector_panics_by_default() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+
+    let output = call_contract(
+        &contract_address,
+        &felt_selector_from_name("not_existing"),
+        &[],
+        &mut state,
+    )
+    .unwrap();
+
+    assert!(matches!(output, CallContractOutput::Panic { .. }));
+}
+
+#[test]
+fn unknown_selector_with_empty_return() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+    state.set_unknown_selector_behavior(UnknownSelectorBehavior::EmptyReturn);
+
+    let output = call_contract(
+        &contract_address,
+        &felt_selector_from_name("not_existing"),
+        &[Felt252::from(1)],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![]);
+
+    // Existing selectors are executed as usual
+    call_contract(
+        &contract_address,
+        &felt_selector_from_name("increase_balance"),
+        &[Felt252::from(42)],
+        &mut state,
+    )
+    .unwrap();
+    let output = call_contract(
+        &contract_address,
+        &felt_selector_from_name("get_balance"),
+        &[],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![Felt252::from(42)]);
+
+    state.set_unknown_selector_behavior(UnknownSelectorBehavior::Panic);
+    let output = call_contract(
+        &contract_address,
+        &felt_selector_from_name("not_existing"),
+        &[],
+        &mut state,
+    )
+    .unwrap();
+    assert!(matches!(output, CallContractOutput::Panic { .. }));
+}
@@ -44,7 +44,6 @@ fn warp_with_other_syscall() {
 }
 
 #[test]
-#[ignore = "TODO(#254)"]
 fn warp_in_constructor() {
     let mut state = create_cheatnet_state();
 
@@ -182,3 +181,76 @@ fn warp_library_call() {
 
     assert_success!(output, vec![Felt252::from(123)]);
 }
+
+#[test]
+fn warp_does_not_affect_called_contracts() {
+    let mut state = create_cheatnet_state();
+
+    let contract_address = deploy_contract(&mut state, "WarpChecker", vec![].as_slice());
+    let proxy_address = deploy_contract(&mut state, "WarpCheckerProxy", vec![].as_slice());
+
+    let selector = felt_selector_from_name("get_block_timestamp");
+    let output =
+        call_contract(&contract_address, &selector, vec![].as_slice(), &mut state).unwrap();
+    let old_block_timestamp = recover_data(output);
+
+    state.start_warp(proxy_address, Felt252::from(123_u128));
+
+    let proxy_selector = felt_selector_from_name("get_warp_checkers_block_timestamp");
+    let output = call_contract(
+        &proxy_address,
+        &proxy_selector,
+        vec![contract_address_to_felt(contract_address)].as_slice(),
+        &mut state,
+    )
+    .unwrap();
+
+    assert_eq!(recover_data(output), old_block_timestamp);
+}
+
+#[test]
+fn warp_global_with_per_contract_override() {
+    let mut state = create_cheatnet_state();
+
+    let warped_address = deploy_contract(&mut state, "WarpChecker", &[]);
+    let other_address = deploy_contract(&mut state, "WarpChecker", &[]);
+    let selector = felt_selector_from_name("get_block_timestamp");
+
+    state.start_warp_global(Felt252::from(100));
+    state.start_warp(warped_address, Felt252::from(200));
+
+    // The warp of the contract takes precedence over the global one
+    let output = call_contract(&warped_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(200)]);
+    let output = call_contract(&other_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(100)]);
+
+    state.stop_warp(warped_address);
+    let output = call_contract(&warped_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(100)]);
+
+    state.stop_warp_global();
+    let output = call_contract(&other_address, &selector, &[], &mut state).unwrap();
+    assert_ne!(recover_data(output), vec![Felt252::from(100)]);
+}
+
+#[test]
+fn get_warp_read_modify_write() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "WarpChecker", &[]);
+
+    assert_eq!(state.get_warp(contract_address), None);
+
+    state.start_warp(contract_address, Felt252::from(123));
+    let value = state.get_warp(contract_address).unwrap();
+    assert_eq!(value, 123);
+    state.start_warp(contract_address, Felt252::from(value + 100));
+
+    let selector = felt_selector_from_name("get_block_timestamp");
+    let output = call_contract(&contract_address, &selector, &[], &mut state).unwrap();
+    assert_success!(output, vec![Felt252::from(223)]);
+
+    state.stop_warp(contract_address);
+    state.start_warp_global(Felt252::from(456));
+    assert_eq!(state.get_warp(contract_address), Some(456));
+}
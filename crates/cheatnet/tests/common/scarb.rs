@@ -1,9 +1,13 @@
 use anyhow::{anyhow, Context, Result};
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use cheatnet::cheatcodes::ContractArtifacts;
 use serde::Deserialize;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::ErrorKind;
+use std::process::Command;
 
 // TODO(#41) TAKEN FROM FORGE, REMOVE AFTER SCARB HAS BEEN MOVED TO A SEPARATE PACKAGE
 #[allow(dead_code)]
@@ -77,3 +81,161 @@ pub fn get_contracts_map(path: &Utf8PathBuf) -> HashMap<String, ContractArtifact
     }
     map
 }
+
+const FINGERPRINT_FILE: &str = "target/build_fingerprint";
+
+/// Hash of everything `scarb build` depends on: the version of scarb, which also determines
+/// the compiler and the bundled corelib, and the manifest, the lockfile and all files in `src`
+/// of the package. Paths are hashed together with the contents,
+/// so renaming, adding or removing a file changes the fingerprint too.
+pub fn build_fingerprint(package_dir: &Utf8Path) -> Result<u64> {
+    let output = Command::new("scarb")
+        .arg("--version")
+        .output()
+        .map_err(scarb_error)?;
+    if !output.status.success() {
+        return Err(anyhow!("scarb --version failed with {}", output.status));
+    }
+
+    package_fingerprint(package_dir, &String::from_utf8_lossy(&output.stdout))
+}
+
+fn package_fingerprint(package_dir: &Utf8Path, scarb_version: &str) -> Result<u64> {
+    let mut files = vec![package_dir.join("Scarb.toml")];
+    let lockfile = package_dir.join("Scarb.lock");
+    if lockfile.exists() {
+        files.push(lockfile);
+    }
+    collect_files(&package_dir.join("src"), &mut files)?;
+    files.sort();
+
+    let mut hasher = DefaultHasher::new();
+    scarb_version.hash(&mut hasher);
+    for file in files {
+        let contents = fs::read(&file).with_context(|| format!("Failed to read {file}"))?;
+        file.strip_prefix(package_dir)?.hash(&mut hasher);
+        contents.hash(&mut hasher);
+    }
+    Ok(hasher.finish())
+}
+
+fn collect_files(dir: &Utf8Path, files: &mut Vec<Utf8PathBuf>) -> Result<()> {
+    for entry in dir
+        .read_dir_utf8()
+        .with_context(|| format!("Failed to read {dir}"))?
+    {
+        let path = entry?.into_path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Runs `scarb build` in the package unless it was already built from the same sources.
+pub fn build_if_changed(package_dir: &Utf8Path) -> Result<()> {
+    let fingerprint = build_fingerprint(package_dir)?.to_string();
+    let fingerprint_path = package_dir.join(FINGERPRINT_FILE);
+    let artifacts_dir = package_dir.join("target/dev");
+
+    if artifacts_dir.exists()
+        && fs::read_to_string(&fingerprint_path).is_ok_and(|stored| stored == fingerprint)
+    {
+        return Ok(());
+    }
+
+    let output = Command::new("scarb")
+        .current_dir(package_dir)
+        .arg("build")
+        .output()
+        .map_err(scarb_error)?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "scarb build failed in {package_dir} with {}\nstdout:\n{}\nstderr:\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    fs::write(&fingerprint_path, fingerprint)
+        .with_context(|| format!("Failed to write {fingerprint_path}"))
+}
+
+fn scarb_error(err: std::io::Error) -> anyhow::Error {
+    match err.kind() {
+        ErrorKind::NotFound => {
+            anyhow!("scarb was not found in PATH, it is required to build the testing contracts")
+        }
+        _ => anyhow!(err).context("Failed to run scarb"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_package() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("Scarb.toml"), "[package]\nname = \"pkg\"\n").unwrap();
+        fs::create_dir_all(dir.path().join("src/nested")).unwrap();
+        fs::write(dir.path().join("src/lib.cairo"), "mod nested;\n").unwrap();
+        fs::write(dir.path().join("src/nested/a.cairo"), "fn a() {}\n").unwrap();
+        dir
+    }
+
+    fn fingerprint(dir: &TempDir) -> u64 {
+        package_fingerprint(Utf8Path::from_path(dir.path()).unwrap(), "scarb 0.6.0").unwrap()
+    }
+
+    #[test]
+    fn fingerprint_is_stable() {
+        let dir = create_package();
+        assert_eq!(fingerprint(&dir), fingerprint(&dir));
+    }
+
+    #[test]
+    fn fingerprint_changes_with_sources() {
+        let dir = create_package();
+        let initial = fingerprint(&dir);
+
+        fs::write(dir.path().join("src/nested/a.cairo"), "fn a() { }\n").unwrap();
+        let edited = fingerprint(&dir);
+        assert_ne!(initial, edited);
+
+        fs::write(dir.path().join("src/nested/b.cairo"), "").unwrap();
+        let added = fingerprint(&dir);
+        assert_ne!(edited, added);
+
+        fs::write(
+            dir.path().join("Scarb.toml"),
+            "[package]\nname = \"pkg\"\nversion = \"0.2.0\"\n",
+        )
+        .unwrap();
+        assert_ne!(added, fingerprint(&dir));
+    }
+
+    #[test]
+    fn fingerprint_changes_with_scarb_version() {
+        let dir = create_package();
+        let package_dir = Utf8Path::from_path(dir.path()).unwrap();
+
+        assert_ne!(
+            package_fingerprint(package_dir, "scarb 0.6.0").unwrap(),
+            package_fingerprint(package_dir, "scarb 0.6.1").unwrap()
+        );
+    }
+
+    #[test]
+    fn fingerprint_ignores_target() {
+        let dir = create_package();
+        let initial = fingerprint(&dir);
+
+        fs::create_dir_all(dir.path().join("target/dev")).unwrap();
+        fs::write(dir.path().join(FINGERPRINT_FILE), initial.to_string()).unwrap();
+        assert_eq!(initial, fingerprint(&dir));
+    }
+}
@@ -1,8 +1,22 @@
 use camino::Utf8PathBuf;
-use cheatnet::CheatnetState;
+use cheatnet::{constants::BlockContextConfig, CheatnetState};
 
 #[allow(clippy::module_name_repetitions)]
 pub fn create_cheatnet_state() -> CheatnetState {
     let predeployed_contracts = Utf8PathBuf::from("predeployed-contracts");
-    CheatnetState::new(&predeployed_contracts)
+    CheatnetState::new(&predeployed_contracts, None)
+}
+
+#[allow(clippy::module_name_repetitions)]
+pub fn create_cheatnet_state_with_block_context_config(
+    block_context_config: BlockContextConfig,
+) -> CheatnetState {
+    let predeployed_contracts = Utf8PathBuf::from("predeployed-contracts");
+    CheatnetState::new_with_block_context_config(&predeployed_contracts, None, block_context_config)
+}
+
+#[allow(clippy::module_name_repetitions)]
+pub fn create_cheatnet_state_with_seed(seed: u64) -> CheatnetState {
+    let predeployed_contracts = Utf8PathBuf::from("predeployed-contracts");
+    CheatnetState::new_with_seed(&predeployed_contracts, None, seed)
 }
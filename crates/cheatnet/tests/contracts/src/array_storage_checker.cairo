@@ -0,0 +1,86 @@
+use array::ArrayTrait;
+use option::OptionTrait;
+use starknet::{StorageBaseAddress, Store, SyscallResult};
+
+// Stores the length of the array at the base address, followed by its elements
+impl StoreFelt252Array of Store<Array<felt252>> {
+    fn read(address_domain: u32, base: StorageBaseAddress) -> SyscallResult<Array<felt252>> {
+        StoreFelt252Array::read_at_offset(address_domain, base, 0)
+    }
+
+    fn write(
+        address_domain: u32, base: StorageBaseAddress, value: Array<felt252>
+    ) -> SyscallResult<()> {
+        StoreFelt252Array::write_at_offset(address_domain, base, 0, value)
+    }
+
+    fn read_at_offset(
+        address_domain: u32, base: StorageBaseAddress, mut offset: u8
+    ) -> SyscallResult<Array<felt252>> {
+        let mut values = ArrayTrait::new();
+        let len: u8 = Store::<u8>::read_at_offset(address_domain, base, offset)
+            .expect('Storage array too large');
+        offset += 1;
+        let end = offset + len;
+        loop {
+            if offset >= end {
+                break;
+            }
+            values.append(Store::<felt252>::read_at_offset(address_domain, base, offset).unwrap());
+            offset += 1;
+        };
+        Result::Ok(values)
+    }
+
+    fn write_at_offset(
+        address_domain: u32, base: StorageBaseAddress, mut offset: u8, mut value: Array<felt252>
+    ) -> SyscallResult<()> {
+        let len: u8 = value.len().try_into().expect('Storage array too large');
+        Store::<u8>::write_at_offset(address_domain, base, offset, len).unwrap();
+        offset += 1;
+        loop {
+            match value.pop_front() {
+                Option::Some(element) => {
+                    Store::<felt252>::write_at_offset(address_domain, base, offset, element)
+                        .unwrap();
+                    offset += 1;
+                },
+                Option::None => {
+                    break;
+                }
+            };
+        };
+        Result::Ok(())
+    }
+
+    fn size() -> u8 {
+        255
+    }
+}
+
+#[starknet::interface]
+trait IArrayStorageChecker<TContractState> {
+    fn get_values(self: @TContractState) -> Array<felt252>;
+    fn set_values(ref self: TContractState, values: Array<felt252>);
+}
+
+#[starknet::contract]
+mod ArrayStorageChecker {
+    use super::StoreFelt252Array;
+
+    #[storage]
+    struct Storage {
+        values: Array<felt252>,
+    }
+
+    #[external(v0)]
+    impl IArrayStorageChecker of super::IArrayStorageChecker<ContractState> {
+        fn get_values(self: @ContractState) -> Array<felt252> {
+            self.values.read()
+        }
+
+        fn set_values(ref self: ContractState, values: Array<felt252>) {
+            self.values.write(values);
+        }
+    }
+}
@@ -0,0 +1,20 @@
+#[starknet::interface]
+trait IBlockHashChecker<TContractState> {
+    fn get_block_hash(self: @TContractState, block_number: u64) -> felt252;
+}
+
+#[starknet::contract]
+mod BlockHashChecker {
+    use starknet::SyscallResultTrait;
+    use starknet::syscalls::get_block_hash_syscall;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    impl IBlockHashChecker of super::IBlockHashChecker<ContractState> {
+        fn get_block_hash(self: @ContractState, block_number: u64) -> felt252 {
+            get_block_hash_syscall(block_number).unwrap_syscall()
+        }
+    }
+}
@@ -0,0 +1,31 @@
+#[starknet::interface]
+trait IConstructorChainIdChecker<TContractState> {
+    fn get_stored_chain_id(self: @TContractState) -> felt252;
+    fn get_chain_id(self: @TContractState) -> felt252;
+}
+
+#[starknet::contract]
+mod ConstructorChainIdChecker {
+    use box::BoxTrait;
+    #[storage]
+    struct Storage {
+        chain_id: felt252,
+    }
+
+    #[constructor]
+    fn constructor(ref self: ContractState) {
+        let chain_id = starknet::get_tx_info().unbox().chain_id;
+        self.chain_id.write(chain_id);
+    }
+
+    #[external(v0)]
+    impl IConstructorChainIdChecker of super::IConstructorChainIdChecker<ContractState> {
+        fn get_stored_chain_id(self: @ContractState) -> felt252 {
+            self.chain_id.read()
+        }
+
+        fn get_chain_id(self: @ContractState) -> felt252 {
+            starknet::get_tx_info().unbox().chain_id
+        }
+    }
+}
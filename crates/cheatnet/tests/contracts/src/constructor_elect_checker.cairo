@@ -0,0 +1,30 @@
+use starknet::ContractAddress;
+
+#[starknet::interface]
+trait IConstructorElectChecker<TContractState> {
+    fn get_stored_sequencer_address(ref self: TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+mod ConstructorElectChecker {
+    use box::BoxTrait;
+    use starknet::ContractAddress;
+
+    #[storage]
+    struct Storage {
+        sequencer_address: ContractAddress,
+    }
+
+    #[constructor]
+    fn constructor(ref self: ContractState) {
+        let sequencer_address = starknet::get_block_info().unbox().sequencer_address;
+        self.sequencer_address.write(sequencer_address);
+    }
+
+    #[external(v0)]
+    impl IConstructorElectChecker of super::IConstructorElectChecker<ContractState> {
+        fn get_stored_sequencer_address(ref self: ContractState) -> ContractAddress {
+            self.sequencer_address.read()
+        }
+    }
+}
@@ -0,0 +1,21 @@
+#[starknet::contract]
+mod ConstructorEventEmitter {
+    #[storage]
+    struct Storage {}
+
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        Initialized: Initialized
+    }
+
+    #[derive(Drop, starknet::Event)]
+    struct Initialized {
+        value: felt252
+    }
+
+    #[constructor]
+    fn constructor(ref self: ContractState, value: felt252) {
+        self.emit(Event::Initialized(Initialized { value }));
+    }
+}
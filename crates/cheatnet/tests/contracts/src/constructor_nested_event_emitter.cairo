@@ -0,0 +1,41 @@
+use starknet::ContractAddress;
+
+#[starknet::interface]
+trait ISpyEventsChecker<TContractState> {
+    fn emit_one_event(ref self: TContractState, some_data: felt252);
+}
+
+#[starknet::contract]
+mod ConstructorNestedEventEmitter {
+    use starknet::ContractAddress;
+    use super::ISpyEventsCheckerDispatcherTrait;
+    use super::ISpyEventsCheckerDispatcher;
+
+    #[storage]
+    struct Storage {}
+
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        Initialized: Initialized,
+        Finished: Finished
+    }
+
+    #[derive(Drop, starknet::Event)]
+    struct Initialized {
+        value: felt252
+    }
+
+    #[derive(Drop, starknet::Event)]
+    struct Finished {
+        value: felt252
+    }
+
+    #[constructor]
+    fn constructor(ref self: ContractState, value: felt252, other: ContractAddress) {
+        self.emit(Event::Initialized(Initialized { value }));
+        let spy_events_checker = ISpyEventsCheckerDispatcher { contract_address: other };
+        spy_events_checker.emit_one_event(value + 1);
+        self.emit(Event::Finished(Finished { value: value + 2 }));
+    }
+}
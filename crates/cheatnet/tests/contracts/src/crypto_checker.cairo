@@ -0,0 +1,29 @@
+#[starknet::interface]
+trait ICryptoChecker<TContractState> {
+    fn keccak_u256s(self: @TContractState, input: Array<u256>) -> u256;
+    fn secp256k1_y_from_x(self: @TContractState, x: u256, y_parity: bool) -> u256;
+}
+
+#[starknet::contract]
+mod CryptoChecker {
+    use array::ArrayTrait;
+    use option::OptionTrait;
+    use result::ResultTrait;
+    use starknet::secp256k1::{secp256k1_get_point_from_x_syscall, secp256k1_get_xy_syscall};
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    impl ICryptoChecker of super::ICryptoChecker<ContractState> {
+        fn keccak_u256s(self: @ContractState, input: Array<u256>) -> u256 {
+            keccak::keccak_u256s_le_inputs(input.span())
+        }
+
+        fn secp256k1_y_from_x(self: @ContractState, x: u256, y_parity: bool) -> u256 {
+            let point = secp256k1_get_point_from_x_syscall(x, y_parity).unwrap().unwrap();
+            let (_, y) = secp256k1_get_xy_syscall(point).unwrap();
+            y
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use starknet::ContractAddress;
+
+#[starknet::interface]
+trait IElectChecker<TContractState> {
+    fn get_sequencer_address(ref self: TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+mod ElectChecker {
+    use box::BoxTrait;
+    use starknet::ContractAddress;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    impl IElectChecker of super::IElectChecker<ContractState> {
+        fn get_sequencer_address(ref self: ContractState) -> ContractAddress {
+            starknet::get_block_info().unbox().sequencer_address
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use starknet::ContractAddress;
+
+#[starknet::interface]
+trait IErc20BalancesChecker<TContractState> {
+    fn balance_of(self: @TContractState, account: ContractAddress) -> u256;
+}
+
+// Stores balances the same way the fee token does
+#[starknet::contract]
+mod Erc20BalancesChecker {
+    use starknet::ContractAddress;
+
+    #[storage]
+    struct Storage {
+        ERC20_balances: LegacyMap::<ContractAddress, u256>,
+    }
+
+    #[external(v0)]
+    impl IErc20BalancesChecker of super::IErc20BalancesChecker<ContractState> {
+        fn balance_of(self: @ContractState, account: ContractAddress) -> u256 {
+            self.ERC20_balances.read(account)
+        }
+    }
+}
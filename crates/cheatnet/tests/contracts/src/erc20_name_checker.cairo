@@ -0,0 +1,28 @@
+use starknet::ContractAddress;
+
+#[starknet::interface]
+trait IERC20Name<TContractState> {
+    fn name(self: @TContractState) -> felt252;
+}
+
+#[starknet::interface]
+trait IErc20NameChecker<TContractState> {
+    fn get_name(self: @TContractState, token: ContractAddress) -> felt252;
+}
+
+#[starknet::contract]
+mod Erc20NameChecker {
+    use starknet::ContractAddress;
+    use super::IERC20NameDispatcherTrait;
+    use super::IERC20NameDispatcher;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    impl IErc20NameChecker of super::IErc20NameChecker<ContractState> {
+        fn get_name(self: @ContractState, token: ContractAddress) -> felt252 {
+            IERC20NameDispatcher { contract_address: token }.name()
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use starknet::ContractAddress;
+
+#[starknet::interface]
+trait IExecutionInfoChecker<TContractState> {
+    fn get_block_number(self: @TContractState) -> u64;
+    fn get_caller_address(self: @TContractState) -> ContractAddress;
+    fn get_transaction_hash(self: @TContractState) -> felt252;
+    fn get_nonce(self: @TContractState) -> felt252;
+}
+
+#[starknet::contract]
+mod ExecutionInfoChecker {
+    use box::BoxTrait;
+    use starknet::{ContractAddress, get_block_info, get_caller_address, get_tx_info};
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    impl IExecutionInfoChecker of super::IExecutionInfoChecker<ContractState> {
+        fn get_block_number(self: @ContractState) -> u64 {
+            get_block_info().unbox().block_number
+        }
+
+        fn get_caller_address(self: @ContractState) -> ContractAddress {
+            get_caller_address()
+        }
+
+        fn get_transaction_hash(self: @ContractState) -> felt252 {
+            get_tx_info().unbox().transaction_hash
+        }
+
+        fn get_nonce(self: @ContractState) -> felt252 {
+            get_tx_info().unbox().nonce
+        }
+    }
+}
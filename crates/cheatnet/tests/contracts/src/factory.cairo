@@ -0,0 +1,46 @@
+use starknet::ContractAddress;
+
+#[starknet::interface]
+trait IFactory<TContractState> {
+    fn get_first_child(self: @TContractState) -> ContractAddress;
+    fn get_second_child(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+mod Factory {
+    use array::ArrayTrait;
+    use starknet::{ClassHash, ContractAddress, SyscallResultTrait};
+
+    #[storage]
+    struct Storage {
+        first_child: ContractAddress,
+        second_child: ContractAddress,
+    }
+
+    #[constructor]
+    fn constructor(ref self: ContractState, child_class_hash: ClassHash) {
+        self.first_child.write(deploy_child(child_class_hash, 1));
+        self.second_child.write(deploy_child(child_class_hash, 2));
+    }
+
+    fn deploy_child(class_hash: ClassHash, value: felt252) -> ContractAddress {
+        let mut calldata = ArrayTrait::new();
+        calldata.append(value);
+        let (contract_address, _) = starknet::deploy_syscall(
+            class_hash, value, calldata.span(), false
+        )
+            .unwrap_syscall();
+        contract_address
+    }
+
+    #[external(v0)]
+    impl IFactory of super::IFactory<ContractState> {
+        fn get_first_child(self: @ContractState) -> ContractAddress {
+            self.first_child.read()
+        }
+
+        fn get_second_child(self: @ContractState) -> ContractAddress {
+            self.second_child.read()
+        }
+    }
+}
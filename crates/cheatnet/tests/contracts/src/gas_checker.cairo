@@ -0,0 +1,26 @@
+#[starknet::interface]
+trait IGasChecker<TContractState> {
+    fn sum_up_to(self: @TContractState, n: felt252) -> felt252;
+}
+
+#[starknet::contract]
+mod GasChecker {
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    impl IGasChecker of super::IGasChecker<ContractState> {
+        fn sum_up_to(self: @ContractState, n: felt252) -> felt252 {
+            let mut sum = 0;
+            let mut i = 0;
+            loop {
+                if i == n {
+                    break;
+                }
+                i += 1;
+                sum += i;
+            };
+            sum
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use starknet::ContractAddress;
+
+#[starknet::interface]
+trait IHelloStarknet<TContractState> {
+    fn increase_balance(ref self: TContractState, amount: felt252);
+}
+
+#[starknet::interface]
+trait IHelloStarknetProxy<TContractState> {
+    fn increase_balance(ref self: TContractState, address: ContractAddress, amount: felt252);
+}
+
+#[starknet::contract]
+mod HelloStarknetProxy {
+    use starknet::ContractAddress;
+    use super::IHelloStarknetDispatcherTrait;
+    use super::IHelloStarknetDispatcher;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    impl IHelloStarknetProxy of super::IHelloStarknetProxy<ContractState> {
+        fn increase_balance(ref self: ContractState, address: ContractAddress, amount: felt252) {
+            let hello_starknet = IHelloStarknetDispatcher { contract_address: address };
+            hello_starknet.increase_balance(amount);
+        }
+    }
+}
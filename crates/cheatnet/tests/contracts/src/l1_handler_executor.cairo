@@ -0,0 +1,32 @@
+#[starknet::interface]
+trait IL1HandlerExecutor<TContractState> {
+    fn get_l1_caller_address(self: @TContractState) -> felt252;
+    fn get_value(self: @TContractState) -> felt252;
+}
+
+#[starknet::contract]
+mod L1HandlerExecutor {
+    #[storage]
+    struct Storage {
+        l1_caller_address: felt252,
+        value: felt252,
+    }
+
+    #[l1_handler]
+    fn process_l1_message(ref self: ContractState, from_address: felt252, value: felt252) {
+        assert(value != 0, 'Value cannot be 0');
+        self.l1_caller_address.write(from_address);
+        self.value.write(value);
+    }
+
+    #[external(v0)]
+    impl IL1HandlerExecutor of super::IL1HandlerExecutor<ContractState> {
+        fn get_l1_caller_address(self: @ContractState) -> felt252 {
+            self.l1_caller_address.read()
+        }
+
+        fn get_value(self: @ContractState) -> felt252 {
+            self.value.read()
+        }
+    }
+}
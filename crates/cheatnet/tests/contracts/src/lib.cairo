@@ -22,3 +22,67 @@ mod mock_checker_library_call;
 mod mock_checker_proxy;
 
 mod get_class_hash_checker;
+
+mod constructor_event_emitter;
+
+mod spy_events_checker;
+
+mod replace_class_a;
+mod replace_class_b;
+
+mod elect_checker;
+mod constructor_elect_checker;
+
+mod spoof_checker;
+
+mod l1_handler_executor;
+
+mod message_to_l1_checker;
+
+mod hello_starknet_proxy;
+
+mod constructor_nested_event_emitter;
+
+mod block_hash_checker;
+
+mod multicall_account;
+
+mod signature_checking_account;
+
+mod erc20_balances_checker;
+
+mod validating_account;
+
+mod reentrancy_checker;
+
+mod retdata_checker;
+
+mod crypto_checker;
+
+mod library_call_logic;
+
+mod library_call_proxy;
+
+mod gas_checker;
+
+mod execution_info_checker;
+
+mod constructor_chain_id_checker;
+
+mod erc20_name_checker;
+
+mod u256_adder;
+
+mod struct_storage_checker;
+
+mod time_lock;
+
+mod factory;
+
+mod shape_matcher;
+
+mod print_checker;
+
+mod array_storage_checker;
+
+mod tx_info_validating_account;
@@ -0,0 +1,38 @@
+use starknet::ContractAddress;
+
+#[starknet::interface]
+trait ILibraryCallLogic<TContractState> {
+    fn set_value(ref self: TContractState, value: felt252);
+    fn get_value(self: @TContractState) -> felt252;
+    fn get_seen_addresses(self: @TContractState) -> (ContractAddress, ContractAddress);
+}
+
+#[starknet::contract]
+mod LibraryCallLogic {
+    use starknet::{ContractAddress, get_caller_address, get_contract_address};
+
+    #[storage]
+    struct Storage {
+        value: felt252,
+        // Addresses seen by the last `set_value`
+        seen_contract_address: ContractAddress,
+        seen_caller_address: ContractAddress,
+    }
+
+    #[external(v0)]
+    impl ILibraryCallLogic of super::ILibraryCallLogic<ContractState> {
+        fn set_value(ref self: ContractState, value: felt252) {
+            self.value.write(value);
+            self.seen_contract_address.write(get_contract_address());
+            self.seen_caller_address.write(get_caller_address());
+        }
+
+        fn get_value(self: @ContractState) -> felt252 {
+            self.value.read()
+        }
+
+        fn get_seen_addresses(self: @ContractState) -> (ContractAddress, ContractAddress) {
+            (self.seen_contract_address.read(), self.seen_caller_address.read())
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use starknet::ClassHash;
+
+#[starknet::interface]
+trait ILibraryCallLogic<TContractState> {
+    fn set_value(ref self: TContractState, value: felt252);
+}
+
+#[starknet::interface]
+trait ILibraryCallProxy<TContractState> {
+    fn set_value_with_lib_call(ref self: TContractState, class_hash: ClassHash, value: felt252);
+}
+
+// Executes the code of the logic class in its own storage context
+#[starknet::contract]
+mod LibraryCallProxy {
+    use super::{ILibraryCallLogicDispatcherTrait, ILibraryCallLogicLibraryDispatcher};
+    use starknet::ClassHash;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    impl ILibraryCallProxy of super::ILibraryCallProxy<ContractState> {
+        fn set_value_with_lib_call(ref self: ContractState, class_hash: ClassHash, value: felt252) {
+            let logic = ILibraryCallLogicLibraryDispatcher { class_hash };
+            logic.set_value(value);
+        }
+    }
+}
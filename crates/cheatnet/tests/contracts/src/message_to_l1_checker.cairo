@@ -0,0 +1,38 @@
+use starknet::ContractAddress;
+
+#[starknet::interface]
+trait IMessageToL1Checker<TContractState> {
+    fn send_message(ref self: TContractState, to_address: felt252, payload: Array<felt252>);
+    fn send_and_call_other(
+        ref self: TContractState, to_address: felt252, payload: Array<felt252>, other: ContractAddress
+    );
+}
+
+#[starknet::contract]
+mod MessageToL1Checker {
+    use array::ArrayTrait;
+    use starknet::{ContractAddress, SyscallResultTrait};
+    use super::IMessageToL1CheckerDispatcherTrait;
+    use super::IMessageToL1CheckerDispatcher;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    impl IMessageToL1Checker of super::IMessageToL1Checker<ContractState> {
+        fn send_message(ref self: ContractState, to_address: felt252, payload: Array<felt252>) {
+            starknet::send_message_to_l1_syscall(to_address, payload.span()).unwrap_syscall();
+        }
+
+        fn send_and_call_other(
+            ref self: ContractState,
+            to_address: felt252,
+            payload: Array<felt252>,
+            other: ContractAddress
+        ) {
+            starknet::send_message_to_l1_syscall(to_address, payload.span()).unwrap_syscall();
+            let message_to_l1_checker = IMessageToL1CheckerDispatcher { contract_address: other };
+            message_to_l1_checker.send_message(to_address + 1, payload);
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use starknet::ContractAddress;
+
+#[derive(Drop, Serde)]
+struct Call {
+    to: ContractAddress,
+    selector: felt252,
+    calldata: Array<felt252>,
+}
+
+#[starknet::contract]
+mod MulticallAccount {
+    use array::{ArrayTrait, SpanTrait};
+    use option::OptionTrait;
+    use starknet::SyscallResultTrait;
+    use super::Call;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    fn __validate__(self: @ContractState, calls: Array<Call>) -> felt252 {
+        'VALID'
+    }
+
+    #[external(v0)]
+    fn __validate_declare__(self: @ContractState, class_hash: felt252) -> felt252 {
+        'VALID'
+    }
+
+    #[external(v0)]
+    fn __execute__(ref self: ContractState, mut calls: Array<Call>) -> Array<Span<felt252>> {
+        let mut results = ArrayTrait::new();
+        loop {
+            match calls.pop_front() {
+                Option::Some(call) => {
+                    let Call{to, selector, calldata } = call;
+                    let result = starknet::call_contract_syscall(to, selector, calldata.span())
+                        .unwrap_syscall();
+                    results.append(result);
+                },
+                Option::None => {
+                    break;
+                },
+            };
+        };
+        results
+    }
+}
@@ -10,11 +10,16 @@ trait IPrankChecker<TContractState> {
 #[starknet::interface]
 trait IPrankCheckerProxy<TContractState> {
     fn get_prank_checkers_caller_address(ref self: TContractState, address: ContractAddress) -> felt252;
+    fn get_own_and_prank_checkers_caller_address(
+        ref self: TContractState, address: ContractAddress
+    ) -> (felt252, felt252);
 }
 
 #[starknet::contract]
 mod PrankCheckerProxy {
     use starknet::ContractAddress;
+    use starknet::ContractAddressIntoFelt252;
+    use traits::Into;
     use super::IPrankCheckerDispatcherTrait;
     use super::IPrankCheckerDispatcher;
 
@@ -27,5 +32,12 @@ mod PrankCheckerProxy {
             let prank_checker = IPrankCheckerDispatcher { contract_address: address };
             prank_checker.get_caller_address()
         }
+
+        fn get_own_and_prank_checkers_caller_address(
+            ref self: ContractState, address: ContractAddress
+        ) -> (felt252, felt252) {
+            let prank_checker = IPrankCheckerDispatcher { contract_address: address };
+            (starknet::get_caller_address().into(), prank_checker.get_caller_address())
+        }
     }
 }
@@ -0,0 +1,21 @@
+#[starknet::interface]
+trait IPrintChecker<TContractState> {
+    fn print_value(self: @TContractState, value: felt252) -> felt252;
+}
+
+#[starknet::contract]
+mod PrintChecker {
+    use debug::PrintTrait;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    impl IPrintChecker of super::IPrintChecker<ContractState> {
+        fn print_value(self: @ContractState, value: felt252) -> felt252 {
+            'Value is'.print();
+            value.print();
+            value
+        }
+    }
+}
@@ -0,0 +1,32 @@
+#[starknet::interface]
+trait IReentrancyChecker<TContractState> {
+    fn reenter(ref self: TContractState, times: felt252);
+    fn get_value(self: @TContractState) -> felt252;
+}
+
+#[starknet::contract]
+mod ReentrancyChecker {
+    use starknet::get_contract_address;
+    use super::IReentrancyCheckerDispatcherTrait;
+    use super::IReentrancyCheckerDispatcher;
+
+    #[storage]
+    struct Storage {
+        value: felt252
+    }
+
+    #[external(v0)]
+    impl IReentrancyChecker of super::IReentrancyChecker<ContractState> {
+        fn reenter(ref self: ContractState, times: felt252) {
+            let this = IReentrancyCheckerDispatcher { contract_address: get_contract_address() };
+            self.value.write(this.get_value() + 1);
+            if times != 0 {
+                this.reenter(times - 1);
+            }
+        }
+
+        fn get_value(self: @ContractState) -> felt252 {
+            self.value.read()
+        }
+    }
+}
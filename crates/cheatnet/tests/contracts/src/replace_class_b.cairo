@@ -0,0 +1,24 @@
+#[starknet::interface]
+trait IReplaceClass<TContractState> {
+    fn set_value(ref self: TContractState, value: felt252);
+    fn get_value(self: @TContractState) -> felt252;
+}
+
+#[starknet::contract]
+mod ReplaceClassB {
+    #[storage]
+    struct Storage {
+        value: felt252,
+    }
+
+    #[external(v0)]
+    impl IReplaceClass of super::IReplaceClass<ContractState> {
+        fn set_value(ref self: ContractState, value: felt252) {
+            self.value.write(value);
+        }
+
+        fn get_value(self: @ContractState) -> felt252 {
+            self.value.read() + 100
+        }
+    }
+}
@@ -0,0 +1,33 @@
+#[starknet::interface]
+trait IRetdataChecker<TContractState> {
+    fn get_u256(self: @TContractState) -> u256;
+    fn get_array(self: @TContractState, len: felt252) -> Array<felt252>;
+}
+
+#[starknet::contract]
+mod RetdataChecker {
+    use array::ArrayTrait;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    impl IRetdataChecker of super::IRetdataChecker<ContractState> {
+        fn get_u256(self: @ContractState) -> u256 {
+            u256 { low: 123, high: 456 }
+        }
+
+        fn get_array(self: @ContractState, len: felt252) -> Array<felt252> {
+            let mut array = ArrayTrait::new();
+            let mut i = 0;
+            loop {
+                if i == len {
+                    break;
+                }
+                array.append(i * 10);
+                i += 1;
+            };
+            array
+        }
+    }
+}
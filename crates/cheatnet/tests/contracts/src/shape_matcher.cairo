@@ -0,0 +1,31 @@
+#[derive(Drop, Serde)]
+enum Shape {
+    Point: (),
+    Circle: felt252,
+    Rectangle: (felt252, felt252),
+}
+
+#[starknet::interface]
+trait IShapeMatcher<TContractState> {
+    fn describe(self: @TContractState, shape: Shape) -> (felt252, felt252);
+}
+
+#[starknet::contract]
+mod ShapeMatcher {
+    use super::Shape;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    impl IShapeMatcher of super::IShapeMatcher<ContractState> {
+        // Returns the variant index and the sum of the payload
+        fn describe(self: @ContractState, shape: Shape) -> (felt252, felt252) {
+            match shape {
+                Shape::Point(()) => (0, 0),
+                Shape::Circle(radius) => (1, radius),
+                Shape::Rectangle((width, height)) => (2, width + height),
+            }
+        }
+    }
+}
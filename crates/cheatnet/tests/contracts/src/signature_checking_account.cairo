@@ -0,0 +1,46 @@
+#[starknet::contract]
+mod SignatureCheckingAccount {
+    use array::{ArrayTrait, SpanTrait};
+    use box::BoxTrait;
+    use starknet::{ContractAddress, SyscallResultTrait};
+
+    #[storage]
+    struct Storage {
+        signer: felt252,
+    }
+
+    #[constructor]
+    fn constructor(ref self: ContractState, signer: felt252) {
+        self.signer.write(signer);
+    }
+
+    #[external(v0)]
+    fn __validate__(
+        self: @ContractState,
+        contract_address: ContractAddress,
+        selector: felt252,
+        calldata: Array<felt252>
+    ) -> felt252 {
+        let signature = starknet::get_tx_info().unbox().signature;
+        assert(signature.len() == 1, 'Invalid signature length');
+        assert(*signature.at(0) == self.signer.read(), 'Invalid signature');
+        'VALID'
+    }
+
+    // Declarations are sent without a signature
+    #[external(v0)]
+    fn __validate_declare__(self: @ContractState, class_hash: felt252) -> felt252 {
+        'VALID'
+    }
+
+    #[external(v0)]
+    fn __execute__(
+        ref self: ContractState,
+        contract_address: ContractAddress,
+        selector: felt252,
+        calldata: Array<felt252>
+    ) -> Span<felt252> {
+        starknet::call_contract_syscall(contract_address, selector, calldata.span())
+            .unwrap_syscall()
+    }
+}
@@ -0,0 +1,34 @@
+#[starknet::interface]
+trait ISpoofChecker<TContractState> {
+    fn get_transaction_hash(self: @TContractState) -> felt252;
+    fn get_signature(self: @TContractState) -> Span<felt252>;
+    fn get_nonce(self: @TContractState) -> felt252;
+    fn get_chain_id(self: @TContractState) -> felt252;
+}
+
+#[starknet::contract]
+mod SpoofChecker {
+    use box::BoxTrait;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    impl ISpoofChecker of super::ISpoofChecker<ContractState> {
+        fn get_transaction_hash(self: @ContractState) -> felt252 {
+            starknet::get_tx_info().unbox().transaction_hash
+        }
+
+        fn get_signature(self: @ContractState) -> Span<felt252> {
+            starknet::get_tx_info().unbox().signature
+        }
+
+        fn get_nonce(self: @ContractState) -> felt252 {
+            starknet::get_tx_info().unbox().nonce
+        }
+
+        fn get_chain_id(self: @ContractState) -> felt252 {
+            starknet::get_tx_info().unbox().chain_id
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use starknet::ContractAddress;
+
+#[starknet::interface]
+trait ISpyEventsChecker<TContractState> {
+    fn emit_one_event(ref self: TContractState, some_data: felt252);
+    fn emit_and_call_other(ref self: TContractState, some_data: felt252, other: ContractAddress);
+}
+
+#[starknet::contract]
+mod SpyEventsChecker {
+    use starknet::ContractAddress;
+    use super::ISpyEventsCheckerDispatcherTrait;
+    use super::ISpyEventsCheckerDispatcher;
+
+    #[storage]
+    struct Storage {}
+
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        FirstEvent: FirstEvent
+    }
+
+    #[derive(Drop, starknet::Event)]
+    struct FirstEvent {
+        some_data: felt252
+    }
+
+    #[external(v0)]
+    impl ISpyEventsChecker of super::ISpyEventsChecker<ContractState> {
+        fn emit_one_event(ref self: ContractState, some_data: felt252) {
+            self.emit(Event::FirstEvent(FirstEvent { some_data }));
+        }
+
+        fn emit_and_call_other(ref self: ContractState, some_data: felt252, other: ContractAddress) {
+            self.emit(Event::FirstEvent(FirstEvent { some_data }));
+            let spy_events_checker = ISpyEventsCheckerDispatcher { contract_address: other };
+            spy_events_checker.emit_one_event(some_data + 1);
+        }
+    }
+}
@@ -0,0 +1,28 @@
+#[derive(Copy, Drop, Serde, starknet::Store)]
+struct Position {
+    x: felt252,
+    y: felt252,
+    z: felt252,
+}
+
+#[starknet::interface]
+trait IStructStorageChecker<TContractState> {
+    fn get_position(self: @TContractState, key: felt252) -> Position;
+}
+
+#[starknet::contract]
+mod StructStorageChecker {
+    use super::Position;
+
+    #[storage]
+    struct Storage {
+        positions: LegacyMap<felt252, Position>,
+    }
+
+    #[external(v0)]
+    impl IStructStorageChecker of super::IStructStorageChecker<ContractState> {
+        fn get_position(self: @ContractState, key: felt252) -> Position {
+            self.positions.read(key)
+        }
+    }
+}
@@ -0,0 +1,34 @@
+#[starknet::interface]
+trait ITimeLock<TContractState> {
+    fn unlock(ref self: TContractState);
+    fn is_unlocked(self: @TContractState) -> bool;
+}
+
+#[starknet::contract]
+mod TimeLock {
+    use box::BoxTrait;
+
+    #[storage]
+    struct Storage {
+        unlock_timestamp: u64,
+        unlocked: bool,
+    }
+
+    #[constructor]
+    fn constructor(ref self: ContractState, unlock_timestamp: u64) {
+        self.unlock_timestamp.write(unlock_timestamp);
+    }
+
+    #[external(v0)]
+    impl ITimeLock of super::ITimeLock<ContractState> {
+        fn unlock(ref self: ContractState) {
+            let block_timestamp = starknet::get_block_info().unbox().block_timestamp;
+            assert(block_timestamp >= self.unlock_timestamp.read(), 'Still locked');
+            self.unlocked.write(true);
+        }
+
+        fn is_unlocked(self: @ContractState) -> bool {
+            self.unlocked.read()
+        }
+    }
+}
@@ -0,0 +1,41 @@
+#[starknet::contract]
+mod TxInfoValidatingAccount {
+    use array::{ArrayTrait, SpanTrait};
+    use box::BoxTrait;
+    use starknet::{ContractAddress, SyscallResultTrait};
+
+    #[storage]
+    struct Storage {}
+
+    // Accepts only validations with a zero caller, like the ones run by the protocol,
+    // and returns the transaction hash
+    #[external(v0)]
+    fn __validate__(
+        self: @ContractState,
+        contract_address: ContractAddress,
+        selector: felt252,
+        calldata: Array<felt252>
+    ) -> felt252 {
+        let caller_address: felt252 = starknet::get_caller_address().into();
+        assert(caller_address == 0, 'Caller is not zero');
+        let tx_info = starknet::get_tx_info().unbox();
+        assert(tx_info.signature.len() == 1, 'Invalid signature length');
+        tx_info.transaction_hash
+    }
+
+    #[external(v0)]
+    fn __validate_declare__(self: @ContractState, class_hash: felt252) -> felt252 {
+        'VALID'
+    }
+
+    #[external(v0)]
+    fn __execute__(
+        ref self: ContractState,
+        contract_address: ContractAddress,
+        selector: felt252,
+        calldata: Array<felt252>
+    ) -> Span<felt252> {
+        starknet::call_contract_syscall(contract_address, selector, calldata.span())
+            .unwrap_syscall()
+    }
+}
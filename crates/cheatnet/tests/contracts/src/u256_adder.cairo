@@ -0,0 +1,17 @@
+#[starknet::interface]
+trait IU256Adder<TContractState> {
+    fn add(self: @TContractState, a: u256, b: u256) -> u256;
+}
+
+#[starknet::contract]
+mod U256Adder {
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    impl IU256Adder of super::IU256Adder<ContractState> {
+        fn add(self: @ContractState, a: u256, b: u256) -> u256 {
+            a + b
+        }
+    }
+}
@@ -0,0 +1,42 @@
+#[starknet::contract]
+mod ValidatingAccount {
+    use array::{ArrayTrait, SpanTrait};
+    use starknet::{ContractAddress, SyscallResultTrait};
+
+    #[storage]
+    struct Storage {
+        validate_count: felt252,
+    }
+
+    #[external(v0)]
+    fn __validate__(
+        ref self: ContractState,
+        contract_address: ContractAddress,
+        selector: felt252,
+        calldata: Array<felt252>
+    ) -> felt252 {
+        self.validate_count.write(self.validate_count.read() + 1);
+        'VALID'
+    }
+
+    #[external(v0)]
+    fn __validate_declare__(self: @ContractState, class_hash: felt252) -> felt252 {
+        'VALID'
+    }
+
+    #[external(v0)]
+    fn __execute__(
+        ref self: ContractState,
+        contract_address: ContractAddress,
+        selector: felt252,
+        calldata: Array<felt252>
+    ) -> Span<felt252> {
+        starknet::call_contract_syscall(contract_address, selector, calldata.span())
+            .unwrap_syscall()
+    }
+
+    #[external(v0)]
+    fn get_validate_count(self: @ContractState) -> felt252 {
+        self.validate_count.read()
+    }
+}
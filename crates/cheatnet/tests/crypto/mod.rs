@@ -0,0 +1,76 @@
+use crate::{
+    assert_success,
+    common::{deploy_contract, state::create_cheatnet_state},
+};
+use cairo_felt::Felt252;
+use cheatnet::{
+    conversions::{felt_selector_from_name, CalldataBuilder},
+    rpc::call_contract,
+};
+use sha3::{Digest, Keccak256};
+
+// Splits a big-endian hex number into the parts of a u256
+fn u256_from_hex(hex: &str) -> (u128, u128) {
+    let (high, low) = hex.split_at(hex.len() - 32);
+    (
+        u128::from_str_radix(low, 16).unwrap(),
+        u128::from_str_radix(high, 16).unwrap(),
+    )
+}
+
+#[test]
+fn keccak_syscall() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "CryptoChecker", &[]);
+
+    let input: [(u128, u128); 2] = [(1, 2), (u128::MAX, 0xabc)];
+    let mut calldata = CalldataBuilder::new().add_felt(Felt252::from(input.len()));
+    for (low, high) in input {
+        calldata = calldata.add_u256(low, high);
+    }
+
+    let output = call_contract(
+        &contract_address,
+        &felt_selector_from_name("keccak_u256s"),
+        &calldata.build(),
+        &mut state,
+    )
+    .unwrap();
+
+    // Inputs are hashed as little-endian words and the hash is returned as a little-endian u256
+    let mut hasher = Keccak256::new();
+    for (low, high) in input {
+        hasher.update(low.to_le_bytes());
+        hasher.update(high.to_le_bytes());
+    }
+    let hash = hasher.finalize();
+    let low = u128::from_le_bytes(hash[..16].try_into().unwrap());
+    let high = u128::from_le_bytes(hash[16..].try_into().unwrap());
+
+    assert_success!(output, vec![Felt252::from(low), Felt252::from(high)]);
+}
+
+#[test]
+fn secp256k1_syscalls() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "CryptoChecker", &[]);
+
+    // Generator point of secp256k1
+    let (x_low, x_high) =
+        u256_from_hex("79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798");
+    let (y_low, y_high) =
+        u256_from_hex("483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8");
+
+    let output = call_contract(
+        &contract_address,
+        &felt_selector_from_name("secp256k1_y_from_x"),
+        &CalldataBuilder::new()
+            .add_u256(x_low, x_high)
+            .add_felt(Felt252::from(0))
+            .build(),
+        &mut state,
+    )
+    .unwrap();
+
+    assert_success!(output, vec![Felt252::from(y_low), Felt252::from(y_high)]);
+}
@@ -0,0 +1,112 @@
+use crate::{assert_success, common::deploy_contract};
+use cairo_felt::Felt252;
+use camino::Utf8PathBuf;
+use cheatnet::conversions::{
+    contract_address_to_felt, felt_from_short_string, felt_selector_from_name, map_entry_address,
+};
+use cheatnet::forking::state::ForkConfig;
+use cheatnet::rpc::{call_contract, CallContractOutput};
+use cheatnet::CheatnetState;
+use starknet_api::block::BlockNumber;
+use starknet_api::core::{ContractAddress, PatriciaKey};
+use starknet_api::hash::StarkHash;
+use starknet_api::patricia_key;
+use url::Url;
+
+// ETH fee token on the Sepolia testnet, a Cairo 0 proxy
+const ETH_CONTRACT_ADDRESS: &str =
+    "0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7";
+
+// STRK fee token on the Sepolia testnet, its class is a Sierra class
+const STRK_CONTRACT_ADDRESS: &str =
+    "0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d";
+
+fn create_forked_cheatnet_state() -> CheatnetState {
+    let url = std::env::var("CHEATNET_FORK_RPC_URL")
+        .expect("CHEATNET_FORK_RPC_URL has to point to a Sepolia testnet node");
+    let predeployed_contracts = Utf8PathBuf::from("predeployed-contracts");
+
+    CheatnetState::new(
+        &predeployed_contracts,
+        Some(ForkConfig {
+            url: Url::parse(&url).unwrap(),
+            block_number: BlockNumber(50_000),
+            cache_dir: None,
+        }),
+    )
+}
+
+#[test]
+fn fork_simple() {
+    let mut state = create_forked_cheatnet_state();
+    let eth_address = ContractAddress(patricia_key!(ETH_CONTRACT_ADDRESS));
+
+    let name = state
+        .load(eth_address, &map_entry_address("ERC20_name", &[]))
+        .unwrap();
+
+    assert_eq!(name, felt_from_short_string("Ether"));
+}
+
+#[test]
+fn fork_not_deployed_contract() {
+    let mut state = create_forked_cheatnet_state();
+
+    let value = state
+        .load(ContractAddress::from(1234_u128), &Felt252::from(1))
+        .unwrap();
+
+    assert_eq!(value, Felt252::from(0));
+}
+
+#[test]
+fn store_overrides_forked_value() {
+    let mut forked_state = create_forked_cheatnet_state();
+    let eth_address = ContractAddress(patricia_key!(ETH_CONTRACT_ADDRESS));
+    let key = map_entry_address("ERC20_name", &[]);
+
+    forked_state
+        .store(eth_address, &key, &felt_from_short_string("Fake"))
+        .unwrap();
+
+    assert_eq!(
+        forked_state.load(eth_address, &key).unwrap(),
+        felt_from_short_string("Fake")
+    );
+}
+
+#[test]
+fn call_forked_contract_from_local_contract() {
+    let mut state = create_forked_cheatnet_state();
+    let strk_address = ContractAddress(patricia_key!(STRK_CONTRACT_ADDRESS));
+    let contract_address = deploy_contract(&mut state, "Erc20NameChecker", &[]);
+
+    // The class of the forked contract is only declared on the network
+    let output = call_contract(
+        &contract_address,
+        &felt_selector_from_name("get_name"),
+        &[contract_address_to_felt(strk_address)],
+        &mut state,
+    )
+    .unwrap();
+
+    assert_success!(output, vec![felt_from_short_string("Starknet Token")]);
+}
+
+#[test]
+fn call_forked_cairo_0_contract_fails() {
+    let mut state = create_forked_cheatnet_state();
+    let eth_address = ContractAddress(patricia_key!(ETH_CONTRACT_ADDRESS));
+    let contract_address = deploy_contract(&mut state, "Erc20NameChecker", &[]);
+
+    // The ETH contract is a Cairo 0 proxy, the call fails instead of aborting the test run
+    let output = call_contract(
+        &contract_address,
+        &felt_selector_from_name("get_name"),
+        &[contract_address_to_felt(eth_address)],
+        &mut state,
+    )
+    .unwrap();
+
+    assert!(matches!(output, CallContractOutput::Panic { .. }));
+}
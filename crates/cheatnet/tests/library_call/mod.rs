@@ -0,0 +1,68 @@
+use crate::{
+    assert_success,
+    common::{deploy_contract, get_contracts, state::create_cheatnet_state},
+};
+use cairo_felt::Felt252;
+use cheatnet::{
+    conversions::{
+        class_hash_to_felt, contract_address_to_felt, felt_from_short_string,
+        felt_selector_from_name, map_entry_address,
+    },
+    rpc::call_contract,
+    CheatnetState,
+};
+use starknet_api::core::ContractAddress;
+
+fn load_var(state: &mut CheatnetState, address: ContractAddress, var_name: &str) -> Felt252 {
+    state
+        .load(address, &map_entry_address(var_name, &[]))
+        .unwrap()
+}
+
+// A library call executes the code of the given class as a part of the calling contract:
+// storage writes land in the caller's storage, `get_contract_address` returns the calling contract
+// and `get_caller_address` returns the caller of the calling contract. No address is derived
+// for the class, it doesn't have to be deployed at all.
+#[test]
+fn library_call_writes_to_caller_storage() {
+    let mut state = create_cheatnet_state();
+    let contracts = get_contracts();
+
+    let logic_class_hash = state
+        .declare(&felt_from_short_string("LibraryCallLogic"), &contracts)
+        .unwrap();
+    let logic_address = state.deploy(&logic_class_hash, &[]).unwrap();
+    let proxy_address = deploy_contract(&mut state, "LibraryCallProxy", &[]);
+
+    let output = call_contract(
+        &proxy_address,
+        &felt_selector_from_name("set_value_with_lib_call"),
+        &[class_hash_to_felt(logic_class_hash), Felt252::from(42)],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![]);
+
+    assert_eq!(
+        load_var(&mut state, proxy_address, "value"),
+        Felt252::from(42)
+    );
+    assert_eq!(
+        load_var(&mut state, proxy_address, "seen_contract_address"),
+        contract_address_to_felt(proxy_address)
+    );
+    assert_eq!(
+        load_var(&mut state, proxy_address, "seen_caller_address"),
+        contract_address_to_felt(state.test_account_address())
+    );
+
+    // The deployed instance of the logic class is left untouched
+    let output = call_contract(
+        &logic_address,
+        &felt_selector_from_name("get_value"),
+        &[],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![Felt252::from(0)]);
+}
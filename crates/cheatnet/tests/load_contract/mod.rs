@@ -0,0 +1,36 @@
+use crate::common::{get_contracts, state::create_cheatnet_state};
+use camino::Utf8Path;
+use cheatnet::conversions::felt_from_short_string;
+use cheatnet::scarb::load_contract;
+use std::collections::HashMap;
+
+#[test]
+fn load_contract_from_package() {
+    let contract = load_contract(Utf8Path::new("tests/contracts"), "HelloStarknet").unwrap();
+    let expected = get_contracts().remove("HelloStarknet").unwrap();
+    assert_eq!(contract.sierra, expected.sierra);
+    assert_eq!(contract.casm, expected.casm);
+
+    let mut state = create_cheatnet_state();
+    let contracts = HashMap::from([("HelloStarknet".to_string(), contract)]);
+    let class_hash = state
+        .declare(&felt_from_short_string("HelloStarknet"), &contracts)
+        .unwrap();
+    state.deploy(&class_hash, &[]).unwrap();
+}
+
+#[test]
+fn load_missing_contract() {
+    let err = load_contract(Utf8Path::new("tests/contracts"), "NotExistingContract").unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Failed to find contract NotExistingContract"));
+}
+
+#[test]
+fn load_contract_from_unbuilt_package() {
+    let err = load_contract(Utf8Path::new("tests/not_a_package"), "HelloStarknet").unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Failed to read tests/not_a_package/target/dev"));
+}
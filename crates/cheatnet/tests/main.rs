@@ -1,16 +1,24 @@
+mod block_context;
 mod cheatcodes;
 pub(crate) mod common;
+mod crypto;
+#[cfg(feature = "forking-tests")]
+mod forking;
+mod library_call;
+mod load_contract;
+mod prints;
+mod retdata;
+#[cfg(feature = "tracing")]
+mod traces;
 
-// Build testing contracts before executing the tests
+// Build testing contracts before executing the tests, unless they are already built
 #[cfg(test)]
 #[ctor::ctor]
 fn init() {
     use camino::Utf8PathBuf;
     let contracts_path = Utf8PathBuf::from("tests").join("contracts");
 
-    std::process::Command::new("scarb")
-        .current_dir(contracts_path)
-        .arg("build")
-        .output()
-        .unwrap();
+    if let Err(err) = common::scarb::build_if_changed(&contracts_path) {
+        panic!("Failed to build testing contracts: {err:#}");
+    }
 }
@@ -0,0 +1,57 @@
+use crate::{
+    assert_success,
+    common::{deploy_contract, state::create_cheatnet_state},
+};
+use cairo_felt::Felt252;
+use cheatnet::{
+    conversions::{felt_from_short_string, felt_selector_from_name},
+    rpc::call_contract,
+};
+
+#[test]
+fn take_prints_of_contract() {
+    let mut state = create_cheatnet_state();
+    state.capture_prints();
+    let contract_address = deploy_contract(&mut state, "PrintChecker", &[]);
+    let selector = felt_selector_from_name("print_value");
+
+    let output = call_contract(
+        &contract_address,
+        &selector,
+        &[Felt252::from(1)],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![Felt252::from(1)]);
+    assert_eq!(state.take_prints(), vec!["Value is", "0x1"]);
+
+    // Taken prints are not returned again
+    assert!(state.take_prints().is_empty());
+
+    call_contract(
+        &contract_address,
+        &selector,
+        &[felt_from_short_string("hello")],
+        &mut state,
+    )
+    .unwrap();
+    assert_eq!(state.take_prints(), vec!["Value is", "hello"]);
+}
+
+#[test]
+fn prints_are_not_captured_by_default() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "PrintChecker", &[]);
+
+    let output = call_contract(
+        &contract_address,
+        &felt_selector_from_name("print_value"),
+        &[Felt252::from(1)],
+        &mut state,
+    )
+    .unwrap();
+    assert_success!(output, vec![Felt252::from(1)]);
+
+    // Written to stdout instead
+    assert!(state.take_prints().is_empty());
+}
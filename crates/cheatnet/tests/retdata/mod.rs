@@ -0,0 +1,53 @@
+use crate::common::{deploy_contract, recover_data, state::create_cheatnet_state};
+use cairo_felt::Felt252;
+use cheatnet::{
+    conversions::{decode_retdata, felt_selector_from_name, DecodedValue, OutputType},
+    rpc::call_contract,
+};
+
+#[test]
+fn decode_u256_retdata() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "RetdataChecker", &[]);
+
+    let output = call_contract(
+        &contract_address,
+        &felt_selector_from_name("get_u256"),
+        &[],
+        &mut state,
+    )
+    .unwrap();
+
+    let output_type = OutputType::from_abi_type("core::integer::u256").unwrap();
+    assert_eq!(
+        decode_retdata(&output_type, &recover_data(output)).unwrap(),
+        DecodedValue::U256 {
+            low: 123,
+            high: 456
+        }
+    );
+}
+
+#[test]
+fn decode_array_retdata() {
+    let mut state = create_cheatnet_state();
+    let contract_address = deploy_contract(&mut state, "RetdataChecker", &[]);
+
+    let output = call_contract(
+        &contract_address,
+        &felt_selector_from_name("get_array"),
+        &[Felt252::from(3)],
+        &mut state,
+    )
+    .unwrap();
+
+    let output_type = OutputType::from_abi_type("core::array::Array::<core::felt252>").unwrap();
+    assert_eq!(
+        decode_retdata(&output_type, &recover_data(output)).unwrap(),
+        DecodedValue::Array(vec![
+            DecodedValue::Felt252(Felt252::from(0)),
+            DecodedValue::Felt252(Felt252::from(10)),
+            DecodedValue::Felt252(Felt252::from(20)),
+        ])
+    );
+}
@@ -0,0 +1,67 @@
+use crate::common::{deploy_contract, state::create_cheatnet_state};
+use cairo_felt::Felt252;
+use cheatnet::{conversions::felt_selector_from_name, rpc::call_contract};
+use starknet_api::core::ContractAddress;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+// Records names of the created spans and the number of emitted events
+#[derive(Default)]
+struct TraceRecorder {
+    span_names: Arc<Mutex<Vec<&'static str>>>,
+    events_count: Arc<AtomicU64>,
+    last_span_id: AtomicU64,
+}
+
+impl Subscriber for TraceRecorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        self.span_names.lock().unwrap().push(span.metadata().name());
+        Id::from_u64(self.last_span_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {
+        self.events_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn deploy_and_call_are_traced() {
+    let recorder = TraceRecorder::default();
+    let span_names = recorder.span_names.clone();
+    let events_count = recorder.events_count.clone();
+
+    tracing::subscriber::with_default(recorder, || {
+        let mut state = create_cheatnet_state();
+        let contract_address = deploy_contract(&mut state, "HelloStarknet", &[]);
+        state.start_prank(contract_address, ContractAddress::from(123_u8));
+
+        call_contract(
+            &contract_address,
+            &felt_selector_from_name("increase_balance"),
+            &[Felt252::from(42)],
+            &mut state,
+        )
+        .unwrap();
+    });
+
+    let span_names = span_names.lock().unwrap();
+    assert!(span_names.contains(&"deploy"));
+    assert!(span_names.contains(&"prank"));
+    assert!(span_names.contains(&"call"));
+    // Outcomes of the deployment and the call
+    assert!(events_count.load(Ordering::Relaxed) >= 2);
+}
@@ -18,6 +18,7 @@ use cairo_vm::vm::vm_core::VirtualMachine;
 use cheatnet::rpc::{call_contract, CallContractOutput};
 use cheatnet::{
     cheatcodes::{CheatcodeError, ContractArtifacts, EnhancedHintError},
+    conversions::contract_address_from_felt,
     CheatnetState,
 };
 use num_traits::ToPrimitive;
@@ -319,8 +320,8 @@ impl CairoHintProcessor<'_> {
                             .expect("Failed to insert deployed contract address");
                         Ok(())
                     }
-                    Err(CheatcodeError::Recoverable(panic_data)) => {
-                        write_cheatcode_panic(&mut buffer, &panic_data);
+                    Err(CheatcodeError::Recoverable(failure)) => {
+                        write_cheatcode_panic(&mut buffer, &failure.panic_data());
                         Ok(())
                     }
                     Err(CheatcodeError::Unrecoverable(err)) => Err(err),
@@ -349,18 +350,27 @@ impl CairoHintProcessor<'_> {
                 Ok(())
             }
             "get_class_hash" => {
-                let contract_address = contract_address_from_felt252(&inputs[0])?;
+                let class_hash =
+                    contract_address_from_felt(&inputs[0]).and_then(|contract_address| {
+                        self.cheatnet_state.get_class_hash(contract_address)
+                    });
 
-                match self.cheatnet_state.get_class_hash(contract_address) {
+                match class_hash {
                     Ok(class_hash) => {
                         let felt_class_hash = stark_felt_to_felt(class_hash.0);
 
+                        buffer
+                            .write(Felt252::from(0))
+                            .expect("Failed to insert error code");
                         buffer
                             .write(felt_class_hash)
                             .expect("Failed to insert contract class hash");
                         Ok(())
                     }
-                    Err(CheatcodeError::Recoverable(_)) => unreachable!(),
+                    Err(CheatcodeError::Recoverable(failure)) => {
+                        write_cheatcode_panic(&mut buffer, &failure.panic_data());
+                        Ok(())
+                    }
                     Err(CheatcodeError::Unrecoverable(err)) => Err(err),
                 }
             }
@@ -444,28 +454,28 @@ fn execute_call_contract(mut buffer: MemBuffer, cheatnet_state: &mut CheatnetSta
     let gas_counter = buffer.next_usize().unwrap();
 
     let contract_address = buffer.next_felt252().unwrap().into_owned();
-    let contract_address = ContractAddress(
-        PatriciaKey::try_from(
-            StarkFelt::new(contract_address.to_be_bytes()).expect("Felt conversion failed"),
-        )
-        .expect("PatriciaKey failed"),
-    );
-
     let entry_point_selector = buffer.next_felt252().unwrap().into_owned();
 
     let calldata = buffer.next_arr().unwrap();
 
-    let call_result = call_contract(
-        &contract_address,
-        &entry_point_selector,
-        &calldata,
-        cheatnet_state,
-    )
-    .unwrap_or_else(|err| panic!("Transaction execution error: {err}"));
-
-    let (result, exit_code) = match call_result {
-        CallContractOutput::Success { ret_data } => (ret_data, 0),
-        CallContractOutput::Panic { panic_data } => (panic_data, 1),
+    // Calls to invalid addresses panic like calls which failed in the called contract
+    let (result, exit_code) = match contract_address_from_felt(&contract_address) {
+        Ok(contract_address) => {
+            let call_result = call_contract(
+                &contract_address,
+                &entry_point_selector,
+                &calldata,
+                cheatnet_state,
+            )
+            .unwrap_or_else(|err| panic!("Transaction execution error: {err}"));
+
+            match call_result {
+                CallContractOutput::Success { ret_data } => (ret_data, 0),
+                CallContractOutput::Panic { panic_data } => (panic_data, 1),
+            }
+        }
+        Err(CheatcodeError::Recoverable(failure)) => (failure.panic_data(), 1),
+        Err(CheatcodeError::Unrecoverable(err)) => panic!("Transaction execution error: {err}"),
     };
 
     buffer.write(gas_counter).unwrap();
@@ -493,9 +503,3 @@ fn write_cheatcode_panic(buffer: &mut MemBuffer, panic_data: &[Felt252]) {
         .write_data(panic_data.iter())
         .expect("Failed to insert error in memory");
 }
-
-fn contract_address_from_felt252(felt: &Felt252) -> Result<ContractAddress, EnhancedHintError> {
-    Ok(ContractAddress(PatriciaKey::try_from(felt_to_stark_felt(
-        felt,
-    ))?))
-}
@@ -1,6 +1,6 @@
 use crate::integration::common::runner::Contract;
 use crate::integration::common::running_tests::run_test_case;
-use crate::{assert_passed, test_case};
+use crate::{assert_case_output_contains, assert_failed, assert_passed, test_case};
 use indoc::indoc;
 use std::path::Path;
 
@@ -90,3 +90,29 @@ fn get_class_hash_replace_class() {
 
     assert_passed!(result);
 }
+
+#[test]
+fn get_class_hash_not_deployed() {
+    let test = test_case!(indoc!(
+        r#"
+            use traits::TryInto;
+            use option::OptionTrait;
+            use starknet::Felt252TryIntoContractAddress;
+            use snforge_std::get_class_hash;
+
+            #[test]
+            fn test_get_class_hash_not_deployed() {
+                get_class_hash(123.try_into().unwrap());
+            }
+        "#
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_case_output_contains!(
+        result,
+        "test_get_class_hash_not_deployed",
+        "Contract not deployed"
+    );
+    assert_failed!(result);
+}
@@ -175,7 +175,27 @@ fn stop_mock_call(contract_address: ContractAddress, function_name: felt252) {
 fn get_class_hash(contract_address: ContractAddress) -> ClassHash {
     let contract_address_felt: felt252 = contract_address.into();
 
-    // Expecting a buffer with one felt252, being the class hash.
+    // Expecting a buffer with an exit code, followed by the class hash or the panic data.
     let buf = cheatcode::<'get_class_hash'>(array![contract_address_felt].span());
-    (*buf[0]).try_into().expect('Invalid class hash value')
+    let exit_code = *buf[0];
+
+    if exit_code == 0 {
+        (*buf[1]).try_into().expect('Invalid class hash value')
+    } else {
+        let panic_data_len_felt = *buf[1];
+        let panic_data_len = panic_data_len_felt.try_into().unwrap();
+        let mut panic_data = array![];
+
+        let offset = 2;
+        let mut i = offset;
+        loop {
+            if panic_data_len + offset == i {
+                break ();
+            }
+            panic_data.append(*buf[i]);
+            i += 1;
+        };
+
+        panic(panic_data)
+    }
 }